@@ -1,12 +1,31 @@
 use service_kit::api_dto;
 
 /// Parameters for adding two numbers.
-#[api_dto]
+#[api_dto(example = json!({ "a": 1.5, "b": 2.5 }))]
 pub struct AddParams {
     pub a: f64,
     pub b: f64,
 }
 
+/// Query parameters for searching products.
+#[api_dto]
+pub struct ProductSearch {
+    /// Text that product names must contain.
+    #[validate(length(min = 1))]
+    #[example = "Example"]
+    pub q: String,
+    /// Only return products in this category.
+    pub category_id: Option<String>,
+    /// Zero-based page index, 20 products per page.
+    #[serde(default)]
+    pub page: u32,
+    /// Upper bound on the product price.
+    pub max_price: Option<f64>,
+    /// Sort order; `price` sorts by ascending price.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub sort: String,
+}
+
 /// Represents a product in the system.
 #[api_dto]
 pub struct Product {
@@ -29,8 +48,14 @@ pub struct Category {
 /// DTO for updating a product.
 #[api_dto]
 pub struct ProductUpdate {
+    #[validate(length(min = 1, max = 64))]
     pub name: Option<String>,
+    /// New product code; codes always start with `P-`.
+    #[validate(pattern = "^P-")]
+    #[example = "P-12345"]
+    pub product_code: Option<String>,
     pub description: Option<String>,
+    #[validate(range(min = 0.0))]
     pub price: Option<f64>,
 }
 
@@ -40,9 +65,115 @@ pub struct Greeting {
     pub message: String,
 }
 
+/// A newsletter subscription submitted as an urlencoded form.
+#[api_dto]
+pub struct Subscription {
+    pub email: String,
+    #[serde(default)]
+    pub weekly: bool,
+    /// Postal code used to pick the regional edition.
+    pub zip: Option<String>,
+}
+
+/// A file received through a multipart upload.
+#[api_dto]
+pub struct UploadedFile {
+    pub field: String,
+    pub file_name: Option<String>,
+    pub size: u64,
+}
+
+/// Summary of a multipart upload.
+#[api_dto]
+pub struct UploadSummary {
+    pub product_id: String,
+    pub files: Vec<UploadedFile>,
+}
+
 /// This is a sample DTO with a different naming convention.
 #[api_dto(rename_all = "snake_case")]
 pub struct LegacyData {
     pub user_id: String,
     pub transaction_amount: f64,
 }
+
+/// Error payload returned by failing endpoints.
+#[api_dto]
+pub struct ErrorBody {
+    pub code: String,
+    pub message: String,
+}
+
+/// A single price update pushed over the price stream.
+#[api_dto]
+pub struct PriceTick {
+    pub product_id: String,
+    pub sequence: u32,
+    pub price: f64,
+}
+
+/// Lifecycle status of a product.
+#[api_dto]
+pub enum ProductStatus {
+    Draft,
+    Active,
+    Discontinued,
+}
+
+/// Query parameters for the paginated product listing.
+#[api_dto]
+pub struct ProductPageQuery {
+    /// Zero-based page index.
+    #[serde(default)]
+    pub page: u32,
+    /// Only return products with this status.
+    pub status: Option<ProductStatus>,
+}
+
+/// One page of a paginated listing.
+#[api_dto]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub page: u32,
+    pub total: u64,
+}
+
+/// A pricing rule; the `type` field selects the kind of rule.
+#[api_dto]
+#[serde(tag = "type")]
+pub enum PriceRule {
+    /// Subtracts a fixed amount from the price.
+    FixedDiscount { amount: f64 },
+    /// Takes a percentage off the price, optionally capped.
+    PercentOff { percent: f64, max_discount: Option<f64> },
+}
+
+/// A node of the category tree.
+#[api_dto]
+pub struct CategoryTree {
+    pub id: String,
+    pub name: String,
+    /// Subcategories, each a tree of its own.
+    pub children: Vec<CategoryTree>,
+}
+
+/// A set of products sold together.
+#[api_dto]
+pub struct Bundle {
+    pub name: String,
+    pub items: Vec<BundleItem>,
+}
+
+/// An entry of a bundle; either a product or a nested bundle.
+#[api_dto]
+pub struct BundleItem {
+    pub product_id: Option<String>,
+    pub bundle: Option<Bundle>,
+}
+
+/// A titled section of the catalog with the first page of its categories.
+#[api_dto]
+pub struct CatalogSection {
+    pub title: String,
+    pub categories: Page<Category>,
+}
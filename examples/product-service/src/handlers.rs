@@ -1,11 +1,19 @@
-use axum::{extract::{Path, Query}, Json, response::IntoResponse};
+use axum::{body::Bytes, extract::{Multipart, Path, Query, State}, http::{HeaderMap, StatusCode}, Form, Json, response::{sse::{Event, Sse}, IntoResponse}};
+use futures::stream::{self, Stream};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use axum_extra::{headers::UserAgent, TypedHeader};
 use service_kit::api;
-use crate::dtos::{AddParams, Product, ProductUpdate};
+use crate::dtos::{
+    AddParams, Bundle, BundleItem, CategoryTree, ErrorBody, Greeting, Page, PriceRule, PriceTick, Product, ProductPageQuery, ProductSearch, ProductStatus,
+    ProductUpdate, Subscription, UploadSummary, UploadedFile,
+};
+use crate::state::AppState;
 
 /// Get a product by its ID
 ///
 /// This endpoint retrieves a specific product from the database using its unique identifier.
-#[api(GET, "/v1/products/{id}")]
+#[api(GET, "/v1/products/{id}", params(id = "Product identifier"))]
 pub async fn get_product(Path(id): Path<String>) -> impl IntoResponse {
     let sample_product = Product {
         id,
@@ -24,14 +32,14 @@ pub async fn get_product(Path(id): Path<String>) -> impl IntoResponse {
 
 /// Add two numbers
 /// This endpoint is a simple calculator to add two numbers.
-#[api(GET, "/v1/add")]
+#[api(GET, "/v1/add", deprecated)]
 pub async fn add(Query(params): Query<AddParams>) -> Json<f64> {
     Json(params.a + params.b)
 }
 
 /// List all products
 /// This endpoint returns a list of all products in the system.
-#[api(GET, "/v1/products")]
+#[api(GET, "/v1/products", tags = ["catalog"])]
 pub async fn list_products() -> Json<Vec<Product>> {
     Json(vec![
         Product {
@@ -61,17 +69,83 @@ pub async fn list_products() -> Json<Vec<Product>> {
     ])
 }
 
+/// Search products
+/// This endpoint filters products by name, category and price.
+#[api(GET, "/v1/products/search", tags = ["catalog"], operation_id = "catalog.search")]
+pub async fn search_products(Query(search): Query<ProductSearch>) -> Json<Vec<Product>> {
+    let mut products: Vec<Product> = list_products()
+        .await
+        .0
+        .into_iter()
+        .filter(|p| p.name.contains(&search.q))
+        .filter(|p| search.category_id.as_ref().is_none_or(|c| &p.category.id == c))
+        .filter(|p| search.max_price.is_none_or(|max| p.price <= max))
+        .skip(search.page as usize * 20)
+        .collect();
+    if search.sort == "price" {
+        products.sort_by(|a, b| a.price.total_cmp(&b.price));
+    }
+    Json(products)
+}
+
+/// List prices by product
+/// This endpoint maps every product ID to its current list price.
+#[api(GET, "/v1/products/prices", tags = ["catalog"])]
+pub async fn list_prices() -> Json<HashMap<String, f64>> {
+    let products = list_products().await.0;
+    Json(products.into_iter().map(|p| (p.id, p.price)).collect())
+}
+
+/// List products page by page
+/// This endpoint returns 20 products per page, optionally filtered by status.
+#[api(GET, "/v1/products/paged", tags = ["catalog"])]
+pub async fn list_products_paged(Query(query): Query<ProductPageQuery>) -> Json<Page<Product>> {
+    // Every mock product is active.
+    let products = match query.status {
+        None | Some(ProductStatus::Active) => list_products().await.0,
+        Some(_) => Vec::new(),
+    };
+    let total = products.len() as u64;
+    Json(Page {
+        items: products.into_iter().skip(query.page as usize * 20).take(20).collect(),
+        page: query.page,
+        total,
+    })
+}
+
+/// Quote a product price under a pricing rule
+/// This endpoint applies a fixed or percentage discount to the product's price.
+#[api(
+    POST,
+    "/v1/products/{id}/price-quote",
+    request_example = json!({ "type": "percentOff", "percent": 10.0, "maxDiscount": 5.0 }),
+    response_example = json!({ "productId": "prod-001", "sequence": 0, "price": 94.99 })
+)]
+pub async fn quote_price(Path(id): Path<String>, Json(rule): Json<PriceRule>) -> Json<PriceTick> {
+    let list_price = 99.99;
+    let price = match rule {
+        PriceRule::FixedDiscount { amount } => list_price - amount,
+        PriceRule::PercentOff { percent, max_discount } => {
+            let discount = list_price * percent / 100.0;
+            list_price - max_discount.map_or(discount, |max| discount.min(max))
+        }
+    };
+    Json(PriceTick { product_id: id, sequence: 0, price })
+}
+
 /// Update a product
 /// This endpoint updates a product's information.
 #[api(PATCH, "/v1/products/{id}")]
 pub async fn update_product(
+    /// Product identifier
     Path(id): Path<String>,
+    /// Fields to change; omitted fields are left untouched
     Json(payload): Json<ProductUpdate>,
 ) -> Json<Product> {
     // In a real implementation, you would fetch the product, update it, and save it.
     Json(Product {
         id,
-        product_code: "P-UPDATED".to_string(),
+        product_code: payload.product_code.unwrap_or_else(|| "P-UPDATED".to_string()),
         name: payload.name.unwrap_or_else(|| "Old Name".to_string()),
         description: payload.description,
         price: payload.price.unwrap_or(0.0),
@@ -83,10 +157,234 @@ pub async fn update_product(
     })
 }
 
+/// Get the category tree
+/// This endpoint returns every category with its nested subcategories.
+#[api(GET, "/v1/categories/tree", tags = ["catalog"])]
+pub async fn get_category_tree() -> Json<CategoryTree> {
+    let leaf = |id: &str, name: &str| CategoryTree { id: id.to_string(), name: name.to_string(), children: Vec::new() };
+    Json(CategoryTree {
+        id: "cat-root".to_string(),
+        name: "All".to_string(),
+        children: vec![
+            CategoryTree {
+                id: "cat-01".to_string(),
+                name: "Electronics".to_string(),
+                children: vec![leaf("cat-03", "Phones"), leaf("cat-04", "Laptops")],
+            },
+            leaf("cat-02", "Books"),
+        ],
+    })
+}
+
+/// Get a bundle
+/// This endpoint returns a bundle whose items are products or nested bundles.
+#[api(GET, "/v1/bundles/{id}")]
+pub async fn get_bundle(Path(id): Path<String>) -> Json<Bundle> {
+    let product = |id: &str| BundleItem { product_id: Some(id.to_string()), bundle: None };
+    Json(Bundle {
+        name: id,
+        items: vec![
+            product("prod-001"),
+            BundleItem {
+                product_id: None,
+                bundle: Some(Bundle { name: "accessories".to_string(), items: vec![product("prod-002")] }),
+            },
+        ],
+    })
+}
+
+/// Get a product within a category
+/// This endpoint looks up a product by its position inside a category.
+#[api(GET, "/v1/categories/{category_id}/products/{index}")]
+pub async fn get_category_product(
+    Path((category_id, index)): Path<(String, u32)>,
+) -> Json<Product> {
+    Json(Product {
+        id: format!("{}-{}", category_id, index),
+        product_code: format!("P-{:05}", index),
+        name: format!("Product #{}", index),
+        description: None,
+        price: 9.99,
+        category: crate::dtos::Category {
+            id: category_id,
+            name: "Electronics".to_string(),
+            parent: None,
+        },
+    })
+}
+
+/// Get a product review
+/// This endpoint reads both path segments from a tuple without destructuring it.
+#[api(GET, "/v1/products/{id}/reviews/{review_id}")]
+pub async fn get_product_review(path: Path<(String, u32)>) -> Json<Greeting> {
+    let (id, review_id) = path.0;
+    Json(Greeting {
+        message: format!("Review {} of {}", review_id, id),
+    })
+}
+
+/// Find a product by its product code
+/// This endpoint responds with 404 when no product has the given code.
+#[api(GET, "/v1/products/by-code/{code}")]
+pub async fn find_product_by_code(Path(code): Path<String>) -> Option<Json<Product>> {
+    (code == "P-12345").then(|| {
+        Json(Product {
+            id: "prod-001".to_string(),
+            product_code: code,
+            name: "Example Product 1".to_string(),
+            description: None,
+            price: 99.99,
+            category: crate::dtos::Category {
+                id: "cat-01".to_string(),
+                name: "Electronics".to_string(),
+                parent: None,
+            },
+        })
+    })
+}
+
+/// Delete a product
+/// Products that are still referenced by an order cannot be deleted.
+#[api(
+    DELETE,
+    "/v1/products/{id}",
+    responses((404, "Product not found", ErrorBody), (409, "Product is still referenced", ErrorBody)),
+    security = "bearer"
+)]
+pub async fn delete_product(
+    Path(id): Path<String>,
+) -> Result<Json<Greeting>, (StatusCode, Json<ErrorBody>)> {
+    match id.as_str() {
+        "prod-001" => Err((
+            StatusCode::CONFLICT,
+            Json(ErrorBody {
+                code: "product_in_use".to_string(),
+                message: format!("Product {} is still referenced by an order", id),
+            }),
+        )),
+        _ if id.starts_with("prod-") => Ok(Json(Greeting {
+            message: format!("Deleted {}", id),
+        })),
+        _ => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorBody {
+                code: "not_found".to_string(),
+                message: format!("Product {} does not exist", id),
+            }),
+        )),
+    }
+}
+
+/// Get the tenant's catalog name
+/// This endpoint reads the tenant from the `x-tenant-id` header.
+#[api(
+    GET,
+    "/v1/tenant/catalog",
+    headers(x_tenant_id: String, x_request_id: Option<String>),
+    params(x_tenant_id = "Tenant whose catalog is returned", x_request_id = "Correlation id for tracing")
+)]
+pub async fn get_tenant_catalog(headers: HeaderMap) -> Json<Greeting> {
+    let tenant = headers
+        .get("x-tenant-id")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    Json(Greeting {
+        message: format!("Catalog of tenant {}", tenant),
+    })
+}
+
+/// Echo the caller's user agent
+/// This endpoint decodes the standard `User-Agent` header.
+#[api(GET, "/v1/whoami")]
+pub async fn whoami(TypedHeader(agent): TypedHeader<UserAgent>) -> Json<Greeting> {
+    Json(Greeting {
+        message: format!("Hello, {}", agent.as_str()),
+    })
+}
+
+/// Get service info
+/// This endpoint greets the caller using the shared application state.
+#[api(GET, "/v1/info", tags = ["ops"], extensions(x_internal = true, x_lint_allow = ["missing-error-response"]))]
+pub async fn service_info(State(state): State<AppState>) -> Json<Greeting> {
+    Json(Greeting {
+        message: format!("Welcome to {}", state.service_name),
+    })
+}
+
+/// Subscribe to the newsletter
+/// This endpoint accepts an urlencoded form.
+#[api(POST, "/v1/newsletter")]
+pub async fn subscribe(Form(subscription): Form<Subscription>) -> Json<Greeting> {
+    let cadence = if subscription.weekly { "weekly" } else { "monthly" };
+    let edition = subscription.zip.map(|zip| format!(" for zip {}", zip)).unwrap_or_default();
+    Json(Greeting {
+        message: format!("Subscribed {} to the {} newsletter{}", subscription.email, cadence, edition),
+    })
+}
+
+/// Upload product images
+/// This endpoint streams every multipart field and reports the received sizes.
+#[api(POST, "/v1/products/{id}/images")]
+pub async fn upload_product_images(
+    Path(id): Path<String>,
+    mut multipart: Multipart,
+) -> Result<Json<UploadSummary>, (StatusCode, Json<ErrorBody>)> {
+    let bad_request = |e: axum::extract::multipart::MultipartError| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorBody { code: "invalid_multipart".to_string(), message: e.body_text() }),
+        )
+    };
+    let mut files = Vec::new();
+    while let Some(mut field) = multipart.next_field().await.map_err(bad_request)? {
+        let name = field.name().unwrap_or_default().to_string();
+        let file_name = field.file_name().map(str::to_string);
+        let mut size = 0;
+        while let Some(chunk) = field.chunk().await.map_err(bad_request)? {
+            size += chunk.len() as u64;
+        }
+        files.push(UploadedFile { field: name, file_name, size });
+    }
+    Ok(Json(UploadSummary { product_id: id, files }))
+}
+
+/// Replace a product thumbnail
+/// This endpoint accepts the raw image bytes.
+#[api(PUT, "/v1/products/{id}/thumbnail")]
+pub async fn put_product_thumbnail(Path(id): Path<String>, image: Bytes) -> Json<Greeting> {
+    Json(Greeting {
+        message: format!("Stored {} bytes for {}", image.len(), id),
+    })
+}
+
+/// Import products from CSV
+/// This endpoint accepts a plain-text CSV document, one product per line.
+#[api(POST, "/v1/products/import")]
+pub async fn import_products(csv: String) -> Json<Greeting> {
+    let count = csv.lines().skip(1).filter(|line| !line.trim().is_empty()).count();
+    Json(Greeting {
+        message: format!("Imported {} products", count),
+    })
+}
+
+/// Stream price updates
+/// This endpoint pushes one `price` event per tick as Server-Sent Events.
+#[api(GET, "/v1/products/{id}/price-stream", responses((200, "One PriceTick per event", PriceTick)))]
+pub async fn stream_prices(Path(id): Path<String>) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let ticks = (1..=3u32).map(move |sequence| {
+        let tick = PriceTick { product_id: id.clone(), sequence, price: 99.99 + sequence as f64 };
+        Ok(Event::default().event("price").json_data(tick).expect("PriceTick serializes"))
+    });
+    Sse::new(stream::iter(ticks))
+}
+
+/// A dummy function to ensure the linker includes this module.
+pub fn load() {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::dtos::LegacyData;
+    use crate::dtos::{Category, LegacyData};
     use serde_json;
 
     #[test]
@@ -125,7 +423,118 @@ mod tests {
         assert!(json_value.get("transaction_amount").is_some());
         assert!(json_value.get("userId").is_none());
     }
-}
 
-/// A dummy function to ensure the linker includes this module.
-pub fn load() {}
+    #[test]
+    fn test_spec_lint_checks_registered_handlers_and_allow_extensions() {
+        use service_kit::spec_lint::{lint_openapi, LintConfig, LintRule, Severity};
+
+        let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+        let report = lint_openapi(&openapi, &LintConfig::default()).unwrap();
+        assert!(!report.has_errors(), "{}", report);
+        let found = |rule: LintRule, location: &str| {
+            report.findings.iter().find(|finding| finding.rule == rule && finding.location == location).map(|finding| finding.severity)
+        };
+        assert_eq!(found(LintRule::MissingErrorResponse, "GET /v1/add"), Some(Severity::Warning));
+        // `/v1/info` opts out with `extensions(x_lint_allow = ["missing-error-response"])`.
+        assert_eq!(found(LintRule::MissingErrorResponse, "GET /v1/info"), None);
+        assert_eq!(found(LintRule::EmptyParameterDescription, "GET /v1/products/{id}"), None);
+    }
+
+    /// Writes generated files under `target/<name>` so repeated runs reuse the build cache.
+    fn write_generated(name: &str, files: &[(&str, String)]) -> std::path::PathBuf {
+        let workspace = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        let dir = workspace.join("target").join(name);
+        for (path, contents) in files {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_rust_client_crate_compiles() {
+        let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+        let spec = serde_json::to_value(&openapi).unwrap();
+        let mut files = service_kit::codegen::rust_client::generate_client(&spec, "product-client");
+        // A standalone crate, resolved against the workspace lockfile so the check needs no new downloads.
+        for (path, contents) in files.iter_mut() {
+            if *path == "Cargo.toml" {
+                contents.push_str("\n[workspace]\n");
+            }
+        }
+        let lockfile = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/../../Cargo.lock")).unwrap();
+        files.push(("Cargo.lock", lockfile));
+        let dir = write_generated("generated-rust-client", &files);
+
+        let output = std::process::Command::new(env!("CARGO"))
+            .args(["check", "--quiet", "--manifest-path"])
+            .arg(dir.join("Cargo.toml"))
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    #[test]
+    fn test_typescript_client_type_checks() {
+        let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+        let spec = serde_json::to_value(&openapi).unwrap();
+        let client = service_kit::codegen::typescript_client::generate_client(&spec);
+        let dir = write_generated("generated-ts-client", &[("client.ts", client)]);
+
+        let tsc = std::process::Command::new("tsc")
+            .args(["--noEmit", "--strict", "--target", "es2022", "--lib", "es2022,dom,dom.iterable"])
+            .arg(dir.join("client.ts"))
+            .output();
+        let Ok(output) = tsc else {
+            eprintln!("skipping: `tsc` is not installed");
+            return;
+        };
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+    }
+
+    #[tokio::test]
+    async fn test_compile_time_client_calls_the_handlers() {
+        use service_kit::auth::Credentials;
+
+        service_kit::client!(ProductService);
+
+        let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+        let router = service_kit::rest_router_builder::RestRouterBuilder::new()
+            .openapi(openapi)
+            .with_state(AppState::default())
+            .with_authenticator(|_: &str, credentials: &Credentials| credentials.token == "secret")
+            .build()
+            .unwrap();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        let service = ProductService::builder(&base_url).bearer_token("secret").build().unwrap();
+
+        let rule = PriceRule::PercentOff { percent: 10.0, max_discount: Some(5.0) };
+        let quote: PriceTick = service.quote_price("prod-001".to_string(), rule).await.unwrap();
+        assert_eq!(quote.price, 94.99);
+        let query = ProductPageQuery { page: 0, status: Some(ProductStatus::Active) };
+        let page: Page<Product> = service.list_products_paged(query).await.unwrap();
+        assert_eq!(page.page, 0);
+        let product = service.get_category_product("books".to_string(), 7).await.unwrap();
+        assert_eq!(product.id, "books-7");
+        assert!(service.find_product_by_code("P-00000".to_string()).await.unwrap().is_none());
+        let catalog = service.get_tenant_catalog("acme".to_string(), None).await.unwrap();
+        assert_eq!(catalog.message, "Catalog of tenant acme");
+        let subscription = Subscription { email: "a@example.com".to_string(), weekly: true, zip: None };
+        assert_eq!(service.subscribe(subscription).await.unwrap().message, "Subscribed a@example.com to the weekly newsletter");
+        let imported = service.import_products("code\nP-1\nP-2\n".to_string()).await.unwrap();
+        assert_eq!(imported.message, "Imported 2 products");
+
+        let deleted = service.delete_product("prod-042".to_string()).await.unwrap();
+        assert_eq!(deleted.message, "Deleted prod-042");
+        let conflict = service.delete_product("prod-001".to_string()).await.unwrap_err();
+        assert_eq!(conflict.status(), Some(409));
+        assert_eq!(conflict.body::<ErrorBody>().unwrap().code, "product_in_use");
+
+        let mut ticks = service.stream_prices("prod-001".to_string()).await.unwrap();
+        let first: PriceTick = ticks.next().await.unwrap().unwrap();
+        assert_eq!(first.sequence, 1);
+    }
+}
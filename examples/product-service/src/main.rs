@@ -1,16 +1,16 @@
 use axum::Router;
-use service_kit::{
-    ApiDtoMetadata, ApiMetadata, inventory, utoipa::{self, OpenApi},
-};
+use service_kit::utoipa;
 use rmcp::transport::streamable_http_server::{
     session::local::LocalSessionManager, StreamableHttpService,
 };
 use rust_embed::RustEmbed;
-use std::{collections::HashMap, sync::Arc};
+use std::sync::Arc;
 use tower_http::cors::{Any, CorsLayer};
-use utoipa::openapi::{self};
 use utoipa_swagger_ui::SwaggerUi;
 use axum_embed::ServeEmbed;
+use product_service::state::AppState;
+use service_kit::ApiDocBuilder;
+use service_kit::auth::Credentials;
 
 // We need to bring the handlers module into scope for the linker to pick up the inventory registrations.
 // use crate::handlers; // This is now incorrect, we use the library.
@@ -24,7 +24,13 @@ mod mcp_server;
 struct Assets;
 
 fn build_openapi_spec() -> utoipa::openapi::OpenApi {
-    service_kit::openapi_utils::build_openapi_basic("Product Service API", env!("CARGO_PKG_VERSION"), "All endpoints for the product service.", "App")
+    ApiDocBuilder::new("Product Service API", env!("CARGO_PKG_VERSION"))
+        .description("All endpoints for the product service.")
+        .server("http://127.0.0.1:3000", "Local development server")
+        .license("MIT OR Apache-2.0", "")
+        .tag("catalog", "Browsing and searching the product catalog")
+        .tag("ops", "Operational endpoints")
+        .build()
 }
 
 #[tokio::main]
@@ -36,21 +42,29 @@ async fn main() {
     
     let openapi = Arc::new(build_openapi_spec());
 
-    // Utility: print OpenAPI and exit if env is set
-    if std::env::var("PRINT_OPENAPI").is_ok() {
-        println!("{}", openapi.to_pretty_json().unwrap_or_else(|_| "{}".to_string()));
+    // `cargo forge export-spec` runs the service in spec-dump mode: write the spec and exit
+    if service_kit::spec_export::export_spec_if_requested(&openapi) {
         return;
     }
 
+    // --- Authentication for `security = "bearer"` endpoints ---
+    let api_token = std::env::var("PRODUCT_SERVICE_TOKEN").unwrap_or_else(|_| "dev-token".to_string());
+    let authenticator = move |_: &str, credentials: &Credentials| credentials.token == api_token;
+
     // --- Build REST Router ---
+    let app_state = AppState::default();
     let rest_router = service_kit::rest_router_builder::RestRouterBuilder::new()
         .openapi((*openapi).clone())
+        .with_state(app_state.clone())
+        .with_authenticator(authenticator.clone())
         .build()
         .expect("Failed to build REST router");
 
     // --- Build MCP Router ---
     let mcp_tool_router = service_kit::openapi_to_mcp::OpenApiMcpRouterBuilder::new()
         .openapi((*openapi).clone())
+        .with_state(app_state)
+        .with_authenticator(authenticator)
         .build()
         .expect("Failed to build MCP router");
     
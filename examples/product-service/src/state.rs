@@ -0,0 +1,13 @@
+/// Shared application state, injected into `#[api]` handlers via `State<AppState>`.
+#[derive(Clone, Debug)]
+pub struct AppState {
+    pub service_name: String,
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self {
+            service_name: "Product Service".to_string(),
+        }
+    }
+}
@@ -7,6 +7,10 @@ async fn main() {
     app::load();
 
     let openapi = app::build_openapi_spec();
+    // `cargo forge export-spec` 以导出模式运行服务：写出文档后直接退出
+    if service_kit::spec_export::export_spec_if_requested(&openapi) {
+        return;
+    }
 
     // 由模板演示如何“组合式”地挂载各个子服务
     let rest = app::build_rest_router(openapi.clone()).expect("build rest router");
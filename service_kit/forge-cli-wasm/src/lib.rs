@@ -95,8 +95,13 @@ async fn execute_request_wasm(
     }
     .ok_or_else(|| JsValue::from_str(&format!("Operation not found for {}", subcommand_name)))?;
 
+    if let Some(warning) = service_kit::cli::deprecation_warning(subcommand_name, operation) {
+        log(&warning);
+    }
+
     let mut final_path = path_template.clone();
     let mut query_params = HashMap::new();
+    let mut header_params: Vec<(String, String)> = Vec::new();
 
     // Process parameters
     if let Some(params) = &operation.parameters {
@@ -111,7 +116,11 @@ async fn execute_request_wasm(
                             oas::ParameterIn::Query => {
                                 query_params.insert(param.name.clone(), value.clone());
                             }
-                            _ => {}
+                            oas::ParameterIn::Header => {
+                                header_params.push((param.name.clone(), value.clone()));
+                            }
+                            // Browsers do not allow setting the Cookie header from fetch
+                            oas::ParameterIn::Cookie => {}
                         }
                     }
                 }
@@ -142,22 +151,61 @@ async fn execute_request_wasm(
     log(&format!("--> Making {} request to: {}", method_str, request_url));
 
     // Create fetch request
-    let mut init = web_sys::RequestInit::new();
+    let init = web_sys::RequestInit::new();
     init.set_method(&method_str);
 
+    let headers = web_sys::Headers::new().unwrap();
+    for (name, value) in &header_params {
+        headers.set(name, value)
+            .map_err(|e| JsValue::from_str(&format!("Invalid header {}: {:?}", name, e)))?;
+    }
+    if let Some(token) = matches.try_get_one::<String>("token").ok().flatten() {
+        headers.set("Authorization", &format!("Bearer {}", token))
+            .map_err(|e| JsValue::from_str(&format!("Invalid token: {:?}", e)))?;
+    }
+    init.set_headers(&headers);
+
     // Add request body if needed
     if let Some(Referenceable::Data(request_body)) = &operation.request_body {
-        if request_body.content.contains_key("application/json") {
-            if let Some(body_str) = matches.get_one::<String>("body") {
-                let json_body: Value = serde_json::from_str(body_str)
-                    .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
-                let body_string = serde_json::to_string(&json_body)
-                    .map_err(|e| JsValue::from_str(&format!("JSON stringify error: {}", e)))?;
-                init.set_body(&JsValue::from_str(&body_string));
-                
-                let headers = web_sys::Headers::new().unwrap();
-                headers.set("Content-Type", "application/json").unwrap();
-                init.set_headers(&headers);
+        if matches.try_get_many::<String>("file").ok().flatten().is_some() {
+            return Err(JsValue::from_str("--file is not supported in the browser CLI"));
+        }
+        if let Some(body_str) = matches.get_one::<String>("body") {
+            match service_kit::cli::request_content_type(request_body) {
+                Some(service_kit::cli::JSON) => {
+                    let json_body: Value = serde_json::from_str(body_str)
+                        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+                    let body_string = serde_json::to_string(&json_body)
+                        .map_err(|e| JsValue::from_str(&format!("JSON stringify error: {}", e)))?;
+                    init.set_body(&JsValue::from_str(&body_string));
+                    headers.set("Content-Type", "application/json").unwrap();
+                }
+                Some(content_type @ (service_kit::cli::FORM | service_kit::cli::MULTIPART)) => {
+                    let fields: HashMap<String, Value> = serde_json::from_str(body_str)
+                        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+                    let fields = fields.into_iter().map(|(k, v)| match v {
+                        Value::String(s) => (k, s),
+                        other => (k, other.to_string()),
+                    });
+                    if content_type == service_kit::cli::FORM {
+                        let encoded = serde_urlencoded::to_string(fields.collect::<Vec<_>>())
+                            .map_err(|e| JsValue::from_str(&format!("Form encoding error: {}", e)))?;
+                        init.set_body(&JsValue::from_str(&encoded));
+                        headers.set("Content-Type", content_type).unwrap();
+                    } else {
+                        // The browser sets the multipart boundary itself
+                        let form = web_sys::FormData::new()?;
+                        for (name, value) in fields {
+                            form.append_with_str(&name, &value)?;
+                        }
+                        init.set_body(&form);
+                    }
+                }
+                Some(content_type) => {
+                    init.set_body(&JsValue::from_str(body_str));
+                    headers.set("Content-Type", content_type).unwrap();
+                }
+                None => {}
             }
         }
     }
@@ -190,39 +238,41 @@ async fn execute_request_wasm(
     }
 }
 
-// New async version that actually executes API requests
-#[wasm_bindgen]
-pub async fn run_command_async(command_line: &str) -> JsValue {
-    let mut cli_command_guard = CLI_COMMAND.lock().unwrap();
-    let spec_guard = SPEC.lock().unwrap();
-    let base_url_guard = BASE_URL.lock().unwrap();
-    let mut history_guard = HISTORY.lock().unwrap();
-
-    // Ensure the CLI has been initialized.
-    let cli_command = match &mut *cli_command_guard {
-        Some(cmd) => cmd,
-        None => return JsValue::from_str("Error: CLI not initialized. Call init_cli(spec, base_url) first."),
-    };
-    
-    let spec = match &*spec_guard {
-        Some(s) => s,
-        None => return JsValue::from_str("Error: Spec not initialized."),
-    };
-
-    let base_url = match &*base_url_guard {
-        Some(url) => url,
-        None => return JsValue::from_str("Error: Base URL not initialized."),
-    };
+/// Snapshot of the state set by `init_cli`. Every lock is released before this returns, so
+/// `init_cli`, completions and history calls made while a request is awaiting do not block on
+/// (or, in a single-threaded WASM runtime, deadlock against) the running command.
+fn session_state() -> Result<(Command, OpenAPIV3, String), &'static str> {
+    let cli_command = CLI_COMMAND
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or("Error: CLI not initialized. Call init_cli(spec, base_url) first.")?;
+    let spec = SPEC.lock().unwrap().clone().ok_or("Error: Spec not initialized.")?;
+    let base_url = BASE_URL.lock().unwrap().clone().ok_or("Error: Base URL not initialized.")?;
+    Ok((cli_command, spec, base_url))
+}
 
-    // 添加到历史记录 (只记录非空命令)
+// 添加到历史记录 (只记录非空命令)
+fn record_history(command_line: &str) {
     let trimmed_command = command_line.trim();
-    if !trimmed_command.is_empty() && (history_guard.is_empty() || history_guard.back() != Some(&trimmed_command.to_string())) {
+    let mut history_guard = HISTORY.lock().unwrap();
+    if !trimmed_command.is_empty() && history_guard.back().map(String::as_str) != Some(trimmed_command) {
         history_guard.push_back(trimmed_command.to_string());
         // 限制历史记录长度
         if history_guard.len() > 1000 {
             history_guard.pop_front();
         }
     }
+}
+
+// New async version that actually executes API requests
+#[wasm_bindgen]
+pub async fn run_command_async(command_line: &str) -> JsValue {
+    let (cli_command, spec, base_url) = match session_state() {
+        Ok(state) => state,
+        Err(message) => return JsValue::from_str(message),
+    };
+    record_history(command_line);
 
     // Parse the command line.
     let args = match shlex::split(command_line) {
@@ -243,7 +293,7 @@ pub async fn run_command_async(command_line: &str) -> JsValue {
         Ok(matches) => {
             if let Some((subcommand, sub_matches)) = matches.subcommand() {
                 // Actually execute the API request
-                match execute_request_wasm(base_url, subcommand, sub_matches, spec).await {
+                match execute_request_wasm(&base_url, subcommand, sub_matches, &spec).await {
                     Ok(response) => JsValue::from_str(&response),
                     Err(e) => JsValue::from_str(&format!("API request failed: {:?}", e)),
                 }
@@ -363,3 +413,34 @@ pub fn clear_history() {
     let mut history_guard = HISTORY.lock().unwrap();
     history_guard.clear();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC_JSON: &str = r#"{
+        "openapi": "3.0.0",
+        "info": { "title": "Test", "version": "0.0.0" },
+        "paths": {}
+    }"#;
+
+    #[test]
+    fn session_state_releases_every_lock() {
+        init_cli(SPEC_JSON, "http://127.0.0.1:3000").unwrap();
+
+        let (_, _, base_url) = session_state().unwrap();
+        assert_eq!(base_url, "http://127.0.0.1:3000");
+        // A request in flight holds only its own copies; re-initializing meanwhile must not block.
+        assert!(CLI_COMMAND.try_lock().is_ok());
+        assert!(SPEC.try_lock().is_ok());
+        assert!(BASE_URL.try_lock().is_ok());
+        init_cli(SPEC_JSON, "http://127.0.0.1:4000").unwrap();
+        assert_eq!(session_state().unwrap().2, "http://127.0.0.1:4000");
+
+        record_history(" products.get ");
+        record_history("products.get");
+        record_history("  ");
+        assert!(HISTORY.try_lock().is_ok());
+        assert_eq!(HISTORY.lock().unwrap().iter().filter(|item| *item == "products.get").count(), 1);
+    }
+}
@@ -1,5 +1,6 @@
 extern crate proc_macro;
 
+use heck::{ToKebabCase, ToUpperCamelCase};
 use proc_macro::TokenStream;
 use quote::{quote, format_ident};
 use syn::{
@@ -13,6 +14,113 @@ use syn::{
 struct ApiMacroArgs {
     method: Ident,
     path: LitStr,
+    headers: Vec<DeclaredParam>,
+    cookies: Vec<DeclaredParam>,
+    responses: Vec<DeclaredResponse>,
+    param_docs: Vec<ParamDoc>,
+    tags: Vec<LitStr>,
+    deprecated: bool,
+    extensions: Vec<DeclaredExtension>,
+    operation_id: Option<LitStr>,
+    security: Vec<LitStr>,
+    request_example: Option<syn::Expr>,
+    response_example: Option<syn::Expr>,
+}
+
+/// Security schemes accepted by `#[api(..., security = "...")]`; kept in sync with `service_kit::auth`.
+const SECURITY_SCHEMES: &[&str] = &["bearer"];
+
+/// A `x_name = <expr>` entry of `extensions(...)`; the name may also be a string literal.
+/// The value is any expression accepted by `serde_json::json!`.
+struct DeclaredExtension {
+    name: String,
+    value: syn::Expr,
+}
+
+impl Parse for DeclaredExtension {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let (name, span) = if input.peek(LitStr) {
+            let lit: LitStr = input.parse()?;
+            (lit.value(), lit.span())
+        } else {
+            let ident: Ident = input.parse()?;
+            (ident.to_string().to_kebab_case(), ident.span())
+        };
+        if !name.starts_with("x-") {
+            return Err(syn::Error::new(
+                span,
+                format!("OpenAPI extension `{}` must start with `x-` (or `x_`)", name),
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        let value: syn::Expr = input.parse()?;
+        Ok(DeclaredExtension { name, value })
+    }
+}
+
+/// A `name = "description"` entry of `params(...)`; the name may also be a string literal
+/// (e.g. `"x-tenant-id" = "..."`).
+struct ParamDoc {
+    name: String,
+    span: proc_macro2::Span,
+    description: LitStr,
+}
+
+impl Parse for ParamDoc {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let (name, span) = if input.peek(LitStr) {
+            let lit: LitStr = input.parse()?;
+            (lit.value(), lit.span())
+        } else {
+            let ident: Ident = input.parse()?;
+            (ident.to_string(), ident.span())
+        };
+        input.parse::<Token![=]>()?;
+        let description: LitStr = input.parse()?;
+        Ok(ParamDoc { name, span, description })
+    }
+}
+
+/// A `(status, "description")` or `(status, "description", Type)` entry of `responses(...)`.
+struct DeclaredResponse {
+    status: u16,
+    description: LitStr,
+    ty: Option<Type>,
+}
+
+impl Parse for DeclaredResponse {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        syn::parenthesized!(content in input);
+        let status: syn::LitInt = content.parse()?;
+        content.parse::<Token![,]>()?;
+        let description: LitStr = content.parse()?;
+        let ty = if content.parse::<Option<Token![,]>>()?.is_some() && !content.is_empty() {
+            Some(content.parse()?)
+        } else {
+            None
+        };
+        Ok(DeclaredResponse {
+            status: status.base10_parse()?,
+            description,
+            ty,
+        })
+    }
+}
+
+/// A `name: Type` entry of the declarative `headers(...)` / `cookies(...)` options.
+struct DeclaredParam {
+    name: Ident,
+    ty: Type,
+}
+
+impl Parse for DeclaredParam {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name: Ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty: Type = input.parse()?;
+        Ok(DeclaredParam { name, ty })
+    }
 }
 
 impl Parse for ApiMacroArgs {
@@ -20,80 +128,286 @@ impl Parse for ApiMacroArgs {
         let method: Ident = input.parse()?;
         input.parse::<Token![,]>()?;
         let path: LitStr = input.parse()?;
-        Ok(ApiMacroArgs { method, path })
+        let mut args = ApiMacroArgs {
+            method,
+            path,
+            headers: Vec::new(),
+            cookies: Vec::new(),
+            responses: Vec::new(),
+            param_docs: Vec::new(),
+            tags: Vec::new(),
+            deprecated: false,
+            extensions: Vec::new(),
+            operation_id: None,
+            security: Vec::new(),
+            request_example: None,
+            response_example: None,
+        };
+
+        // Optional trailing options: `, key(...)` or `, key = value`
+        while input.parse::<Option<Token![,]>>()?.is_some() {
+            if input.is_empty() {
+                break;
+            }
+            let key: Ident = input.parse()?;
+            match key.to_string().as_str() {
+                "headers" | "cookies" => {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    let declared = Punctuated::<DeclaredParam, Token![,]>::parse_terminated(&content)?;
+                    if key == "headers" {
+                        args.headers.extend(declared);
+                    } else {
+                        args.cookies.extend(declared);
+                    }
+                }
+                "responses" => {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    args.responses
+                        .extend(Punctuated::<DeclaredResponse, Token![,]>::parse_terminated(&content)?);
+                }
+                "params" => {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    args.param_docs
+                        .extend(Punctuated::<ParamDoc, Token![,]>::parse_terminated(&content)?);
+                }
+                "operation_id" => {
+                    input.parse::<Token![=]>()?;
+                    let operation_id: LitStr = input.parse()?;
+                    if operation_id.value().is_empty() {
+                        return Err(syn::Error::new(operation_id.span(), "operation_id must not be empty"));
+                    }
+                    args.operation_id = Some(operation_id);
+                }
+                "security" => {
+                    input.parse::<Token![=]>()?;
+                    let scheme: LitStr = input.parse()?;
+                    if !SECURITY_SCHEMES.contains(&scheme.value().as_str()) {
+                        return Err(syn::Error::new(
+                            scheme.span(),
+                            format!("unsupported security scheme, expected one of: {}", SECURITY_SCHEMES.join(", ")),
+                        ));
+                    }
+                    args.security.push(scheme);
+                }
+                "request_example" | "response_example" => {
+                    input.parse::<Token![=]>()?;
+                    let example: syn::Expr = input.parse()?;
+                    if key == "request_example" {
+                        args.request_example = Some(example);
+                    } else {
+                        args.response_example = Some(example);
+                    }
+                }
+                "tags" => {
+                    input.parse::<Token![=]>()?;
+                    let content;
+                    syn::bracketed!(content in input);
+                    args.tags.extend(Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?);
+                }
+                "deprecated" => {
+                    args.deprecated = if input.parse::<Option<Token![=]>>()?.is_some() {
+                        input.parse::<syn::LitBool>()?.value
+                    } else {
+                        true
+                    };
+                }
+                "extensions" => {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    args.extensions
+                        .extend(Punctuated::<DeclaredExtension, Token![,]>::parse_terminated(&content)?);
+                }
+                other => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("unknown #[api] option `{}`", other),
+                    ))
+                }
+            }
+        }
+        Ok(args)
     }
 }
 
 #[proc_macro_attribute]
 pub fn api(args: TokenStream, input: TokenStream) -> TokenStream {
-    let item_fn = parse_macro_input!(input as ItemFn);
+    let mut item_fn = parse_macro_input!(input as ItemFn);
     let args_parsed = parse_macro_input!(args as ApiMacroArgs);
 
     let fn_ident = &item_fn.sig.ident;
     let fn_name_str = fn_ident.to_string();
+    // Defaults to the function name; must be unique across the service (checked when routers are built)
+    let operation_id = args_parsed
+        .operation_id
+        .as_ref()
+        .map(LitStr::value)
+        .unwrap_or_else(|| fn_name_str.clone());
     let method_str = args_parsed.method.to_string();
     let path_str = args_parsed.path.value();
     let (summary, description) = parse_doc_comments(&item_fn.attrs);
+    // A plain `#[deprecated]` on the handler also marks the operation as deprecated
+    let deprecated = args_parsed.deprecated || item_fn.attrs.iter().any(|attr| attr.path().is_ident("deprecated"));
+    let tags = &args_parsed.tags;
+    let security = &args_parsed.security;
+    let extensions_tokens = args_parsed.extensions.iter().map(|ext| {
+        let name = &ext.name;
+        let value = &ext.value;
+        quote! {
+            ::service_kit::ApiExtension { name: #name, value: || serde_json::json!(#value) }
+        }
+    });
+    let path_placeholders = parse_path_placeholders(&path_str);
+
+    // Header/cookie names known at compile time; used to rebuild `HeaderMap` from flat (MCP) arguments.
+    let mut header_names: Vec<String> = args_parsed
+        .headers
+        .iter()
+        .map(|h| header_name_from_ident(&h.name))
+        .collect();
+    for arg in &item_fn.sig.inputs {
+        if let FnArg::Typed(pat_type) = arg {
+            if let Some(header_ty) = get_inner_type(&pat_type.ty, "TypedHeader") {
+                header_names.push(typed_header_name(header_ty));
+            }
+        }
+    }
+    let cookie_names: Vec<String> = args_parsed.cookies.iter().map(|c| c.name.to_string()).collect();
 
     // --- Parse Parameters and Request Body ---
     let mut params_tokens = Vec::new();
     let mut request_body_token = quote! { None };
+    let mut has_request_body = false;
 
     // For building runtime wrapper
     let mut arg_prepare_tokens = Vec::new();
     let mut call_args_tokens = Vec::new();
 
+    // Declarative `headers(...)` / `cookies(...)`: documented, and required ones are checked before dispatch
+    let declared_headers = args_parsed.headers.iter().map(|h| {
+        (h, header_name_from_ident(&h.name), quote! { Header }, quote! { require_header })
+    });
+    let declared_cookies = args_parsed.cookies.iter().map(|c| {
+        (c, c.name.to_string(), quote! { Cookie }, quote! { require_cookie })
+    });
+    // Names a `params(...)` entry may refer to; anything else is reported as a typo.
+    let mut known_param_names: Vec<String> = Vec::new();
+    // Instantiations of generic DTOs (`Page<Product>`) used by this handler; each gets its own schema.
+    let mut generic_dtos: Vec<Type> = Vec::new();
+    for (declared, param_name, param_in, require_fn) in declared_headers.chain(declared_cookies) {
+        let (value_ty, required) = match get_inner_type(&declared.ty, "Option") {
+            Some(inner) => (inner, false),
+            None => (&declared.ty, true),
+        };
+        let type_name = type_to_string(value_ty);
+        generic_dtos.push(value_ty.clone());
+        let param_description = describe_param(&args_parsed.param_docs, &param_name, "");
+        known_param_names.push(param_name.clone());
+        params_tokens.push(quote! {
+            ::service_kit::ApiParameter {
+                name: #param_name,
+                param_in: ::service_kit::ParamIn::#param_in,
+                description: #param_description,
+                required: #required,
+                type_name: #type_name,
+            }
+        });
+        if required {
+            arg_prepare_tokens.push(quote! {
+                ::service_kit::handler::#require_fn(&params, #param_name)?;
+            });
+        }
+    }
+
     for arg in &item_fn.sig.inputs {
         if let FnArg::Typed(pat_type) = arg {
+            // `/// ...` on an argument documents the parameter(s) or request body it extracts
+            let arg_doc = doc_text(&pat_type.attrs);
             if let Some(inner_type) = get_inner_type(&pat_type.ty, "Path") {
-                if let Pat::TupleStruct(pat_tuple) = &*pat_type.pat {
-                     if let Some(Pat::Ident(inner_pat)) = pat_tuple.elems.first() {
-                        let param_name = inner_pat.ident.to_string();
-                        let type_name = type_to_string(inner_type);
+                let path_ident = format_ident!("__path_{}", call_args_tokens.len());
+                let inner_ty_tokens = quote! { #inner_type };
+                let tuple_pat = match &*pat_type.pat {
+                    Pat::TupleStruct(pat_tuple) => match pat_tuple.elems.first() {
+                        Some(Pat::Tuple(t)) => Some(t),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+
+                if let Some(tuple_ty) = non_unit_tuple(inner_type) {
+                    // `Path((org, repo)): Path<(String, String)>` or `path: Path<(String, String)>`:
+                    // one parameter per element, named after the path placeholders in declaration order.
+                    let mut elem_idents = Vec::new();
+                    let mut elem_prepare = Vec::new();
+                    for (i, elem_ty) in tuple_ty.elems.iter().enumerate() {
+                        let param_name = path_placeholders
+                            .get(i)
+                            .cloned()
+                            .or_else(|| tuple_pat.and_then(|t| t.elems.iter().nth(i)).and_then(pat_ident_name))
+                            .unwrap_or_else(|| format!("param{}", i));
+                        let type_name = type_to_string(elem_ty);
+                        let param_description = describe_param(&args_parsed.param_docs, &param_name, &arg_doc);
+                        known_param_names.push(param_name.clone());
                         params_tokens.push(quote! {
                             ::service_kit::ApiParameter {
                                 name: #param_name,
                                 param_in: ::service_kit::ParamIn::Path,
-                                description: "", // TODO: Parse from attributes
+                                description: #param_description,
                                 required: true,
                                 type_name: #type_name,
                             }
                         });
-                        // runtime wrapper: read string and wrap
-                        let var_ident = &inner_pat.ident;
-                        arg_prepare_tokens.push(quote! {
-                            let #var_ident: String = match params.get(#param_name).and_then(|v| v.as_str()) {
-                                Some(s) => s.to_string(),
-                                None => String::new(),
-                            };
-                            let #var_ident = axum::extract::Path::<String>(#var_ident);
+                        let elem_ident = format_ident!("{}_{}", path_ident, i);
+                        elem_prepare.push(quote! {
+                            let #elem_ident: #elem_ty = ::service_kit::handler::extract_path_param::<#elem_ty>(&params, #param_name)?;
                         });
-                        call_args_tokens.push(quote! { #var_ident });
+                        elem_idents.push(elem_ident);
                     }
-                } else if let Pat::Ident(pat_ident) = &*pat_type.pat {
-                    // Also support `id: Path<String>` style
-                    let param_name = pat_ident.ident.to_string();
+                    arg_prepare_tokens.push(quote! {
+                        #(#elem_prepare)*
+                        let #path_ident = axum::extract::Path::<#inner_ty_tokens>((#(#elem_idents,)*));
+                    });
+                    call_args_tokens.push(quote! { #path_ident });
+                } else {
+                    // `Path(id): Path<T>` or `id: Path<T>`: `T` is either a scalar bound to the
+                    // single placeholder, or a struct whose fields are the placeholders.
+                    let binding_name = match &*pat_type.pat {
+                        Pat::TupleStruct(pat_tuple) => pat_tuple.elems.first().and_then(pat_ident_name),
+                        other => pat_ident_name(other),
+                    };
+                    let param_name = if path_placeholders.len() == 1 {
+                        path_placeholders[0].clone()
+                    } else {
+                        binding_name.unwrap_or_else(|| "params".to_string())
+                    };
                     let type_name = type_to_string(inner_type);
+                    generic_dtos.push(inner_type.clone());
+                    let param_description = describe_param(&args_parsed.param_docs, &param_name, &arg_doc);
+                    known_param_names.push(param_name.clone());
+                    known_param_names.extend(path_placeholders.iter().cloned());
                     params_tokens.push(quote! {
                         ::service_kit::ApiParameter {
                             name: #param_name,
                             param_in: ::service_kit::ParamIn::Path,
-                            description: "",
+                            description: #param_description,
                             required: true,
                             type_name: #type_name,
                         }
                     });
-                    let var_ident = &pat_ident.ident;
+                    let names: Vec<&String> = if path_placeholders.is_empty() {
+                        vec![&param_name]
+                    } else {
+                        path_placeholders.iter().collect()
+                    };
                     arg_prepare_tokens.push(quote! {
-                        let #var_ident: String = match params.get(#param_name).and_then(|v| v.as_str()) {
-                            Some(s) => s.to_string(),
-                            None => String::new(),
-                        };
-                        let #var_ident = axum::extract::Path::<String>(#var_ident);
+                        let #path_ident: #inner_ty_tokens = ::service_kit::handler::extract_path_params::<#inner_ty_tokens>(&params, &[#(#names),*])?;
+                        let #path_ident = axum::extract::Path::<#inner_ty_tokens>(#path_ident);
                     });
-                    call_args_tokens.push(quote! { #var_ident });
+                    call_args_tokens.push(quote! { #path_ident });
                 }
-            } else if let Some(inner_type) = get_inner_type(&pat_type.ty, "Query") {
+            } else if let Some((query_ty, extractor_optional)) = query_extractor_type(&pat_type.ty) {
                 // Support both `Query(params): Query<T>` and `params: Query<T>` patterns
                 let param_name_opt = if let Pat::TupleStruct(pat_tuple) = &*pat_type.pat {
                     pat_tuple
@@ -105,80 +419,303 @@ pub fn api(args: TokenStream, input: TokenStream) -> TokenStream {
                 } else { None };
 
                 if let Some(param_name) = param_name_opt {
-                    let type_name = type_to_string(inner_type);
+                    // `Query<Option<T>>` and `Option<Query<T>>` make the whole query optional;
+                    // per-field requiredness comes from the DTO schema (`Option`, `serde(default)`).
+                    let (schema_ty, required) = match get_inner_type(query_ty, "Option") {
+                        Some(inner) => (inner, false),
+                        None => (query_ty, !extractor_optional),
+                    };
+                    let type_name = type_to_string(schema_ty);
+                    generic_dtos.push(schema_ty.clone());
+                    let param_description = describe_param(&args_parsed.param_docs, &param_name, &arg_doc);
+                    known_param_names.push(param_name.clone());
                     params_tokens.push(quote! {
                         ::service_kit::ApiParameter {
                             name: #param_name,
                             param_in: ::service_kit::ParamIn::Query,
-                            description: "", // TODO: Parse from attributes
-                            required: true, // TODO: Detect Option
+                            description: #param_description,
+                            required: #required,
                             type_name: #type_name,
                         }
                     });
                     // runtime wrapper: deserialize whole params into T
                     let var_ident = format_ident!("{}", param_name);
-                     let inner_ty_tokens = quote! { #inner_type };
-                    arg_prepare_tokens.push(quote! {
-                        let #var_ident: #inner_ty_tokens = match serde_json::from_value(params.clone()) {
-                            Ok(v) => v,
-                             Err(e) => return Err(::service_kit::error::Error::SerdeJson(e)),
-                        };
-                        let #var_ident = axum::extract::Query::<#inner_ty_tokens>(#var_ident);
-                    });
+                    let inner_ty_tokens = quote! { #query_ty };
+                    if extractor_optional {
+                        arg_prepare_tokens.push(quote! {
+                            let #var_ident: Option<axum::extract::Query<#inner_ty_tokens>> =
+                                ::service_kit::handler::extract_optional_query::<#inner_ty_tokens>(&params, #type_name)?
+                                    .map(axum::extract::Query::<#inner_ty_tokens>);
+                        });
+                    } else {
+                        arg_prepare_tokens.push(quote! {
+                            let #var_ident = axum::extract::Query::<#inner_ty_tokens>(
+                                ::service_kit::handler::extract_query::<#inner_ty_tokens>(&params, #type_name)?,
+                            );
+                        });
+                    }
                     call_args_tokens.push(quote! { #var_ident });
                 }
-            } else if let Some(inner_type) = get_inner_type(&pat_type.ty, "Json") {
-                let type_name = type_to_string(inner_type);
+            } else if let Some(header_ty) = get_inner_type(&pat_type.ty, "TypedHeader") {
+                let param_name = typed_header_name(header_ty);
+                let param_description = describe_param(&args_parsed.param_docs, &param_name, &arg_doc);
+                known_param_names.push(param_name.clone());
+                params_tokens.push(quote! {
+                    ::service_kit::ApiParameter {
+                        name: #param_name,
+                        param_in: ::service_kit::ParamIn::Header,
+                        description: #param_description,
+                        required: true,
+                        type_name: "String",
+                    }
+                });
+                // runtime wrapper: decode the typed header from the raw header values
+                let header_ident = format_ident!("__header_{}", call_args_tokens.len());
+                arg_prepare_tokens.push(quote! {
+                    let #header_ident = ::service_kit::handler::extract_header_values(
+                        &params,
+                        <#header_ty as ::axum_extra::headers::Header>::name().as_str(),
+                    );
+                    let #header_ident: #header_ty = match <#header_ty as ::axum_extra::headers::Header>::decode(&mut #header_ident.iter()) {
+                        Ok(v) => v,
+                        Err(e) => return Err(::service_kit::error::Error::BadRequest(format!("Invalid header `{}`: {}", #param_name, e))),
+                    };
+                    let #header_ident = ::axum_extra::TypedHeader(#header_ident);
+                });
+                call_args_tokens.push(quote! { #header_ident });
+            } else if is_type_named(&pat_type.ty, "HeaderMap") {
+                let header_map_ident = format_ident!("__header_map_{}", call_args_tokens.len());
+                arg_prepare_tokens.push(quote! {
+                    let #header_map_ident = ::service_kit::handler::extract_header_map(
+                        &params,
+                        &[#(#header_names),*],
+                        &[#(#cookie_names),*],
+                    );
+                });
+                call_args_tokens.push(quote! { #header_map_ident });
+            } else if let Some(inner_type) = get_inner_type(&pat_type.ty, "State") {
+                // runtime wrapper: recover the typed state injected via `.with_state(..)`
+                let state_ident = format_ident!("__state_{}", call_args_tokens.len());
+                let inner_ty_tokens = quote! { #inner_type };
+                arg_prepare_tokens.push(quote! {
+                    let #state_ident: #inner_ty_tokens = __api_state.require::<#inner_ty_tokens>()?;
+                    let #state_ident = axum::extract::State::<#inner_ty_tokens>(#state_ident);
+                });
+                call_args_tokens.push(quote! { #state_ident });
+            } else if let Some(body) = body_extractor(&pat_type.ty) {
+                let content_type = body.content_type();
+                let type_name = body.type_name();
+                if let BodyExtractor::Json(inner_type) | BodyExtractor::Form(inner_type) = body {
+                    generic_dtos.push(inner_type.clone());
+                }
+                let body_description = match &*pat_type.pat {
+                    Pat::TupleStruct(pat_tuple) => pat_tuple.elems.first().and_then(pat_ident_name),
+                    other => pat_ident_name(other),
+                }
+                .map(|binding| {
+                    known_param_names.push(binding.clone());
+                    describe_param(&args_parsed.param_docs, &binding, &arg_doc)
+                })
+                .unwrap_or(arg_doc);
+                has_request_body = true;
+                let example = example_tokens(args_parsed.request_example.as_ref());
                 request_body_token = quote! {
                     Some(&::service_kit::ApiRequestBody {
-                        description: "", // TODO: Parse from attributes
+                        description: #body_description,
                         required: true,
                         type_name: #type_name,
+                        content_type: #content_type,
+                        example: #example,
                     })
                 };
-                // runtime wrapper: deserialize whole params into body T
-                 let inner_ty_tokens = quote! { #inner_type };
-                 let json_ident = syn::Ident::new("__json_body", proc_macro2::Span::call_site());
-                arg_prepare_tokens.push(quote! {
-                    let #json_ident: #inner_ty_tokens = match serde_json::from_value(params.clone()) {
-                         Ok(v) => v,
-                         Err(e) => return Err(::service_kit::error::Error::SerdeJson(e)),
-                    };
-                    let #json_ident = axum::Json::<#inner_ty_tokens>(#json_ident);
+                let body_ident = syn::Ident::new("__request_body", proc_macro2::Span::call_site());
+                arg_prepare_tokens.push(match body {
+                    // runtime wrapper: deserialize whole params into body T
+                    BodyExtractor::Json(inner_type) => quote! {
+                        let #body_ident = ::service_kit::handler::strip_request_meta(&params);
+                        ::service_kit::validation::validate(#type_name, &#body_ident)?;
+                        let #body_ident: #inner_type = match serde_json::from_value(#body_ident) {
+                            Ok(v) => v,
+                            Err(e) => return Err(::service_kit::error::Error::SerdeJson(e)),
+                        };
+                        let #body_ident = axum::Json::<#inner_type>(#body_ident);
+                    },
+                    // runtime wrapper: parse the raw urlencoded body (REST) or the flat arguments (MCP)
+                    BodyExtractor::Form(inner_type) => quote! {
+                        let #body_ident = axum::Form::<#inner_type>(
+                            ::service_kit::handler::extract_form::<#inner_type>(&params, __body, #type_name).await?,
+                        );
+                    },
+                    // runtime wrapper: read the raw body (REST) or rebuild it from flat arguments (MCP)
+                    BodyExtractor::Multipart => {
+                        let non_field_names = path_placeholders.iter().chain(&header_names).chain(&cookie_names);
+                        quote! {
+                            let #body_ident = ::service_kit::handler::extract_multipart(
+                                &params,
+                                __body,
+                                &[#(#non_field_names),*],
+                            )
+                            .await?;
+                        }
+                    }
+                    BodyExtractor::Bytes => quote! {
+                        let #body_ident = ::service_kit::handler::extract_bytes(&params, __body).await?;
+                    },
+                    BodyExtractor::Text => quote! {
+                        let #body_ident = ::service_kit::handler::extract_string(&params, __body).await?;
+                    },
                 });
-                call_args_tokens.push(quote! { #json_ident });
+                call_args_tokens.push(quote! { #body_ident });
             }
         }
     }
 
+    if let (Some(example), false) = (&args_parsed.request_example, has_request_body) {
+        return syn::Error::new_spanned(example, "`request_example` requires a request body argument")
+            .to_compile_error()
+            .into();
+    }
+
+    if let Some(unknown) = args_parsed
+        .param_docs
+        .iter()
+        .find(|doc| !known_param_names.iter().any(|name| param_doc_matches(doc, name)))
+    {
+        return syn::Error::new(
+            unknown.span,
+            format!("`params(...)` refers to unknown parameter `{}`", unknown.name),
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    // Doc comments are not allowed on function parameters once the attribute is expanded.
+    for arg in item_fn.sig.inputs.iter_mut() {
+        if let FnArg::Typed(pat_type) = arg {
+            pat_type.attrs.retain(|attr| !attr.path().is_ident("doc"));
+        }
+    }
+
     // --- Parse Responses ---
-    let mut responses_tokens = Vec::new();
-    if let ReturnType::Type(_, ty) = &item_fn.sig.output {
-        if let Some(inner_type) = get_inner_type(ty, "Json") {
-            let type_name = type_to_string(inner_type);
-            responses_tokens.push(quote! {
-                ::service_kit::ApiResponse {
-                    status_code: 200,
-                    description: #summary,
-                    type_name: Some(#type_name),
-                }
-            });
+    // Explicit `responses(...)` first, then whatever can be inferred from the return type.
+    let inferred = match &item_fn.sig.output {
+        ReturnType::Type(_, ty) => infer_responses(ty),
+        ReturnType::Default => InferredResponses::default(),
+    };
+    // `Sse<...>` handlers stream their successful responses as `text/event-stream`
+    let content_type_for = |status: u16| {
+        if inferred.streaming && (200..300).contains(&status) {
+            "text/event-stream"
+        } else {
+            "application/json"
         }
+    };
+    // `response_example` documents the success responses
+    let example_for = |status: u16| {
+        example_tokens(args_parsed.response_example.as_ref().filter(|_| (200..300).contains(&status)))
+    };
+    let mut responses_tokens = Vec::new();
+    for declared in &args_parsed.responses {
+        let status = declared.status;
+        let content_type = content_type_for(status);
+        let example = example_for(status);
+        let description = &declared.description;
+        let type_name = match &declared.ty {
+            Some(ty) => {
+                let type_name = type_to_string(ty);
+                generic_dtos.push(ty.clone());
+                quote! { Some(#type_name) }
+            }
+            None => quote! { None },
+        };
+        responses_tokens.push(quote! {
+            ::service_kit::ApiResponse {
+                status_code: #status,
+                description: #description,
+                type_name: #type_name,
+                content_type: #content_type,
+                example: #example,
+            }
+        });
+    }
+    let declares = |range: std::ops::Range<u16>| args_parsed.responses.iter().any(|r| range.contains(&r.status));
+
+    if !declares(200..300) {
+        let type_name = match inferred.success {
+            Some(ty) => {
+                let type_name = type_to_string(ty);
+                generic_dtos.push(ty.clone());
+                quote! { Some(#type_name) }
+            }
+            None => quote! { None },
+        };
+        let content_type = content_type_for(200);
+        let example = example_for(200);
+        responses_tokens.push(quote! {
+            ::service_kit::ApiResponse {
+                status_code: 200,
+                description: "Success",
+                type_name: #type_name,
+                content_type: #content_type,
+                example: #example,
+            }
+        });
+    }
+    if inferred.optional && !declares(404..405) {
+        responses_tokens.push(quote! {
+            ::service_kit::ApiResponse {
+                status_code: 404,
+                description: "Not Found",
+                type_name: None,
+                content_type: "application/json",
+                example: None,
+            }
+        });
     }
-    // Add a default response if none was parsed
-    if responses_tokens.is_empty() {
+    if let Some(error_ty) = inferred.error {
+        let error_ty = get_inner_type(error_ty, "Json").unwrap_or(error_ty);
+        let type_name = type_to_string(error_ty);
+        generic_dtos.push(error_ty.clone());
         responses_tokens.push(quote! {
-            ::service_kit::ApiResponse { status_code: 200, description: "Success", type_name: None }
+            ::service_kit::ApiResponse {
+                status_code: ::service_kit::DEFAULT_RESPONSE_STATUS,
+                description: "Error",
+                type_name: Some(#type_name),
+                content_type: "application/json",
+                example: None,
+            }
         });
     }
 
+    let generic_dto_registrations = generic_dto_registrations(&generic_dtos, &[]);
+
+    // `Option<T>` is not an axum response; map `None` to 404 in the runtime wrapper
+    let call_tokens = quote! { #fn_ident(#(#call_args_tokens),*).await };
+    let into_response_tokens = if inferred.optional {
+        quote! {
+            let __resp = match #call_tokens {
+                Some(__resp) => ::axum::response::IntoResponse::into_response(__resp),
+                None => ::axum::response::IntoResponse::into_response(::axum::http::StatusCode::NOT_FOUND),
+            };
+        }
+    } else {
+        quote! {
+            let __resp = #call_tokens;
+            let __resp = ::axum::response::IntoResponse::into_response(__resp);
+        }
+    };
+
     // --- Generate Static Metadata ---
     let params_ident = format_ident!("__API_PARAMS_{}", fn_name_str.to_uppercase());
     let responses_ident = format_ident!("__API_RESPONSES_{}", fn_name_str.to_uppercase());
     let request_body_ident = format_ident!("__API_REQ_BODY_{}", fn_name_str.to_uppercase());
 
+    let metadata_ident = format_ident!("__API_METADATA_{}", fn_name_str.to_uppercase());
+
     let exec_fn_ident = format_ident!("__API_EXEC_{}", fn_name_str.to_uppercase());
 
+    let client_tokens = client_trait(&item_fn, &args_parsed, &inferred, deprecated, &metadata_ident);
+
     let static_metadata = quote! {
         #[allow(non_upper_case_globals)]
         const #params_ident: &[::service_kit::ApiParameter] = &[#(#params_tokens),*];
@@ -186,36 +723,51 @@ pub fn api(args: TokenStream, input: TokenStream) -> TokenStream {
         const #responses_ident: &[::service_kit::ApiResponse] = &[#(#responses_tokens),*];
         #[allow(non_upper_case_globals)]
         const #request_body_ident: Option<&'static ::service_kit::ApiRequestBody> = #request_body_token;
+        // Named so the typed client can refer to it without going through the inventory
+        #[allow(non_upper_case_globals)]
+        const #metadata_ident: ::service_kit::ApiMetadata = ::service_kit::ApiMetadata {
+            operation_id: #operation_id,
+            method: #method_str,
+            path: #path_str,
+            summary: #summary,
+            description: #description,
+            parameters: #params_ident,
+            request_body: #request_body_ident,
+            responses: #responses_ident,
+            tags: &[#(#tags),*],
+            deprecated: #deprecated,
+            extensions: &[#(#extensions_tokens),*],
+            security: &[#(#security),*],
+        };
 
-        ::service_kit::inventory::submit! {
-            ::service_kit::ApiMetadata {
-                operation_id: #fn_name_str,
-                method: #method_str,
-                path: #path_str,
-                summary: #summary,
-                description: #description,
-                parameters: #params_ident,
-                request_body: #request_body_ident,
-                responses: #responses_ident,
-            }
-        }
+        ::service_kit::inventory::submit! { #metadata_ident }
+
+        #client_tokens
+
+        #(#generic_dto_registrations)*
 
         // Static handler function for REST/MCP routers
-        fn #exec_fn_ident(__params_ref: &serde_json::Value) -> ::service_kit::handler::DynHandlerFuture {
+        #[allow(deprecated)]
+        fn #exec_fn_ident(
+            __params_ref: &serde_json::Value,
+            __state_ref: &::service_kit::handler::ApiState,
+            __body: ::service_kit::handler::RawBody,
+        ) -> ::service_kit::handler::DynHandlerFuture {
             let __params_json = __params_ref.clone();
+            #[allow(unused_variables)]
+            let __api_state = __state_ref.clone();
             Box::pin(async move {
                 let params = __params_json.clone();
                 #(#arg_prepare_tokens)*
-                let __resp = #fn_ident(#(#call_args_tokens),*).await;
-                let __resp = ::axum::response::IntoResponse::into_response(__resp);
-                Ok(__resp)
+                #into_response_tokens
+                Ok::<_, ::service_kit::error::Error>(__resp)
             })
         }
 
         // Register executable handler
         ::service_kit::inventory::submit! {
             ::service_kit::handler::ApiHandlerInventory {
-                operation_id: #fn_name_str,
+                operation_id: #operation_id,
                 handler: #exec_fn_ident,
             }
         }
@@ -230,10 +782,537 @@ pub fn api(args: TokenStream, input: TokenStream) -> TokenStream {
     output.into()
 }
 
+/// The extension trait behind `service_kit::client!`: one method named after the handler whose
+/// arguments and return type are the handler's own DTOs. Expands to nothing unless `service_kit`
+/// is built with the `client` feature.
+fn client_trait(
+    item_fn: &ItemFn,
+    args: &ApiMacroArgs,
+    inferred: &InferredResponses,
+    deprecated: bool,
+    metadata_ident: &Ident,
+) -> proc_macro2::TokenStream {
+    let fn_ident = &item_fn.sig.ident;
+    let vis = &item_fn.vis;
+    let trait_ident = format_ident!("{}Client", fn_ident.to_string().to_upper_camel_case());
+    let path_placeholders = parse_path_placeholders(&args.path.value());
+    let mut params = Vec::new();
+    let mut calls = Vec::new();
+
+    for arg in &item_fn.sig.inputs {
+        let FnArg::Typed(pat_type) = arg else { continue };
+        let ty = &*pat_type.ty;
+        let binding = binding_ident(&pat_type.pat);
+        if let Some(inner_type) = get_inner_type(ty, "Path") {
+            let tuple_pat = match &*pat_type.pat {
+                Pat::TupleStruct(pat_tuple) => match pat_tuple.elems.first() {
+                    Some(Pat::Tuple(t)) => Some(t),
+                    _ => None,
+                },
+                _ => None,
+            };
+            if let Some(tuple_ty) = non_unit_tuple(inner_type) {
+                let idents: Vec<Ident> = (0..tuple_ty.elems.len())
+                    .map(|i| {
+                        tuple_pat.and_then(|t| t.elems.iter().nth(i)).and_then(binding_ident).unwrap_or_else(|| {
+                            match path_placeholders.get(i) {
+                                Some(name) => format_ident!("{}", name),
+                                None => format_ident!("param{}", i),
+                            }
+                        })
+                    })
+                    .collect();
+                let elem_tys = tuple_ty.elems.iter();
+                params.push(quote! { #(#idents: #elem_tys),* });
+                calls.push(quote! { .path(&(#(#idents,)*)) });
+            } else {
+                let ident = binding.unwrap_or_else(|| match path_placeholders.as_slice() {
+                    [name] => format_ident!("{}", name),
+                    _ => format_ident!("path"),
+                });
+                params.push(quote! { #ident: #inner_type });
+                calls.push(quote! { .path(&#ident) });
+            }
+        } else if let Some((query_ty, extractor_optional)) = query_extractor_type(ty) {
+            let ident = binding.unwrap_or_else(|| format_ident!("query"));
+            if extractor_optional {
+                params.push(quote! { #ident: Option<#query_ty> });
+            } else {
+                params.push(quote! { #ident: #query_ty });
+            }
+            calls.push(quote! { .query(&#ident) });
+        } else if let Some(header_ty) = get_inner_type(ty, "TypedHeader") {
+            let ident = binding.unwrap_or_else(|| format_ident!("header"));
+            params.push(quote! { #ident: #header_ty });
+            calls.push(quote! {
+                .header_values(<#header_ty as ::axum_extra::headers::Header>::name().as_str(), {
+                    let mut values = Vec::new();
+                    ::axum_extra::headers::Header::encode(&#ident, &mut values);
+                    values
+                })
+            });
+        } else if let Some(body) = body_extractor(ty) {
+            let ident = binding.unwrap_or_else(|| format_ident!("body"));
+            let content_type = body.content_type();
+            match body {
+                BodyExtractor::Json(inner_type) => {
+                    params.push(quote! { #ident: #inner_type });
+                    calls.push(quote! { .json(&#ident) });
+                }
+                BodyExtractor::Form(inner_type) => {
+                    params.push(quote! { #ident: #inner_type });
+                    calls.push(quote! { .form(&#ident) });
+                }
+                BodyExtractor::Multipart => {
+                    params.push(quote! { #ident: ::service_kit::typed_client::multipart::Form });
+                    calls.push(quote! { .multipart(#ident) });
+                }
+                BodyExtractor::Bytes | BodyExtractor::Text => {
+                    params.push(quote! { #ident: #ty });
+                    calls.push(quote! { .body(#content_type, #ident) });
+                }
+            }
+        }
+        // `State` and `HeaderMap` have no counterpart on the wire.
+    }
+    let declared_headers = args.headers.iter().map(|h| (h, header_name_from_ident(&h.name), quote! { header }));
+    let declared_cookies = args.cookies.iter().map(|c| (c, c.name.to_string(), quote! { cookie }));
+    for (declared, name, method) in declared_headers.chain(declared_cookies) {
+        let ident = &declared.name;
+        let ty = &declared.ty;
+        params.push(quote! { #ident: #ty });
+        calls.push(quote! { .#method(#name, &#ident) });
+    }
+
+    // A typed 2xx entry of `responses(...)` wins over the type inferred from the signature.
+    let success = args
+        .responses
+        .iter()
+        .filter(|r| (200..300).contains(&r.status))
+        .find_map(|r| r.ty.as_ref())
+        .or(inferred.success)
+        .map(|ty| quote! { #ty })
+        .unwrap_or_else(|| quote! { serde_json::Value });
+    let (output, finish) = if inferred.streaming {
+        (quote! { ::service_kit::typed_client::EventStream<#success> }, quote! { .events::<#success>() })
+    } else if inferred.optional {
+        (quote! { Option<#success> }, quote! { .decode_optional::<#success>() })
+    } else {
+        (success.clone(), quote! { .decode::<#success>() })
+    };
+
+    let trait_doc = format!(
+        "Typed client for [`{}`], implemented for every `service_kit::typed_client::ApiClient`.",
+        fn_ident
+    );
+    let docs = item_fn.attrs.iter().filter(|attr| attr.path().is_ident("doc"));
+    let deprecated_attr = if deprecated { quote! { #[deprecated] } } else { quote! {} };
+    quote! {
+        ::service_kit::__api_client! {
+            #[doc = #trait_doc]
+            #[allow(dead_code)]
+            #vis trait #trait_ident: ::service_kit::typed_client::ApiClient {
+                #(#docs)*
+                #deprecated_attr
+                fn #fn_ident(
+                    &self,
+                    #(#params),*
+                ) -> impl ::std::future::Future<
+                    Output = ::std::result::Result<#output, ::service_kit::typed_client::Error>,
+                > + Send {
+                    ::service_kit::typed_client::Call::new(self.api_client(), &#metadata_ident)
+                        #(#calls)*
+                        #finish
+                }
+            }
+
+            impl<C: ::service_kit::typed_client::ApiClient + ?Sized> #trait_ident for C {}
+        }
+    }
+}
+
+/// The identifier bound by `Path(id)`, `Query(params)`, `mut multipart` and the like.
+fn binding_ident(pat: &Pat) -> Option<Ident> {
+    match pat {
+        Pat::Ident(pat_ident) => Some(pat_ident.ident.clone()),
+        Pat::TupleStruct(pat_tuple) if pat_tuple.elems.len() == 1 => {
+            pat_tuple.elems.first().and_then(binding_ident)
+        }
+        _ => None,
+    }
+}
+
+/// The request body extractors understood by `#[api]`.
+enum BodyExtractor<'a> {
+    Json(&'a Type),
+    Form(&'a Type),
+    Multipart,
+    Bytes,
+    Text,
+}
+
+impl BodyExtractor<'_> {
+    fn content_type(&self) -> &'static str {
+        match self {
+            BodyExtractor::Json(_) => "application/json",
+            BodyExtractor::Form(_) => "application/x-www-form-urlencoded",
+            BodyExtractor::Multipart => "multipart/form-data",
+            BodyExtractor::Bytes => "application/octet-stream",
+            BodyExtractor::Text => "text/plain",
+        }
+    }
+
+    /// Schema name of the body; raw bodies have no registered schema.
+    fn type_name(&self) -> String {
+        match self {
+            BodyExtractor::Json(ty) | BodyExtractor::Form(ty) => type_to_string(ty),
+            _ => String::new(),
+        }
+    }
+}
+
+fn body_extractor(ty: &Type) -> Option<BodyExtractor<'_>> {
+    if let Some(inner) = get_inner_type(ty, "Json") {
+        Some(BodyExtractor::Json(inner))
+    } else if let Some(inner) = get_inner_type(ty, "Form") {
+        Some(BodyExtractor::Form(inner))
+    } else if is_type_named(ty, "Multipart") {
+        Some(BodyExtractor::Multipart)
+    } else if is_type_named(ty, "Bytes") {
+        Some(BodyExtractor::Bytes)
+    } else if is_type_named(ty, "String") {
+        Some(BodyExtractor::Text)
+    } else {
+        None
+    }
+}
+
+#[derive(Default)]
+struct InferredResponses<'a> {
+    success: Option<&'a Type>,
+    error: Option<&'a Type>,
+    optional: bool,
+    streaming: bool,
+}
+
+/// Infers the documented responses from common handler return types:
+/// `Json<T>`, `Result<Json<T>, E>`, `(StatusCode, Json<T>)`, `Sse<S>` and a top-level `Option<Json<T>>`.
+fn infer_responses(ty: &Type) -> InferredResponses<'_> {
+    let mut inferred = InferredResponses::default();
+    let mut current = ty;
+    if let Some(inner) = get_inner_type(current, "Option") {
+        inferred.optional = true;
+        current = inner;
+    }
+    if let Some(ok_ty) = get_inner_type(current, "Result") {
+        inferred.error = get_generic_arg(current, "Result", 1).map(response_body_type);
+        current = ok_ty;
+    }
+    let body = response_body_type(current);
+    inferred.streaming = is_type_named(body, "Sse");
+    inferred.success = get_inner_type(body, "Json");
+    inferred
+}
+
+/// `(StatusCode, Json<T>)` -> `Json<T>`, `Json<AppError>` -> `AppError`, anything else unchanged.
+fn response_body_type(ty: &Type) -> &Type {
+    match ty {
+        Type::Tuple(tuple) => tuple.elems.last().map(response_body_type).unwrap_or(ty),
+        Type::Paren(paren) => response_body_type(&paren.elem),
+        _ => ty,
+    }
+}
+
+fn get_generic_arg<'a>(ty: &'a Type, type_name: &str, index: usize) -> Option<&'a Type> {
+    if let Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            if segment.ident == type_name {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    return args
+                        .args
+                        .iter()
+                        .filter_map(|arg| match arg {
+                            syn::GenericArgument::Type(t) => Some(t),
+                            _ => None,
+                        })
+                        .nth(index);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// `Some(|| json)` for an example given as `json!({...})` or any other expression accepted by
+/// `serde_json::json!`.
+fn example_tokens(example: Option<&syn::Expr>) -> proc_macro2::TokenStream {
+    match example {
+        Some(syn::Expr::Macro(expr_macro)) if expr_macro.mac.path.is_ident("json") => {
+            let tokens = &expr_macro.mac.tokens;
+            quote! { Some(|| serde_json::json!(#tokens)) }
+        }
+        Some(example) => quote! { Some(|| serde_json::json!(#example)) },
+        None => quote! { None },
+    }
+}
+
+/// `params(name = "...")` wins over the argument's doc comment.
+fn describe_param(docs: &[ParamDoc], name: &str, fallback: &str) -> String {
+    docs.iter()
+        .find(|doc| param_doc_matches(doc, name))
+        .map(|doc| doc.description.value())
+        .unwrap_or_else(|| fallback.to_string())
+}
+
+/// `params(x_tenant_id = "...")` also matches the header `x-tenant-id`.
+fn param_doc_matches(doc: &ParamDoc, name: &str) -> bool {
+    doc.name == name || doc.name.to_kebab_case() == name
+}
+
+/// All `///` lines of an item joined into a single paragraph.
+fn doc_text(attrs: &[Attribute]) -> String {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(nv) => match &nv.value {
+                syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) => Some(lit.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 fn type_to_string(ty: &Type) -> String {
     quote!(#ty).to_string().replace(' ', "")
 }
 
+/// A generic `#[api_dto]` instantiation such as `Page<Product>`; std containers and extractors
+/// are not DTOs and are resolved by `openapi_utils`.
+fn is_generic_dto(ty: &Type) -> bool {
+    const NON_DTO: &[&str] = &[
+        "Vec", "Option", "Box", "HashMap", "BTreeMap", "HashSet", "BTreeSet", "Result", "Json", "Sse",
+    ];
+    match ty {
+        Type::Path(type_path) => type_path.path.segments.last().is_some_and(|segment| {
+            matches!(segment.arguments, syn::PathArguments::AngleBracketed(_))
+                && !NON_DTO.iter().any(|name| segment.ident == name)
+        }),
+        _ => false,
+    }
+}
+
+/// Every generic DTO instantiation inside `ty`, outermost first: `Vec<Page<Page<Product>>>` yields
+/// `Page<Page<Product>>` and `Page<Product>`, so that each `$ref` the outer schema emits resolves.
+fn collect_generic_dtos(ty: &Type, found: &mut Vec<Type>) {
+    match ty {
+        Type::Path(type_path) => {
+            if is_generic_dto(ty) {
+                found.push(ty.clone());
+            }
+            for segment in &type_path.path.segments {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    for arg in &args.args {
+                        if let syn::GenericArgument::Type(inner) = arg {
+                            collect_generic_dtos(inner, found);
+                        }
+                    }
+                }
+            }
+        }
+        Type::Tuple(tuple) => tuple.elems.iter().for_each(|elem| collect_generic_dtos(elem, found)),
+        Type::Reference(reference) => collect_generic_dtos(&reference.elem, found),
+        Type::Array(array) => collect_generic_dtos(&array.elem, found),
+        Type::Slice(slice) => collect_generic_dtos(&slice.elem, found),
+        Type::Paren(paren) => collect_generic_dtos(&paren.elem, found),
+        _ => {}
+    }
+}
+
+/// Registers a component for every generic DTO instantiation found in `types`, once per name.
+/// Instantiations that mention one of `type_params` (e.g. `Page<T>` inside `Wrapper<T>`) have no
+/// concrete schema and are skipped.
+fn generic_dto_registrations(types: &[Type], type_params: &[Ident]) -> Vec<proc_macro2::TokenStream> {
+    let mut instances = Vec::new();
+    types.iter().for_each(|ty| collect_generic_dtos(ty, &mut instances));
+
+    let mut names = Vec::new();
+    let mut registrations = Vec::new();
+    for ty in instances {
+        let name = type_to_string(&ty);
+        if names.contains(&name) || mentions_type_param(&ty, type_params) {
+            continue;
+        }
+        registrations.push(quote! {
+            ::service_kit::inventory::submit! {
+                ::service_kit::ApiDtoMetadata {
+                    name: #name,
+                    schema_provider: || {
+                        (#name.to_string(), <#ty as ::service_kit::utoipa::PartialSchema>::schema())
+                    },
+                    nested_schemas: || {
+                        let mut schemas = Vec::new();
+                        <#ty as ::service_kit::utoipa::ToSchema>::schemas(&mut schemas);
+                        schemas
+                    },
+                }
+            }
+        });
+        names.push(name);
+    }
+    registrations
+}
+
+/// The named types a field schema `$ref`s, looking through containers and generic arguments:
+/// `Option<Vec<Dimensions>>` yields `Dimensions`. Primitives and the well-known types that
+/// `openapi_utils` maps to inline schemas are not components and are left out.
+fn collect_schema_refs(ty: &Type, found: &mut Vec<Type>) {
+    const INLINE: &[&str] = &[
+        "String", "str", "bool", "char", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64",
+        "u128", "usize", "f32", "f64", "Uuid", "DateTime", "NaiveDate", "NaiveDateTime", "NaiveTime", "Date",
+        "Time", "OffsetDateTime", "PrimitiveDateTime", "Duration", "Decimal", "Value", "Map", "Bytes", "Url",
+        "PathBuf", "Self",
+    ];
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => {
+            let Some(last) = type_path.path.segments.last() else {
+                return;
+            };
+            match &last.arguments {
+                syn::PathArguments::None if !INLINE.iter().any(|name| last.ident == name) => {
+                    found.push(ty.clone());
+                }
+                // Containers and generic DTOs: their own schema is inline or registered per
+                // instance, the type arguments may still be components
+                syn::PathArguments::AngleBracketed(args) => args.args.iter().for_each(|arg| {
+                    if let syn::GenericArgument::Type(inner) = arg {
+                        collect_schema_refs(inner, found);
+                    }
+                }),
+                _ => {}
+            }
+        }
+        Type::Tuple(tuple) => tuple.elems.iter().for_each(|elem| collect_schema_refs(elem, found)),
+        Type::Reference(reference) => collect_schema_refs(&reference.elem, found),
+        Type::Array(array) => collect_schema_refs(&array.elem, found),
+        Type::Slice(slice) => collect_schema_refs(&slice.elem, found),
+        Type::Paren(paren) => collect_schema_refs(&paren.elem, found),
+        _ => {}
+    }
+}
+
+/// Pushes the schema of every concrete component a DTO's fields reference, plus whatever those
+/// types collect through `ToSchema::schemas`. Plain `#[derive(ToSchema)]` types used as fields
+/// are registered this way; `#[api_dto]` types register themselves and take precedence.
+fn nested_schema_pushes(fields: &[&syn::Field], type_params: &[Ident]) -> Vec<proc_macro2::TokenStream> {
+    let mut referenced = Vec::new();
+    for field in fields {
+        // `value_type` replaces the field's schema, `inline` embeds it, skipped fields have none
+        if has_attr_flag(&field.attrs, "schema", "value_type")
+            || has_attr_flag(&field.attrs, "schema", "inline")
+            || has_serde_flag(&field.attrs, "skip")
+        {
+            continue;
+        }
+        collect_schema_refs(&field.ty, &mut referenced);
+    }
+
+    let mut names = Vec::new();
+    let mut pushes = Vec::new();
+    for ty in referenced {
+        let name = type_to_string(&ty);
+        if names.contains(&name) || mentions_type_param(&ty, type_params) {
+            continue;
+        }
+        pushes.push(quote! {
+            schemas.push((
+                <#ty as ::service_kit::utoipa::ToSchema>::name().into_owned(),
+                <#ty as ::service_kit::utoipa::PartialSchema>::schema(),
+            ));
+            <#ty as ::service_kit::utoipa::ToSchema>::schemas(&mut schemas);
+        });
+        names.push(name);
+    }
+    pushes
+}
+
+fn mentions_type_param(ty: &Type, type_params: &[Ident]) -> bool {
+    fn mentions(tokens: proc_macro2::TokenStream, type_params: &[Ident]) -> bool {
+        tokens.into_iter().any(|token| match token {
+            proc_macro2::TokenTree::Ident(ident) => type_params.contains(&ident),
+            proc_macro2::TokenTree::Group(group) => mentions(group.stream(), type_params),
+            _ => false,
+        })
+    }
+    !type_params.is_empty() && mentions(quote!(#ty), type_params)
+}
+
+fn is_type_named(ty: &Type, type_name: &str) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == type_name),
+        _ => false,
+    }
+}
+
+/// `x_tenant_id` -> `x-tenant-id`
+fn header_name_from_ident(ident: &Ident) -> String {
+    ident.to_string().to_kebab_case()
+}
+
+/// `TypedHeader<UserAgent>` -> `user-agent`, `TypedHeader<Authorization<Bearer>>` -> `authorization`
+fn typed_header_name(ty: &Type) -> String {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string().to_kebab_case())
+            .unwrap_or_default(),
+        _ => type_to_string(ty).to_kebab_case(),
+    }
+}
+
+/// `Path<(A, B)>` binds its elements to the placeholders by position, whether or not the
+/// pattern destructures the tuple.
+fn non_unit_tuple(ty: &Type) -> Option<&syn::TypeTuple> {
+    match ty {
+        Type::Tuple(tuple) if !tuple.elems.is_empty() => Some(tuple),
+        _ => None,
+    }
+}
+
+fn pat_ident_name(pat: &Pat) -> Option<String> {
+    match pat {
+        Pat::Ident(pat_ident) => Some(pat_ident.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// Extracts `{name}` / `{*name}` placeholders from an axum route path, in order.
+fn parse_path_placeholders(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')))
+        .map(|name| name.trim_start_matches('*').to_string())
+        .collect()
+}
+
+/// Matches `Query<T>` and `Option<Query<T>>`, returning `T` and whether the extractor itself is optional.
+fn query_extractor_type(ty: &Type) -> Option<(&Type, bool)> {
+    if let Some(inner) = get_inner_type(ty, "Query") {
+        return Some((inner, false));
+    }
+    get_inner_type(ty, "Option")
+        .and_then(|opt| get_inner_type(opt, "Query"))
+        .map(|inner| (inner, true))
+}
+
 fn get_inner_type<'a>(ty: &'a Type, type_name: &str) -> Option<&'a Type> {
     if let Type::Path(type_path) = ty {
         if let Some(segment) = type_path.path.segments.last() {
@@ -276,6 +1355,7 @@ fn parse_doc_comments(attrs: &[Attribute]) -> (String, String) {
 #[derive(Debug, Default)]
 struct ApiDtoArgs {
     rename_all: Option<String>,
+    example: Option<syn::Expr>,
 }
 
 impl syn::parse::Parse for ApiDtoArgs {
@@ -291,6 +1371,8 @@ impl syn::parse::Parse for ApiDtoArgs {
                             args.rename_all = Some(lit_str.value());
                         }
                     }
+                } else if nv.path.is_ident("example") {
+                    args.example = Some(nv.value);
                 }
             }
         }
@@ -308,6 +1390,31 @@ pub fn api_dto(attr: TokenStream, item: TokenStream) -> TokenStream {
 
     let rename_all_strategy = args.rename_all.unwrap_or_else(|| "camelCase".to_string());
 
+    // Enums rename their variants with the strategy; the fields of struct variants are renamed by a
+    // variant-level `rename_all`, which utoipa honours (unlike serde's `rename_all_fields`).
+    //
+    // Every DTO registers its own component and refers to other DTOs (itself included) by `$ref`,
+    // so utoipa's nested schema collection is disabled with `no_recursion`: it never terminates on
+    // `Vec<Self>`, `HashMap<String, Self>` or indirect cycles such as `A -> B -> A`. Unit variants
+    // hold no schemas and do not accept the attribute.
+    match &mut input.data {
+        syn::Data::Enum(data_enum) => {
+            for variant in data_enum.variants.iter_mut() {
+                if matches!(variant.fields, syn::Fields::Unit) {
+                    continue;
+                }
+                variant.attrs.push(syn::parse_quote! { #[schema(no_recursion)] });
+                if matches!(variant.fields, syn::Fields::Named(_))
+                    && serde_string_value(&variant.attrs, "rename_all").is_none()
+                {
+                    variant.attrs.push(syn::parse_quote! {
+                        #[serde(rename_all = #rename_all_strategy)]
+                    });
+                }
+            }
+        }
+        _ => input.attrs.push(syn::parse_quote! { #[schema(no_recursion)] }),
+    }
     let attributes_to_add = quote! {
         #[derive(
             Debug,
@@ -322,33 +1429,95 @@ pub fn api_dto(attr: TokenStream, item: TokenStream) -> TokenStream {
     let parsed_attrs: Vec<syn::Attribute> =
         syn::parse::Parser::parse(syn::Attribute::parse_outer, attributes_to_add.into())
             .expect("Failed to parse attributes");
-    input.attrs.extend(parsed_attrs);
-
-    if let syn::Data::Struct(ref mut data_struct) = input.data {
-        if let syn::Fields::Named(ref mut fields) = data_struct.fields {
-            for field in fields.named.iter_mut() {
-                if let Type::Path(type_path) = &field.ty {
-                    if is_recursive_type(&type_path.path, &input.ident.to_string()) {
-                        field.attrs.push(syn::parse_quote! {
-                            #[schema(value_type = Object)]
-                        });
-                    }
-                }
-            }
+    // The derives must precede container attributes such as `#[serde(tag = "...")]` that use their helpers.
+    input.attrs.splice(0..0, parsed_attrs);
+    if let Some(example) = &args.example {
+        input.attrs.push(syn::parse_quote! { #[schema(example = #example)] });
+    }
+
+    let container_default = has_serde_flag(&input.attrs, "default");
+    let named_fields: Vec<&mut syn::Field> = match &mut input.data {
+        syn::Data::Struct(data_struct) => match &mut data_struct.fields {
+            syn::Fields::Named(fields) => fields.named.iter_mut().collect(),
+            _ => Vec::new(),
+        },
+        syn::Data::Enum(data_enum) => data_enum
+            .variants
+            .iter_mut()
+            .flat_map(|variant| match &mut variant.fields {
+                syn::Fields::Named(fields) => fields.named.iter_mut().collect(),
+                _ => Vec::new(),
+            })
+            .collect(),
+        syn::Data::Union(_) => Vec::new(),
+    };
+    for field in named_fields {
+        match example_schema_attr(field) {
+            Ok(Some(schema_attr)) => field.attrs.push(schema_attr),
+            Ok(None) => {}
+            Err(e) => return e.to_compile_error().into(),
+        }
+        match validation_schema_attr(field) {
+            Ok(Some(schema_attr)) => field.attrs.push(schema_attr),
+            Ok(None) => {}
+            Err(e) => return e.to_compile_error().into(),
+        }
+        // utoipa treats `skip_serializing_if` alone as optional, but serde still rejects input that
+        // omits the field unless it also has a default, so the schema keeps such fields required.
+        if get_inner_type(&field.ty, "Option").is_none()
+            && has_serde_flag(&field.attrs, "skip_serializing_if")
+            && !has_serde_flag(&field.attrs, "default")
+            && !container_default
+        {
+            field.attrs.push(syn::parse_quote! {
+                #[schema(required = true)]
+            });
         }
     }
 
-    // 注册 DTO schema 到 inventory
-    let registration = quote! {
-        ::service_kit::inventory::submit! {
-            ::service_kit::ApiDtoMetadata {
-                name: #type_name_str,
-                schema_provider: || {
-                    (
-                        #type_name_str.to_string(),
-                        <#type_name as ::service_kit::utoipa::PartialSchema>::schema(),
-                    )
-                },
+    // Internally (`tag = "..."`) and adjacently (`tag`, `content`) tagged enums document the tag
+    // field as the `oneOf` discriminator.
+    let discriminator = match input.data {
+        syn::Data::Enum(_) => serde_string_value(&input.attrs, "tag"),
+        _ => None,
+    };
+    let set_discriminator = discriminator.map(|tag| {
+        quote! { ::service_kit::openapi_utils::set_discriminator(&mut schema, #tag); }
+    });
+
+    // 字段中用到的泛型 DTO 实例（如 `Page<Product>`）同样注册为组件，保证字段的 `$ref` 可解析
+    let type_params: Vec<Ident> = input.generics.type_params().map(|param| param.ident.clone()).collect();
+    let fields: Vec<&syn::Field> = match &input.data {
+        syn::Data::Struct(data_struct) => data_struct.fields.iter().collect(),
+        syn::Data::Enum(data_enum) => data_enum.variants.iter().flat_map(|variant| variant.fields.iter()).collect(),
+        syn::Data::Union(_) => Vec::new(),
+    };
+    let field_types: Vec<Type> = fields.iter().map(|field| field.ty.clone()).collect();
+    let field_registrations = generic_dto_registrations(&field_types, &type_params);
+    // 字段引用的普通 `ToSchema` 类型（未标注 `#[api_dto]`）随本 DTO 一并注册
+    let nested_pushes = nested_schema_pushes(&fields, &type_params);
+
+    // 注册 DTO schema 到 inventory；泛型 DTO 由 `#[api]` 按具体实例（如 `Page<Product>`）注册
+    let registration = if !type_params.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            ::service_kit::inventory::submit! {
+                ::service_kit::ApiDtoMetadata {
+                    name: #type_name_str,
+                    schema_provider: || {
+                        #[allow(unused_mut)]
+                        let mut schema = <#type_name as ::service_kit::utoipa::PartialSchema>::schema();
+                        #set_discriminator
+                        (#type_name_str.to_string(), schema)
+                    },
+                    nested_schemas: || {
+                        #[allow(unused_mut)]
+                        let mut schemas = Vec::new();
+                        #(#nested_pushes)*
+                        schemas
+                    },
+                }
             }
         }
     };
@@ -356,41 +1525,128 @@ pub fn api_dto(attr: TokenStream, item: TokenStream) -> TokenStream {
     let output = quote! {
         #input
         #registration
+        #(#field_registrations)*
     };
 
     output.into()
 }
 
-fn is_recursive_type(path: &syn::Path, self_name: &str) -> bool {
-    if let Some(segment) = path.segments.last() {
-        let type_name = segment.ident.to_string();
-        if type_name == "Box" || type_name == "Option" {
-            if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-                if let Some(syn::GenericArgument::Type(Type::Path(inner_type_path))) = args.args.first()
-                {
-                    if type_name == "Option" {
-                        if let Some(inner_segment) = inner_type_path.path.segments.last() {
-                            if inner_segment.ident == "Box" {
-                                return is_recursive_boxed_type(inner_segment, self_name);
-                            }
-                        }
-                    } else {
-                        return is_recursive_boxed_type(segment, self_name);
+/// Turns a field-level `#[example = ...]` (a literal or `json!(...)`) into `#[schema(example = ...)]`.
+fn example_schema_attr(field: &mut syn::Field) -> Result<Option<Attribute>> {
+    let Some(index) = field.attrs.iter().position(|attr| attr.path().is_ident("example")) else {
+        return Ok(None);
+    };
+    let attr = field.attrs.remove(index);
+    let example = &attr.meta.require_name_value()?.value;
+    Ok(Some(syn::parse_quote! { #[schema(example = #example)] }))
+}
+
+/// Turns `#[validate(length(min = .., max = ..), range(min = .., max = ..), pattern = "..")]` into the
+/// matching `#[schema(...)]` constraints and removes the `validate` attributes. The generated handler
+/// wrapper checks incoming values against those constraints before calling the handler.
+fn validation_schema_attr(field: &mut syn::Field) -> Result<Option<Attribute>> {
+    let (validate_attrs, other_attrs): (Vec<Attribute>, Vec<Attribute>) =
+        field.attrs.drain(..).partition(|attr| attr.path().is_ident("validate"));
+    field.attrs = other_attrs;
+    if validate_attrs.is_empty() {
+        return Ok(None);
+    }
+
+    // `length` bounds the number of items for `Vec<T>` fields and the number of characters otherwise
+    let is_list = is_type_named(get_inner_type(&field.ty, "Option").unwrap_or(&field.ty), "Vec");
+    let (min_length, max_length) = if is_list { ("min_items", "max_items") } else { ("min_length", "max_length") };
+    let mut constraints = Vec::new();
+    for attr in &validate_attrs {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("length") || meta.path.is_ident("range") {
+                let is_length = meta.path.is_ident("length");
+                meta.parse_nested_meta(|bound| {
+                    let key = match (is_length, bound.path.is_ident("min"), bound.path.is_ident("max")) {
+                        (true, true, _) => min_length,
+                        (true, _, true) => max_length,
+                        (false, true, _) => "minimum",
+                        (false, _, true) => "maximum",
+                        _ => return Err(bound.error("expected `min` or `max`")),
+                    };
+                    let key = format_ident!("{}", key);
+                    let value: syn::Expr = bound.value()?.parse()?;
+                    constraints.push(quote! { #key = #value });
+                    Ok(())
+                })
+            } else if meta.path.is_ident("pattern") {
+                let pattern: LitStr = meta.value()?.parse()?;
+                // Same engine as the runtime check, so an invalid pattern fails the build instead of every request
+                if let Err(e) = regex::Regex::new(&pattern.value()) {
+                    return Err(syn::Error::new(pattern.span(), format!("invalid `pattern`: {}", e)));
+                }
+                constraints.push(quote! { pattern = #pattern });
+                Ok(())
+            } else {
+                Err(meta.error("unsupported validation, expected `length(...)`, `range(...)` or `pattern = \"...\"`"))
+            }
+        })?;
+    }
+    Ok(Some(syn::parse_quote! { #[schema(#(#constraints),*)] }))
+}
+
+/// The string value of a container-level `#[serde(key = "...")]`, e.g. `tag`.
+fn serde_string_value(attrs: &[Attribute], key: &str) -> Option<String> {
+    let mut value = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.input.peek(syn::Token![=]) {
+                let expr: syn::Expr = meta.value()?.parse()?;
+                if meta.path.is_ident(key) {
+                    if let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) = expr {
+                        value = Some(lit.value());
                     }
                 }
+            } else if meta.input.peek(syn::token::Paren) {
+                let _content;
+                syn::parenthesized!(_content in meta.input);
             }
-        }
+            Ok(())
+        });
     }
-    false
+    value
 }
 
-fn is_recursive_boxed_type(segment: &syn::PathSegment, self_name: &str) -> bool {
-    if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-        if let Some(syn::GenericArgument::Type(Type::Path(inner_type))) = args.args.first() {
-            if let Some(inner_segment) = inner_type.path.segments.last() {
-                return inner_segment.ident == self_name;
+fn has_serde_flag(attrs: &[Attribute], flag: &str) -> bool {
+    has_attr_flag(attrs, "serde", flag)
+}
+
+/// Whether an `#[<attr>(...)]` attribute lists `flag`, bare or as `flag = ..` / `flag(..)`.
+fn has_attr_flag(attrs: &[Attribute], attr_name: &str, flag: &str) -> bool {
+    attrs.iter().filter(|attr| attr.path().is_ident(attr_name)).any(|attr| {
+        let mut found = false;
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(flag) {
+                found = true;
             }
-        }
+            // Consume `= value` / `(...)` so parsing continues past the entry.
+            if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                let _content;
+                syn::parenthesized!(_content in meta.input);
+            }
+            Ok(())
+        });
+        found
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_patterns_are_rejected_at_the_attribute() {
+        let mut valid: syn::Field = syn::parse_quote! { #[validate(pattern = "^P-[0-9]+$")] code: String };
+        assert!(validation_schema_attr(&mut valid).unwrap().is_some());
+
+        let mut invalid: syn::Field = syn::parse_quote! { #[validate(pattern = "^P-(")] code: String };
+        let err = validation_schema_attr(&mut invalid).unwrap_err();
+        assert!(err.to_string().starts_with("invalid `pattern`"), "{}", err);
     }
-    false
-}
\ No newline at end of file
+}
@@ -0,0 +1,213 @@
+//! 链式构建完整的 OpenAPI 文档：paths 与 components 仍由 `build_openapi_basic` 从 inventory 汇总，
+//! 其余顶层字段（servers、contact、license、termsOfService、tags、externalDocs、`x-` 扩展）在此补充。
+//!
+//! ```ignore
+//! let openapi = ApiDocBuilder::new("Product Service API", env!("CARGO_PKG_VERSION"))
+//!     .description("All endpoints for the product service.")
+//!     .server("https://api.example.com", "Production")
+//!     .contact("Catalog team", "https://example.com/catalog", "catalog@example.com")
+//!     .license("MIT", "https://opensource.org/licenses/MIT")
+//!     .tag("catalog", "Browsing and searching products")
+//!     .external_docs("https://docs.example.com/products", "Integration guide")
+//!     .customize(|openapi| openapi.info.title.push_str(" (beta)"))
+//!     .build();
+//! ```
+
+use crate::openapi_utils::build_openapi_basic;
+use utoipa::openapi::extensions::ExtensionsBuilder;
+use utoipa::openapi::external_docs::ExternalDocs;
+use utoipa::openapi::info::{ContactBuilder, LicenseBuilder};
+use utoipa::openapi::server::ServerBuilder;
+use utoipa::openapi::tag::{Tag, TagBuilder};
+use utoipa::openapi::OpenApi;
+
+/// 未声明 `tags` 的 `#[api]` 操作默认归入的 tag
+pub const DEFAULT_TAG: &str = "App";
+
+type Customizer = Box<dyn FnOnce(&mut OpenApi)>;
+
+pub struct ApiDocBuilder {
+    title: String,
+    version: String,
+    description: Option<String>,
+    default_tag: String,
+    terms_of_service: Option<String>,
+    contact: Option<(String, String, String)>,
+    license: Option<(String, String)>,
+    servers: Vec<(String, String)>,
+    tags: Vec<(String, String)>,
+    external_docs: Option<(String, String)>,
+    extensions: Vec<(String, serde_json::Value)>,
+    customizers: Vec<Customizer>,
+}
+
+impl ApiDocBuilder {
+    pub fn new(title: &str, version: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            version: version.to_string(),
+            description: None,
+            default_tag: DEFAULT_TAG.to_string(),
+            terms_of_service: None,
+            contact: None,
+            license: None,
+            servers: Vec::new(),
+            tags: Vec::new(),
+            external_docs: None,
+            extensions: Vec::new(),
+            customizers: Vec::new(),
+        }
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = non_empty(description);
+        self
+    }
+
+    /// 未声明 `tags` 的操作归入的 tag，默认为 [`DEFAULT_TAG`]
+    pub fn default_tag(mut self, tag: &str) -> Self {
+        self.default_tag = tag.to_string();
+        self
+    }
+
+    pub fn terms_of_service(mut self, url: &str) -> Self {
+        self.terms_of_service = non_empty(url);
+        self
+    }
+
+    /// 联系方式；空字符串的字段不输出
+    pub fn contact(mut self, name: &str, url: &str, email: &str) -> Self {
+        self.contact = Some((name.to_string(), url.to_string(), email.to_string()));
+        self
+    }
+
+    /// 许可证名称与链接；链接为空时不输出
+    pub fn license(mut self, name: &str, url: &str) -> Self {
+        self.license = Some((name.to_string(), url.to_string()));
+        self
+    }
+
+    /// 追加一个 server，按调用顺序输出；第一个即生成的客户端的默认地址
+    pub fn server(mut self, url: &str, description: &str) -> Self {
+        self.servers.push((url.to_string(), description.to_string()));
+        self
+    }
+
+    /// 带描述的 tag 对象，按调用顺序排在前面；操作用到但未在此声明的 tag 按名称排在其后
+    pub fn tag(mut self, name: &str, description: &str) -> Self {
+        match self.tags.iter_mut().find(|(existing, _)| existing == name) {
+            Some(tag) => tag.1 = description.to_string(),
+            None => self.tags.push((name.to_string(), description.to_string())),
+        }
+        self
+    }
+
+    pub fn external_docs(mut self, url: &str, description: &str) -> Self {
+        self.external_docs = Some((url.to_string(), description.to_string()));
+        self
+    }
+
+    /// 顶层的 `x-` 扩展字段；OpenAPI 只允许以 `x-` 开头的扩展，缺少前缀时自动补上（`logo` -> `x-logo`）
+    pub fn extension(mut self, name: &str, value: serde_json::Value) -> Self {
+        let name = if name.starts_with("x-") { name.to_string() } else { format!("x-{}", name) };
+        self.extensions.push((name, value));
+        self
+    }
+
+    /// 在 `build` 的最后修改文档，可覆盖或补充任意字段；按注册顺序执行
+    pub fn customize(mut self, customizer: impl FnOnce(&mut OpenApi) + 'static) -> Self {
+        self.customizers.push(Box::new(customizer));
+        self
+    }
+
+    pub fn build(self) -> OpenApi {
+        let mut openapi = build_openapi_basic(&self.title, &self.version, "", &self.default_tag);
+        let info = &mut openapi.info;
+        info.description = self.description;
+        info.terms_of_service = self.terms_of_service;
+        info.contact = self.contact.map(|(name, url, email)| {
+            ContactBuilder::new()
+                .name(non_empty(&name))
+                .url(non_empty(&url))
+                .email(non_empty(&email))
+                .build()
+        });
+        info.license = self.license.map(|(name, url)| LicenseBuilder::new().name(name).url(non_empty(&url)).build());
+
+        if !self.servers.is_empty() {
+            let servers = self.servers.iter().map(|(url, description)| {
+                ServerBuilder::new().url(url).description(non_empty(description)).build()
+            });
+            openapi.servers = Some(servers.collect());
+        }
+
+        let mut tags: Vec<Tag> = self
+            .tags
+            .iter()
+            .map(|(name, description)| TagBuilder::new().name(name).description(non_empty(description)).build())
+            .collect();
+        let mut used: Vec<String> = operation_tags(&openapi)
+            .filter(|name| !tags.iter().any(|tag| tag.name == *name))
+            .collect();
+        used.sort();
+        used.dedup();
+        tags.extend(used.into_iter().map(Tag::new));
+        if !tags.is_empty() {
+            openapi.tags = Some(tags);
+        }
+
+        openapi.external_docs = self.external_docs.map(|(url, description)| {
+            let mut docs = ExternalDocs::new(url);
+            docs.description = non_empty(&description);
+            docs
+        });
+        if !self.extensions.is_empty() {
+            let extensions = self
+                .extensions
+                .into_iter()
+                .fold(ExtensionsBuilder::new(), |builder, (name, value)| builder.add(name, value))
+                .build();
+            openapi.extensions = Some(extensions);
+        }
+
+        for customize in self.customizers {
+            customize(&mut openapi);
+        }
+        openapi
+    }
+}
+
+/// 所有操作引用的 tag 名称（可能重复）
+fn operation_tags(openapi: &OpenApi) -> impl Iterator<Item = String> + '_ {
+    openapi
+        .paths
+        .paths
+        .values()
+        .flat_map(|item| {
+            [&item.get, &item.put, &item.post, &item.delete, &item.options, &item.head, &item.patch, &item.trace]
+        })
+        .flatten()
+        .flat_map(|operation| operation.tags.iter().flatten().cloned())
+}
+
+fn non_empty(text: &str) -> Option<String> {
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extensions_always_carry_the_x_prefix() {
+        let openapi = ApiDocBuilder::new("Test", "0.0.0")
+            .extension("x-audience", serde_json::json!("internal"))
+            .extension("logo", serde_json::json!({ "url": "logo.png" }))
+            .build();
+        let spec = serde_json::to_value(&openapi).unwrap();
+
+        assert_eq!(spec["x-audience"], "internal");
+        assert_eq!(spec["x-logo"]["url"], "logo.png");
+        assert!(spec.get("logo").is_none());
+    }
+}
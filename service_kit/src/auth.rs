@@ -0,0 +1,127 @@
+//! 认证：`#[api(..., security = "bearer")]` 声明的安全方案，由注册在 REST / MCP 路由构建器上的
+//! `Authenticator` 统一校验，两种传输方式共用同一套规则。
+
+use crate::error::{Error, Result};
+use axum::http::{header, HeaderMap};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use utoipa::openapi::path::Operation;
+use utoipa::openapi::security::{Http, HttpAuthScheme, SecurityScheme};
+use utoipa::openapi::OpenApi;
+
+/// HTTP Bearer 令牌：`Authorization: Bearer <token>`
+pub const BEARER: &str = "bearer";
+
+/// MCP 工具调用 `_meta` 中携带凭证的字段，值与 `Authorization` 请求头相同；
+/// 供 stdio 等没有 HTTP 请求头的传输方式使用
+pub const MCP_AUTHORIZATION_META: &str = "authorization";
+
+/// 从请求中取得的凭证
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Credentials {
+    /// 安全方案名，例如 `bearer`
+    pub scheme: String,
+    /// 凭证本身（Bearer 令牌）
+    pub token: String,
+}
+
+pub type AuthFuture<'a> = Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
+
+/// 校验调用方凭证；拒绝时返回 `Error::Unauthorized`（REST 为 401，MCP 为工具错误）
+pub trait Authenticator: Send + Sync + 'static {
+    fn authenticate<'a>(&'a self, operation_id: &'a str, credentials: &'a Credentials) -> AuthFuture<'a>;
+}
+
+/// 同步校验的闭包，返回 `true` 表示通过
+impl<F> Authenticator for F
+where
+    F: Fn(&str, &Credentials) -> bool + Send + Sync + 'static,
+{
+    fn authenticate<'a>(&'a self, operation_id: &'a str, credentials: &'a Credentials) -> AuthFuture<'a> {
+        let accepted = self(operation_id, credentials);
+        Box::pin(async move {
+            if accepted {
+                Ok(())
+            } else {
+                Err(Error::Unauthorized("invalid credentials".to_string()))
+            }
+        })
+    }
+}
+
+pub type SharedAuthenticator = Arc<dyn Authenticator>;
+
+/// 安全方案在 `components.securitySchemes` 中的定义
+pub fn security_scheme(name: &str) -> Option<SecurityScheme> {
+    match name {
+        BEARER => Some(SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer))),
+        _ => None,
+    }
+}
+
+/// 操作要求的安全方案（满足任意一个即可），未声明时沿用文档级 `security`。
+/// 包含空的安全需求 `{}` 时表示认证可选，返回空列表。
+pub fn required_schemes(openapi: &OpenApi, operation: &Operation) -> Vec<String> {
+    let Some(requirements) = operation.security.as_ref().or(openapi.security.as_ref()) else {
+        return Vec::new();
+    };
+    let mut schemes = Vec::new();
+    for requirement in requirements {
+        let names: Vec<String> = serde_json::to_value(requirement)
+            .ok()
+            .and_then(|v| v.as_object().map(|obj| obj.keys().cloned().collect()))
+            .unwrap_or_default();
+        if names.is_empty() {
+            return Vec::new();
+        }
+        schemes.extend(names);
+    }
+    schemes
+}
+
+/// 按请求头中的 `Authorization` 提取凭证
+pub fn credentials_from_headers(schemes: &[String], headers: &HeaderMap) -> Option<Credentials> {
+    let authorization = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (auth_scheme, token) = authorization.trim().split_once(' ')?;
+    schemes
+        .iter()
+        .find(|scheme| scheme.as_str() == BEARER && auth_scheme.eq_ignore_ascii_case("bearer"))
+        .map(|scheme| Credentials {
+            scheme: scheme.clone(),
+            token: token.trim().to_string(),
+        })
+}
+
+/// 校验一次调用：无需认证的操作直接放行，其余交给 `authenticator`
+pub async fn authorize(
+    authenticator: Option<&dyn Authenticator>,
+    operation_id: &str,
+    schemes: &[String],
+    headers: &HeaderMap,
+) -> Result<()> {
+    if schemes.is_empty() {
+        return Ok(());
+    }
+    let authenticator = authenticator
+        .ok_or_else(|| Error::Unauthorized("no authenticator registered".to_string()))?;
+    let credentials = credentials_from_headers(schemes, headers)
+        .ok_or_else(|| Error::Unauthorized(format!("missing `{}` credentials", schemes.join("` or `"))))?;
+    authenticator.authenticate(operation_id, &credentials).await
+}
+
+/// 需要认证的操作必须注册 `Authenticator`，否则在构建路由时报错
+pub(crate) fn ensure_authenticator(
+    authenticator: Option<&SharedAuthenticator>,
+    operation_id: &str,
+    schemes: &[String],
+) -> Result<()> {
+    if schemes.is_empty() || authenticator.is_some() {
+        return Ok(());
+    }
+    Err(Error::SpecError(format!(
+        "operation `{}` requires `{}` authentication but no authenticator is registered",
+        operation_id,
+        schemes.join("` or `")
+    )))
+}
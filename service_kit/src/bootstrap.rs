@@ -6,10 +6,15 @@ pub fn build_openapi_from_inventory(title: &str, version: &str, description: &st
     openapi_utils::build_openapi_basic(title, version, description, tag)
 }
 
+/// 从 inventory 元数据构建 REST Router 的 builder，可继续链式调用 `.with_state(state)`
+pub fn rest_router_builder_from_inventory(title: &str, version: &str, description: &str, tag: &str) -> RestRouterBuilder {
+    let openapi = build_openapi_from_inventory(title, version, description, tag);
+    RestRouterBuilder::new().openapi(openapi)
+}
+
 /// 从 inventory 元数据直接构建 REST Router
 pub fn rest_router_from_inventory(title: &str, version: &str, description: &str, tag: &str) -> crate::error::Result<axum::Router> {
-    let openapi = build_openapi_from_inventory(title, version, description, tag);
-    RestRouterBuilder::new().openapi(openapi).build()
+    rest_router_builder_from_inventory(title, version, description, tag).build()
 }
 
 /// 从已有的 OpenAPI 文档构建 REST Router
@@ -17,11 +22,17 @@ pub fn rest_router_from_openapi(openapi: OpenApi) -> crate::error::Result<axum::
     RestRouterBuilder::new().openapi(openapi).build()
 }
 
+#[cfg(all(not(target_arch = "wasm32"), feature = "mcp"))]
+/// 从 inventory 元数据构建 MCP ToolRouter 的 builder，可继续链式调用 `.with_state(state)`
+pub fn mcp_router_builder_from_inventory(title: &str, version: &str, description: &str, tag: &str) -> crate::openapi_to_mcp::OpenApiMcpRouterBuilder {
+    let openapi = build_openapi_from_inventory(title, version, description, tag);
+    crate::openapi_to_mcp::OpenApiMcpRouterBuilder::new().openapi(openapi)
+}
+
 #[cfg(all(not(target_arch = "wasm32"), feature = "mcp"))]
 /// 从 inventory 元数据直接构建 MCP ToolRouter
 pub fn mcp_router_from_inventory<S: Send + Sync + 'static>(title: &str, version: &str, description: &str, tag: &str) -> crate::error::Result<rmcp::handler::server::router::tool::ToolRouter<S>> {
-    let openapi = build_openapi_from_inventory(title, version, description, tag);
-    crate::openapi_to_mcp::OpenApiMcpRouterBuilder::new().openapi(openapi).build()
+    mcp_router_builder_from_inventory(title, version, description, tag).build()
 }
 
 #[cfg(all(not(target_arch = "wasm32"), feature = "mcp"))]
@@ -1,5 +1,5 @@
 use clap::{Arg, Command};
-use oas::{OpenAPIV3, PathItem, Referenceable};
+use oas::{OpenAPIV3, Operation, PathItem, Referenceable};
 
 pub fn build_cli_from_spec(spec: &OpenAPIV3) -> Command {
     let app = Command::new("forge-api-cli")
@@ -8,17 +8,79 @@ pub fn build_cli_from_spec(spec: &OpenAPIV3) -> Command {
         .about("A dynamic OpenAPI CLI client. After providing the URL, use one of the generated subcommands.")
         .arg_required_else_help(true);
 
-    spec.paths.iter().fold(app, |acc, (path, path_item)| {
-        add_operations_as_subcommands(acc, path, path_item)
-    })
+    // Group subcommands by their first tag in `--help`; untagged operations come last.
+    let mut sub_commands: Vec<(Option<String>, Command)> = spec
+        .paths
+        .iter()
+        .flat_map(|(path, path_item)| operations_as_subcommands(path, path_item))
+        .collect();
+    sub_commands.sort_by(|(a_tag, a), (b_tag, b)| {
+        (a_tag.is_none(), a_tag, a.get_name()).cmp(&(b_tag.is_none(), b_tag, b.get_name()))
+    });
+    sub_commands
+        .into_iter()
+        .enumerate()
+        .fold(app, |acc, (order, (_, sub_command))| acc.subcommand(sub_command.display_order(order)))
 }
 
-fn add_operations_as_subcommands(mut app: Command, path: &str, item: &PathItem) -> Command {
+pub const JSON: &str = "application/json";
+pub const FORM: &str = "application/x-www-form-urlencoded";
+pub const MULTIPART: &str = "multipart/form-data";
+pub const OCTET_STREAM: &str = "application/octet-stream";
+pub const TEXT: &str = "text/plain";
+
+/// The request content type the CLI sends for an operation, in order of preference.
+pub fn request_content_type(request_body: &oas::RequestBody) -> Option<&'static str> {
+    [JSON, FORM, MULTIPART, OCTET_STREAM, TEXT]
+        .into_iter()
+        .find(|ct| request_body.content.contains_key(*ct))
+}
+
+/// Parses a `--file field=@path` value; the `@` is optional.
+pub fn parse_file_arg(value: &str) -> Option<(&str, &str)> {
+    let (field, path) = value.split_once('=')?;
+    let path = path.strip_prefix('@').unwrap_or(path);
+    (!field.is_empty() && !path.is_empty()).then_some((field, path))
+}
+
+/// Whether the operation declares a security requirement (the CLI then accepts `--token`).
+pub fn requires_auth(operation: &Operation) -> bool {
+    operation
+        .security
+        .as_ref()
+        .is_some_and(|requirements| requirements.iter().any(|requirement| !requirement.data.is_empty()))
+}
+
+/// Warning shown before calling an operation marked `deprecated` in the spec.
+pub fn deprecation_warning(subcommand_name: &str, operation: &Operation) -> Option<String> {
+    operation
+        .deprecated
+        .unwrap_or(false)
+        .then(|| format!("warning: `{}` is deprecated and may be removed in a future version", subcommand_name))
+}
+
+/// Appends the spec's example (declared on the parameter / media type, else on its schema) to a help text.
+fn with_example(
+    help: String,
+    example: Option<&serde_json::Value>,
+    schema: Option<&Referenceable<oas::Schema>>,
+) -> String {
+    let schema_example = match schema {
+        Some(Referenceable::Data(schema)) => schema.extras.get("example"),
+        _ => None,
+    };
+    match example.or(schema_example) {
+        Some(example) if help.is_empty() => format!("Example: {}", example),
+        Some(example) => format!("{} Example: {}", help, example),
+        None => help,
+    }
+}
+
+fn operations_as_subcommands(path: &str, item: &PathItem) -> Vec<(Option<String>, Command)> {
     let command_name_prefix = path
         .trim_start_matches('/')
         .replace('/', ".")
-        .replace('{', "")
-        .replace('}', "");
+        .replace(['{', '}'], "");
 
     let operations = [
         ("GET", &item.get),
@@ -28,12 +90,21 @@ fn add_operations_as_subcommands(mut app: Command, path: &str, item: &PathItem)
         ("PATCH", &item.patch),
     ];
 
+    let mut sub_commands = Vec::new();
     for (method, op_opt) in &operations {
         if let Some(op) = op_opt {
             let command_name = format!("{}.{}", command_name_prefix, method.to_lowercase());
             let static_command_name: &'static str = Box::leak(command_name.into_boxed_str());
             
-            let sub_command_about: &'static str = Box::leak(op.summary.as_deref().unwrap_or_else(|| op.description.as_deref().unwrap_or("")).to_owned().into_boxed_str());
+            let tag = op.tags.as_ref().and_then(|tags| tags.first()).cloned();
+            let mut about = op.summary.as_deref().unwrap_or_else(|| op.description.as_deref().unwrap_or("")).to_owned();
+            if let Some(tag) = &tag {
+                about = format!("[{}] {}", tag, about);
+            }
+            if op.deprecated.unwrap_or(false) {
+                about.push_str(" (deprecated)");
+            }
+            let sub_command_about: &'static str = Box::leak(about.into_boxed_str());
             let mut sub_command = Command::new(static_command_name)
                 .about(sub_command_about);
 
@@ -41,7 +112,11 @@ fn add_operations_as_subcommands(mut app: Command, path: &str, item: &PathItem)
                 for param_ref in params {
                     if let Referenceable::Data(param) = param_ref {
                         let arg_name: &'static str = Box::leak(param.name.clone().into_boxed_str());
-                        let arg_help = param.description.as_deref().unwrap_or("").to_owned();
+                        let arg_help = with_example(
+                            param.description.as_deref().unwrap_or("").to_owned(),
+                            param.example.as_ref(),
+                            param.schema.as_ref(),
+                        );
 
                         let arg = Arg::new(arg_name)
                             .long(arg_name)
@@ -54,19 +129,49 @@ fn add_operations_as_subcommands(mut app: Command, path: &str, item: &PathItem)
             }
 
             if let Some(Referenceable::Data(request_body)) = &op.request_body {
-                if request_body.content.contains_key("application/json") {
+                if let Some(content_type) = request_content_type(request_body) {
+                    let default_help = match content_type {
+                        JSON => "The JSON request body as a string.",
+                        FORM => "The form fields as a JSON object.",
+                        MULTIPART => "The text form fields as a JSON object; use --file for files.",
+                        _ => "The raw request body; use --file body=@PATH to send a file.",
+                    };
+                    let body_help = match request_body.description.as_deref() {
+                        Some(desc) if !desc.is_empty() => format!("{} ({})", desc, default_help),
+                        _ => default_help.to_string(),
+                    };
+                    let media_type = &request_body.content[content_type];
+                    let body_help = with_example(body_help, media_type.example.as_ref(), media_type.schema.as_ref());
+                    let accepts_files = content_type == MULTIPART || content_type == OCTET_STREAM;
                     let arg = Arg::new("body")
                         .long("body")
-                        .help("The JSON request body as a string.")
-                        .required(request_body.required.unwrap_or(false))
+                        .help(body_help)
+                        .required(request_body.required.unwrap_or(false) && !accepts_files)
                         .action(clap::ArgAction::Set);
                     sub_command = sub_command.arg(arg);
+                    if accepts_files {
+                        let arg = Arg::new("file")
+                            .long("file")
+                            .value_name("FIELD=@PATH")
+                            .help("Upload a file as the given form field (repeatable)")
+                            .action(clap::ArgAction::Append);
+                        sub_command = sub_command.arg(arg);
+                    }
                 }
             }
-            app = app.subcommand(sub_command);
+
+            if requires_auth(op) {
+                let arg = Arg::new("token")
+                    .long("token")
+                    .help("Bearer token sent in the Authorization header")
+                    .action(clap::ArgAction::Set);
+                sub_command = sub_command.arg(arg);
+            }
+
+            sub_commands.push((tag, sub_command));
         }
     }
-    app
+    sub_commands
 }
 
 
@@ -10,6 +10,8 @@ use reqwest::Client;
 use serde_json::Value;
 #[cfg(not(target_arch = "wasm32"))]
 use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::cli;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub async fn fetch_openapi_spec(base_url: &str) -> Result<OpenAPIV3> {
@@ -39,12 +41,15 @@ pub async fn execute_request(
 
     let parts: Vec<&str> = subcommand_name.split('.').collect();
     let method_str = parts.last().unwrap().to_uppercase();
-    let path_template = format!("/{}", parts[..parts.len() - 1].join("/"));
+    let command_path = format!("/{}", parts[..parts.len() - 1].join("/"));
 
-    let path_item = spec
+    // Command names drop the braces of path placeholders: `/v1/products/{id}` -> `v1.products.id`
+    let (path_template, path_item) = spec
         .paths
-        .get(&path_template)
-        .ok_or_else(|| Error::SpecError(format!("Path not found for {}", path_template)))?;
+        .iter()
+        .find(|(key, _)| key.replace(['{', '}'], "") == command_path)
+        .map(|(key, item)| (key.clone(), item))
+        .ok_or_else(|| Error::SpecError(format!("Path not found for {}", command_path)))?;
 
     let operation = match method_str.as_str() {
         "GET" => path_item.get.as_ref(),
@@ -56,8 +61,14 @@ pub async fn execute_request(
     }
     .ok_or_else(|| Error::SpecError(format!("Operation not found for {}", subcommand_name)))?;
 
+    if let Some(warning) = crate::cli::deprecation_warning(subcommand_name, operation) {
+        eprintln!("{}", warning);
+    }
+
     let mut final_path = path_template.clone();
     let mut query_params = HashMap::new();
+    let mut header_params = HashMap::new();
+    let mut cookie_params = Vec::new();
 
     if let Some(params) = &operation.parameters {
         for param_ref in params {
@@ -70,7 +81,12 @@ pub async fn execute_request(
                         oas::ParameterIn::Query => {
                             query_params.insert(param.name.clone(), value.clone());
                         }
-                        _ => {}
+                        oas::ParameterIn::Header => {
+                            header_params.insert(param.name.clone(), value.clone());
+                        }
+                        oas::ParameterIn::Cookie => {
+                            cookie_params.push(format!("{}={}", param.name, value));
+                        }
                     }
                 }
             }
@@ -95,12 +111,63 @@ pub async fn execute_request(
         _ => return Err(Error::SpecError(format!("Unsupported method {}", method_str))),
     };
 
+    for (name, value) in &header_params {
+        request_builder = request_builder.header(name.as_str(), value.as_str());
+    }
+    if !cookie_params.is_empty() {
+        request_builder = request_builder.header(reqwest::header::COOKIE, cookie_params.join("; "));
+    }
+    if let Some(token) = matches.try_get_one::<String>("token").ok().flatten() {
+        request_builder = request_builder.bearer_auth(token);
+    }
+
     if let Some(oas::Referenceable::Data(request_body)) = &operation.request_body {
-        if request_body.content.contains_key("application/json") {
-            if let Some(body_str) = matches.get_one::<String>("body") {
-                let json_body: Value = serde_json::from_str(body_str)?;
-                request_builder = request_builder.json(&json_body);
+        let body_arg = matches.get_one::<String>("body");
+        let mut files = Vec::new();
+        for file_arg in matches.try_get_many::<String>("file").ok().flatten().into_iter().flatten() {
+            let (field, path) = cli::parse_file_arg(file_arg)
+                .ok_or_else(|| Error::BadRequest(format!("Invalid --file `{}`, expected FIELD=@PATH", file_arg)))?;
+            files.push((field.to_string(), std::path::PathBuf::from(path)));
+        }
+        match cli::request_content_type(request_body) {
+            Some(cli::JSON) => {
+                if let Some(body_str) = body_arg {
+                    let json_body: Value = serde_json::from_str(body_str)?;
+                    request_builder = request_builder.json(&json_body);
+                }
+            }
+            Some(cli::FORM) => {
+                if let Some(body_str) = body_arg {
+                    let fields: HashMap<String, Value> = serde_json::from_str(body_str)?;
+                    let fields: HashMap<String, String> = fields.into_iter().map(|(k, v)| (k, form_value(v))).collect();
+                    request_builder = request_builder.form(&fields);
+                }
+            }
+            Some(cli::MULTIPART) => {
+                let mut form = reqwest::multipart::Form::new();
+                if let Some(body_str) = body_arg {
+                    let fields: HashMap<String, Value> = serde_json::from_str(body_str)?;
+                    for (name, value) in fields {
+                        form = form.text(name, form_value(value));
+                    }
+                }
+                for (field, path) in files {
+                    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                    let part = reqwest::multipart::Part::bytes(std::fs::read(&path)?).file_name(file_name);
+                    form = form.part(field, part);
+                }
+                request_builder = request_builder.multipart(form);
+            }
+            Some(content_type) => {
+                let body = match files.into_iter().next() {
+                    Some((_, path)) => std::fs::read(path)?,
+                    None => body_arg.map(|b| b.clone().into_bytes()).unwrap_or_default(),
+                };
+                request_builder = request_builder
+                    .header(reqwest::header::CONTENT_TYPE, content_type)
+                    .body(body);
             }
+            None => {}
         }
     }
 
@@ -108,6 +175,15 @@ pub async fn execute_request(
     let status = response.status();
     println!("<-- Response Status: {}", status);
 
+    let is_event_stream = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with(crate::sse::EVENT_STREAM));
+    if is_event_stream {
+        return print_event_stream(response).await;
+    }
+
     let response_body = response.text().await?;
     if let Ok(json_body) = serde_json::from_str::<Value>(&response_body) {
         println!("{}", serde_json::to_string_pretty(&json_body)?);
@@ -118,4 +194,41 @@ pub async fn execute_request(
     Ok(())
 }
 
+/// 逐个打印到达的 SSE 事件，直到服务端关闭流
+#[cfg(not(target_arch = "wasm32"))]
+async fn print_event_stream(mut response: reqwest::Response) -> Result<()> {
+    use std::io::Write;
+
+    let mut parser = crate::sse::SseParser::new();
+    let print = |event: crate::sse::SseEvent| -> Result<()> {
+        if let Some(name) = &event.event {
+            println!("event: {}", name);
+        }
+        match serde_json::from_str::<Value>(&event.data) {
+            Ok(json) => println!("{}", serde_json::to_string_pretty(&json)?),
+            Err(_) => println!("{}", event.data),
+        }
+        std::io::stdout().flush()?;
+        Ok(())
+    };
+    while let Some(chunk) = response.chunk().await? {
+        for event in parser.feed(&chunk) {
+            print(event)?;
+        }
+    }
+    if let Some(event) = parser.finish() {
+        print(event)?;
+    }
+    Ok(())
+}
+
+/// 表单字段值：字符串原样发送，其它 JSON 值按其文本形式发送
+#[cfg(not(target_arch = "wasm32"))]
+fn form_value(value: Value) -> String {
+    match value {
+        Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
 
@@ -0,0 +1,351 @@
+//! 由 OpenAPI 规范生成客户端代码，供 `cargo forge` 的生成类命令使用。
+//!
+//! 生成器只读取规范的 JSON，不依赖 Node.js 等外部工具；输出按名称排序、不含时间戳，
+//! 同一份规范总是生成相同的代码，适合提交到仓库。
+
+use serde_json::Value;
+
+pub mod rust_client;
+pub mod typescript;
+pub mod typescript_client;
+
+/// 早期版本的 `build_openapi_basic` 为 Rust 基础类型注册的组件，生成代码时跳过
+const PRIMITIVE_COMPONENTS: &[&str] = &["String", "str", "i32", "i64", "u32", "u64", "f32", "f64", "bool"];
+
+/// 按名称排序的 `components.schemas`（不含基础类型组件）
+pub fn component_schemas(spec: &Value) -> Vec<(&str, &Value)> {
+    let mut schemas: Vec<(&str, &Value)> = spec
+        .pointer("/components/schemas")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .filter(|(name, _)| !PRIMITIVE_COMPONENTS.contains(&name.as_str()))
+        .map(|(name, schema)| (name.as_str(), schema))
+        .collect();
+    schemas.sort_by_key(|(name, _)| *name);
+    schemas
+}
+
+/// `$ref`（`#/components/schemas/Product`）指向的组件名
+pub fn ref_name(schema: &Value) -> Option<&str> {
+    schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .map(|reference| reference.rsplit('/').next().unwrap_or(reference))
+}
+
+/// 除 `null` 之外的首个 `type`，兼容 OpenAPI 3.1 的类型数组（`["string", "null"]`）
+pub fn primary_type(schema: &Value) -> Option<&str> {
+    match schema.get("type") {
+        Some(Value::String(ty)) => Some(ty),
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).find(|ty| *ty != "null"),
+        _ => None,
+    }
+}
+
+/// 可为 `null` 的 schema：3.0 的 `nullable: true`、3.1 的类型数组，或带 `{"type": "null"}` 分支的 `oneOf`/`anyOf`
+pub fn is_nullable(schema: &Value) -> bool {
+    schema.get("nullable").and_then(Value::as_bool) == Some(true)
+        || schema
+            .get("type")
+            .and_then(Value::as_array)
+            .is_some_and(|types| types.iter().any(|ty| ty == "null"))
+        || ["oneOf", "anyOf"]
+            .iter()
+            .filter_map(|key| schema.get(*key).and_then(Value::as_array))
+            .flatten()
+            .any(is_null_schema)
+}
+
+/// 只允许 `null` 的 schema
+pub fn is_null_schema(schema: &Value) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some("null")
+}
+
+/// `required` 中列出的属性名
+pub fn required_properties(schema: &Value) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect()
+}
+
+/// `fixedDiscount` / `in-progress` / `draft` -> `FixedDiscount` / `InProgress` / `Draft`
+pub fn pascal_case(text: &str) -> String {
+    let mut out = String::new();
+    for word in text.split(|c: char| !c.is_ascii_alphanumeric()).filter(|word| !word.is_empty()) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            out.push(first.to_ascii_uppercase());
+            out.extend(chars);
+        }
+    }
+    if out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    out
+}
+
+/// `catalog.search` / `get_product` -> `catalogSearch` / `getProduct`
+pub fn camel_case(text: &str) -> String {
+    let pascal = pascal_case(text);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => pascal,
+    }
+}
+
+/// 与 `schema` 完全相同的组件名；用于把内联的 DTO schema 还原为具名类型
+pub fn component_for<'a>(spec: &'a Value, schema: &Value) -> Option<&'a str> {
+    component_schemas(spec)
+        .into_iter()
+        .find(|(_, component)| *component == schema)
+        .map(|(name, _)| name)
+}
+
+/// `servers` 中的地址，服务器变量替换为默认值
+pub fn server_urls(spec: &Value) -> Vec<String> {
+    let servers = spec.get("servers").and_then(Value::as_array).into_iter().flatten();
+    servers
+        .filter_map(|server| {
+            let mut url = server.get("url")?.as_str()?.to_string();
+            for (name, variable) in server.get("variables").and_then(Value::as_object).into_iter().flatten() {
+                if let Some(default) = variable.get("default").and_then(Value::as_str) {
+                    url = url.replace(&format!("{{{}}}", name), default);
+                }
+            }
+            Some(url)
+        })
+        .collect()
+}
+
+/// 规范中的一个操作（`paths` 下的一个方法）
+pub struct Operation<'a> {
+    /// `operationId`；缺省时为 `<method>_<path>`
+    pub id: String,
+    /// 小写的 HTTP 方法
+    pub method: &'a str,
+    pub path: &'a str,
+    pub operation: &'a Value,
+    path_item: &'a Value,
+    spec: &'a Value,
+}
+
+/// 操作的请求体：选用的内容类型及其 schema
+pub struct RequestBody<'a> {
+    pub content_type: &'a str,
+    pub schema: Option<&'a Value>,
+    pub required: bool,
+}
+
+/// 操作的一个响应；`content_type` 为空表示没有响应体
+pub struct Response<'a> {
+    /// 状态码或 `default`
+    pub status: &'a str,
+    pub description: Option<&'a str>,
+    pub content_type: Option<&'a str>,
+    pub schema: Option<&'a Value>,
+}
+
+const METHODS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+/// 按 `operationId` 排序的全部操作
+pub fn operations(spec: &Value) -> Vec<Operation<'_>> {
+    let mut operations = Vec::new();
+    for (path, path_item) in spec.get("paths").and_then(Value::as_object).into_iter().flatten() {
+        for method in METHODS {
+            let Some(operation) = path_item.get(*method) else { continue };
+            let id = operation
+                .get("operationId")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{}_{}", method, path));
+            operations.push(Operation { id, method, path, operation, path_item, spec });
+        }
+    }
+    operations.sort_by(|a, b| a.id.cmp(&b.id));
+    operations
+}
+
+impl<'a> Operation<'a> {
+    /// 路径级与操作级的 path/query/header 参数，操作级同名参数优先
+    pub fn parameters(&self) -> Vec<&'a Value> {
+        let declared = |item: &'a Value| item.get("parameters").and_then(Value::as_array).into_iter().flatten();
+        let own: Vec<&'a Value> = declared(self.operation).collect();
+        let key = |parameter: &Value| (parameter.get("name").cloned(), parameter.get("in").cloned());
+        let inherited = declared(self.path_item).filter(|parameter| own.iter().all(|p| key(p) != key(parameter)));
+        inherited
+            .chain(own.iter().copied())
+            .filter(|parameter| matches!(parameter.get("in").and_then(Value::as_str), Some("path" | "query" | "header")))
+            .collect()
+    }
+
+    /// 请求体；有多种内容类型时优先 `application/json`
+    pub fn request_body(&self) -> Option<RequestBody<'a>> {
+        let body = self.operation.get("requestBody")?;
+        let (content_type, media) = preferred_content(body)?;
+        Some(RequestBody {
+            content_type,
+            schema: media.get("schema"),
+            required: body.get("required").and_then(Value::as_bool).unwrap_or(false),
+        })
+    }
+
+    /// 首个 2xx 响应
+    pub fn success_response(&self) -> Option<Response<'a>> {
+        self.responses().into_iter().find(|response| response.status.starts_with('2'))
+    }
+
+    /// 非 2xx 响应（含 `default`）
+    pub fn error_responses(&self) -> Vec<Response<'a>> {
+        self.responses().into_iter().filter(|response| !response.status.starts_with('2')).collect()
+    }
+
+    /// 操作自身或规范顶层声明了 `security`
+    pub fn is_secured(&self) -> bool {
+        let has_requirements = |value: Option<&Value>| value.and_then(Value::as_array).is_some_and(|requirements| !requirements.is_empty());
+        match self.operation.get("security") {
+            Some(security) => has_requirements(Some(security)),
+            None => has_requirements(self.spec.get("security")),
+        }
+    }
+
+    pub fn summary(&self) -> Option<&'a str> {
+        self.operation.get("summary").and_then(Value::as_str).filter(|summary| !summary.is_empty())
+    }
+
+    fn responses(&self) -> Vec<Response<'a>> {
+        let mut responses: Vec<Response<'a>> = self
+            .operation
+            .get("responses")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .map(|(status, response)| {
+                let content = preferred_content(response);
+                Response {
+                    status,
+                    description: response.get("description").and_then(Value::as_str),
+                    content_type: content.map(|(content_type, _)| content_type),
+                    schema: content.and_then(|(_, media)| media.get("schema")),
+                }
+            })
+            .collect();
+        // `default` 排在具体状态码之后
+        responses.sort_by_key(|response| (response.status == "default", response.status));
+        responses
+    }
+}
+
+fn preferred_content(value: &Value) -> Option<(&str, &Value)> {
+    let content = value.get("content")?.as_object()?;
+    content
+        .get_key_value("application/json")
+        .or_else(|| content.iter().next())
+        .map(|(content_type, media)| (content_type.as_str(), media))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn names_are_converted_between_cases() {
+        assert_eq!(pascal_case("fixedDiscount"), "FixedDiscount");
+        assert_eq!(pascal_case("in-progress"), "InProgress");
+        assert_eq!(pascal_case("2fa"), "_2fa");
+        assert_eq!(camel_case("catalog.search"), "catalogSearch");
+        assert_eq!(camel_case("get_product"), "getProduct");
+    }
+
+    #[test]
+    fn nullability_is_detected_in_3_0_and_3_1_forms() {
+        assert!(is_nullable(&json!({ "type": "string", "nullable": true })));
+        assert!(is_nullable(&json!({ "type": ["string", "null"] })));
+        assert!(is_nullable(&json!({ "oneOf": [{ "type": "null" }, { "$ref": "#/components/schemas/Category" }] })));
+        assert!(!is_nullable(&json!({ "type": "string" })));
+        assert_eq!(primary_type(&json!({ "type": ["null", "integer"] })), Some("integer"));
+        assert_eq!(ref_name(&json!({ "$ref": "#/components/schemas/Category" })), Some("Category"));
+    }
+
+    #[test]
+    fn primitive_components_are_skipped() {
+        let spec = json!({ "components": { "schemas": {
+            "String": { "type": "string" },
+            "Product": { "type": "object" },
+            "Category": { "type": "object", "properties": {} }
+        } } });
+
+        let names: Vec<&str> = component_schemas(&spec).into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["Category", "Product"]);
+        assert_eq!(component_for(&spec, &json!({ "type": "object" })), Some("Product"));
+    }
+
+    #[test]
+    fn server_variables_take_their_defaults() {
+        let spec = json!({ "servers": [
+            { "url": "https://{region}.example.com/{base}", "variables": {
+                "region": { "default": "eu" }, "base": { "default": "v1" }
+            } },
+            { "url": "http://127.0.0.1:3000" }
+        ] });
+
+        assert_eq!(server_urls(&spec), ["https://eu.example.com/v1", "http://127.0.0.1:3000"]);
+    }
+
+    #[test]
+    fn operations_merge_parameters_and_order_responses() {
+        let spec = json!({
+            "security": [{ "bearer": [] }],
+            "paths": {
+                "/items/{id}": {
+                    "parameters": [
+                        { "name": "id", "in": "path", "required": true, "description": "inherited" },
+                        { "name": "trace", "in": "cookie" }
+                    ],
+                    "put": {
+                        "operationId": "update_item",
+                        "parameters": [{ "name": "id", "in": "path", "required": true, "description": "own" }],
+                        "requestBody": { "required": true, "content": {
+                            "application/x-www-form-urlencoded": { "schema": { "type": "object" } },
+                            "application/json": { "schema": { "$ref": "#/components/schemas/Item" } }
+                        } },
+                        "responses": {
+                            "default": { "description": "Unexpected error" },
+                            "404": { "description": "Not found" },
+                            "204": { "description": "Updated" }
+                        }
+                    },
+                    "get": { "security": [], "responses": { "200": { "description": "", "content": {
+                        "text/plain": { "schema": { "type": "string" } }
+                    } } } }
+                }
+            }
+        });
+        let operations = operations(&spec);
+
+        let ids: Vec<&str> = operations.iter().map(|operation| operation.id.as_str()).collect();
+        assert_eq!(ids, ["get_/items/{id}", "update_item"]);
+        let (get, put) = (&operations[0], &operations[1]);
+
+        let parameters = put.parameters();
+        assert_eq!(parameters.len(), 1);
+        assert_eq!(parameters[0]["description"], "own");
+        let body = put.request_body().unwrap();
+        assert_eq!((body.content_type, body.required), ("application/json", true));
+        assert_eq!(body.schema.and_then(ref_name), Some("Item"));
+        assert_eq!(put.success_response().unwrap().status, "204");
+        let errors: Vec<&str> = put.error_responses().iter().map(|response| response.status).collect();
+        assert_eq!(errors, ["404", "default"]);
+        assert!(put.is_secured());
+
+        assert!(!get.is_secured());
+        assert_eq!(get.success_response().unwrap().content_type, Some("text/plain"));
+        assert!(get.request_body().is_none());
+    }
+}
@@ -0,0 +1,1126 @@
+//! OpenAPI 规范 -> 基于 `reqwest` 的 Rust 客户端 crate。
+//!
+//! 生成 `Cargo.toml`、`src/lib.rs`（`Client`、`ClientBuilder`、错误类型与每个操作对应的异步方法）
+//! 和 `src/types.rs`（`components.schemas` 对应的 serde 结构体与枚举）。
+//! path 参数按位置传入，query 参数合并为 `<Operation>Query` 结构体，header 参数按位置传入；
+//! 文档声明的非 2xx 响应生成为 `<Operation>Error` 枚举，通过 `Error::Api` 返回。
+
+use super::{
+    component_for, component_schemas, is_nullable, is_null_schema, operations, pascal_case, primary_type, ref_name,
+    required_properties, server_urls, Operation, Response,
+};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+const HEADER: &str = "// Generated by `cargo forge generate-client --lang rust` from an OpenAPI specification. Do not edit.\n";
+
+/// 生成客户端 crate 的全部文件：(相对路径, 内容)
+pub fn generate_client(spec: &Value, crate_name: &str) -> Vec<(&'static str, String)> {
+    let mut generator = Generator::new(spec);
+    let library = generator.library();
+    let types = generator.types();
+    vec![("Cargo.toml", manifest(spec, crate_name)), ("src/lib.rs", library), ("src/types.rs", types)]
+}
+
+/// `Product Service` / `my_client` -> `product-service` / `my_client`
+pub fn package_name(name: &str) -> String {
+    let name: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '-' { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    let name = name.trim_matches('-').to_string();
+    if name.is_empty() { "api-client".to_string() } else { name }
+}
+
+fn manifest(spec: &Value, crate_name: &str) -> String {
+    let version = spec
+        .pointer("/info/version")
+        .and_then(Value::as_str)
+        .filter(|version| version.split('.').count() == 3 && version.split('.').all(|part| part.parse::<u64>().is_ok()))
+        .unwrap_or("0.1.0");
+    let mut out = String::from("# Generated by `cargo forge generate-client --lang rust` from an OpenAPI specification.\n");
+    out.push_str(&format!("[package]\nname = \"{}\"\nversion = \"{}\"\nedition = \"2021\"\n", package_name(crate_name), version));
+    if let Some(title) = spec.pointer("/info/title").and_then(Value::as_str) {
+        out.push_str(&format!("description = {}\n", Value::from(format!("Client for {}", title))));
+    }
+    out.push_str(
+        "\n[dependencies]\nreqwest = { version = \"0.12\", features = [\"json\", \"multipart\"] }\n\
+         serde = { version = \"1\", features = [\"derive\"] }\nserde_json = \"1\"\n",
+    );
+    out
+}
+
+/// 组件名 -> Rust 类型名（`Page_Product` -> `PageProduct`）
+pub fn type_name(component: &str) -> String {
+    let name = pascal_case(component);
+    if name.is_empty() { "Unnamed".to_string() } else { name }
+}
+
+/// `productId` / `x-tenant-id` / `type` -> `product_id` / `x_tenant_id` / `r#type`
+pub fn field_name(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !out.is_empty() && !out.ends_with('_') {
+                out.push('_');
+            }
+            continue;
+        }
+        if c.is_ascii_uppercase() && i > 0 && !out.is_empty() && !out.ends_with('_') {
+            let previous = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_ascii_lowercase());
+            if previous.is_ascii_lowercase() || previous.is_ascii_digit() || (previous.is_ascii_uppercase() && next_is_lower) {
+                out.push('_');
+            }
+        }
+        out.push(c.to_ascii_lowercase());
+    }
+    let out = out.trim_end_matches('_').to_string();
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        return format!("_{}", out);
+    }
+    match out.as_str() {
+        "self" | "super" | "crate" => format!("{}_", out),
+        keyword if KEYWORDS.contains(&keyword) => format!("r#{}", out),
+        _ => out,
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn", "else", "enum",
+    "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "macro", "match", "mod", "move",
+    "mut", "override", "priv", "pub", "ref", "return", "static", "struct", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+fn doc_lines(schema: &Value, indent: &str) -> String {
+    let description = schema.get("description").and_then(Value::as_str).map(str::trim).unwrap_or_default();
+    description.lines().map(|line| format!("{}///{}{}\n", indent, if line.is_empty() { "" } else { " " }, line)).collect()
+}
+
+fn rust_string(text: &str) -> String {
+    Value::from(text).to_string()
+}
+
+/// 保存生成过程中的状态：已占用的类型名与由内联对象生成的附加类型
+struct Generator<'a> {
+    spec: &'a Value,
+    names: BTreeSet<String>,
+    extra: Vec<String>,
+}
+
+impl<'a> Generator<'a> {
+    fn new(spec: &'a Value) -> Self {
+        let names = component_schemas(spec).into_iter().map(|(name, _)| type_name(name)).collect();
+        Generator { spec, names, extra: Vec::new() }
+    }
+
+    fn types(&mut self) -> String {
+        let mut declarations: Vec<String> = component_schemas(self.spec)
+            .into_iter()
+            .map(|(name, schema)| self.declaration(&type_name(name), schema, Some(name)))
+            .collect();
+        declarations.append(&mut self.extra);
+        let mut out = String::from(HEADER);
+        if !declarations.is_empty() {
+            out.push_str("\nuse serde::{Deserialize, Serialize};\n");
+        }
+        for declaration in declarations {
+            out.push('\n');
+            out.push_str(&declaration);
+        }
+        out
+    }
+
+    /// 为内联对象分配一个未占用的类型名
+    fn fresh_name(&mut self, hint: &str) -> String {
+        let base = type_name(hint);
+        let mut name = base.clone();
+        let mut index = 2;
+        while !self.names.insert(name.clone()) {
+            name = format!("{}{}", base, index);
+            index += 1;
+        }
+        name
+    }
+
+    fn declaration(&mut self, name: &str, schema: &Value, owner: Option<&str>) -> String {
+        let mut out = doc_lines(schema, "");
+        if let Some(values) = string_enum(schema) {
+            out.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]\n");
+            out.push_str(&format!("pub enum {} {{\n", name));
+            let mut used = BTreeSet::new();
+            for (index, value) in values.iter().enumerate() {
+                let mut variant = pascal_case(value);
+                if variant.is_empty() || !used.insert(variant.clone()) {
+                    variant = format!("Value{}", index);
+                    used.insert(variant.clone());
+                }
+                out.push_str(&format!("    #[serde(rename = {})]\n    {},\n", rust_string(value), variant));
+            }
+            out.push_str("}\n");
+        } else if let Some(tag) = schema.pointer("/discriminator/propertyName").and_then(Value::as_str) {
+            out.push_str(&self.tagged_enum(name, schema, tag, owner));
+        } else if schema.get("oneOf").or_else(|| schema.get("anyOf")).is_some() && !is_single_branch(schema) {
+            out.push_str(&self.untagged_enum(name, schema, owner));
+        } else if schema.get("properties").is_some() && !is_nullable(schema) {
+            out.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+            out.push_str(&format!("pub struct {} {{\n", name));
+            out.push_str(&self.fields(name, schema, None, owner, "    ", "pub "));
+            out.push_str("}\n");
+        } else {
+            let mut ty = self.base_type(schema, name, owner, true);
+            if is_nullable(schema) && !ty.starts_with("Option<") {
+                ty = format!("Option<{}>", ty);
+            }
+            out.push_str(&format!("pub type {} = {};\n", name, ty));
+        }
+        out
+    }
+
+    /// `#[serde(tag = "...")]` 枚举；每个分支的标签值取自分支中该属性的 `enum`/`const`
+    fn tagged_enum(&mut self, name: &str, schema: &Value, tag: &str, owner: Option<&str>) -> String {
+        let mut out = String::from("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+        out.push_str(&format!("#[serde(tag = {})]\npub enum {} {{\n", rust_string(tag), name));
+        let branches = schema.get("oneOf").or_else(|| schema.get("anyOf")).and_then(Value::as_array).cloned().unwrap_or_default();
+        for (index, branch) in branches.iter().enumerate() {
+            let resolved = self.resolve(branch);
+            let value = resolved
+                .pointer(&format!("/properties/{}", tag.replace('~', "~0").replace('/', "~1")))
+                .and_then(|property| property.get("const").or_else(|| property.pointer("/enum/0")))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("variant{}", index));
+            let variant = match pascal_case(&value) {
+                variant if variant.is_empty() => format!("Variant{}", index),
+                variant => variant,
+            };
+            out.push_str(&doc_lines(&resolved, "    "));
+            out.push_str(&format!("    #[serde(rename = {})]\n", rust_string(&value)));
+            match ref_name(branch).or_else(|| component_for(self.spec, branch)) {
+                Some(component) => out.push_str(&format!("    {}({}),\n", variant, type_name(component))),
+                None => {
+                    out.push_str(&format!("    {} {{\n", variant));
+                    out.push_str(&self.fields(&format!("{}{}", name, variant), &resolved, Some(tag), owner, "        ", ""));
+                    out.push_str("    },\n");
+                }
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// 无标签的 `oneOf`/`anyOf`：`#[serde(untagged)]`，按顺序尝试各分支
+    fn untagged_enum(&mut self, name: &str, schema: &Value, owner: Option<&str>) -> String {
+        let mut out = String::from("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+        out.push_str(&format!("#[serde(untagged)]\npub enum {} {{\n", name));
+        let branches = schema.get("oneOf").or_else(|| schema.get("anyOf")).and_then(Value::as_array).cloned().unwrap_or_default();
+        let mut used = BTreeSet::new();
+        for (index, branch) in branches.iter().filter(|branch| !is_null_schema(branch)).enumerate() {
+            let ty = self.rust_type(branch, &format!("{}Variant{}", name, index), owner);
+            let mut variant = match ref_name(branch).or_else(|| component_for(self.spec, branch)) {
+                Some(component) => type_name(component),
+                None => primary_type(branch).map(pascal_case).unwrap_or_default(),
+            };
+            if variant.is_empty() || !used.insert(variant.clone()) {
+                variant = format!("Variant{}", index);
+                used.insert(variant.clone());
+            }
+            out.push_str(&doc_lines(branch, "    "));
+            out.push_str(&format!("    {}({}),\n", variant, ty));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// 结构体（或结构体变体）的字段；`skip` 为判别字段，由 serde 处理
+    fn fields(&mut self, name: &str, schema: &Value, skip: Option<&str>, owner: Option<&str>, indent: &str, visibility: &str) -> String {
+        let required = required_properties(schema);
+        let mut out = String::new();
+        for (property, property_schema) in schema.get("properties").and_then(Value::as_object).into_iter().flatten() {
+            if Some(property.as_str()) == skip {
+                continue;
+            }
+            let field = field_name(property);
+            let mut ty = self.rust_type(property_schema, &format!("{}{}", name, pascal_case(property)), owner);
+            let mut attributes = Vec::new();
+            if field.trim_start_matches("r#") != property {
+                attributes.push(format!("rename = {}", rust_string(property)));
+            }
+            if !required.contains(&property.as_str()) {
+                if !ty.starts_with("Option<") {
+                    ty = format!("Option<{}>", ty);
+                }
+                attributes.push("default, skip_serializing_if = \"Option::is_none\"".to_string());
+            }
+            out.push_str(&doc_lines(property_schema, indent));
+            if !attributes.is_empty() {
+                out.push_str(&format!("{}#[serde({})]\n", indent, attributes.join(", ")));
+            }
+            out.push_str(&format!("{}{}{}: {},\n", indent, visibility, field, ty));
+        }
+        out
+    }
+
+    /// schema 对应的 Rust 类型；`hint` 用于命名内联对象，`owner` 为所在组件（用于打断递归）
+    fn rust_type(&mut self, schema: &Value, hint: &str, owner: Option<&str>) -> String {
+        let ty = self.base_type(schema, hint, owner, false);
+        if is_nullable(schema) && !ty.starts_with("Option<") && ty != "serde_json::Value" {
+            format!("Option<{}>", ty)
+        } else {
+            ty
+        }
+    }
+
+    /// `root` 为组件自身的 schema，此时不与组件比较，避免类型别名指向自己
+    fn base_type(&mut self, schema: &Value, hint: &str, owner: Option<&str>, root: bool) -> String {
+        let component = match root {
+            true => ref_name(schema),
+            false => ref_name(schema).or_else(|| component_for(self.spec, schema)),
+        };
+        if let Some(component) = component {
+            let name = type_name(component);
+            return match owner {
+                Some(owner) if self.reaches(component, owner) => format!("Box<{}>", name),
+                _ => name,
+            };
+        }
+        for key in ["oneOf", "anyOf", "allOf"] {
+            if let Some(branches) = schema.get(key).and_then(Value::as_array) {
+                let branches: Vec<&Value> = branches.iter().filter(|branch| !is_null_schema(branch)).collect();
+                return match branches.as_slice() {
+                    [branch] => self.base_type(branch, hint, owner, false),
+                    _ if key != "allOf" => {
+                        let name = self.fresh_name(hint);
+                        let declaration = self.declaration(&name, schema, owner);
+                        self.extra.push(declaration);
+                        name
+                    }
+                    _ => "serde_json::Value".to_string(),
+                };
+            }
+        }
+        match primary_type(schema) {
+            Some("string") => "String".to_string(),
+            Some("integer") => integer_type(schema).to_string(),
+            Some("number") if schema.get("format").and_then(Value::as_str) == Some("float") => "f32".to_string(),
+            Some("number") => "f64".to_string(),
+            Some("boolean") => "bool".to_string(),
+            Some("array") => {
+                let item = match schema.get("items") {
+                    Some(items) => self.rust_type(items, &format!("{}Item", hint), None),
+                    None => "serde_json::Value".to_string(),
+                };
+                format!("Vec<{}>", item)
+            }
+            _ if schema.get("properties").is_some() => {
+                let name = self.fresh_name(hint);
+                let declaration = self.declaration(&name, &strip_nullable(schema), owner);
+                self.extra.push(declaration);
+                name
+            }
+            Some("object") => match schema.get("additionalProperties") {
+                Some(values @ Value::Object(_)) => {
+                    let value = self.rust_type(values, &format!("{}Value", hint), None);
+                    format!("std::collections::HashMap<String, {}>", value)
+                }
+                _ => "serde_json::Value".to_string(),
+            },
+            _ => "serde_json::Value".to_string(),
+        }
+    }
+
+    /// `$ref` 指向的组件；非引用时原样返回
+    fn resolve(&self, schema: &Value) -> Value {
+        ref_name(schema)
+            .and_then(|name| self.spec.pointer("/components/schemas").and_then(|schemas| schemas.get(name)))
+            .unwrap_or(schema)
+            .clone()
+    }
+
+    /// 组件 `from` 是否（经由引用或内联的组件）包含 `to`；成立时按值持有会形成无限大小的类型
+    fn reaches(&self, from: &str, to: &str) -> bool {
+        let mut pending = vec![from.to_string()];
+        let mut seen = BTreeSet::new();
+        while let Some(name) = pending.pop() {
+            if name == to {
+                return true;
+            }
+            if !seen.insert(name.clone()) {
+                continue;
+            }
+            if let Some(schema) = self.spec.pointer("/components/schemas").and_then(|schemas| schemas.get(&name)) {
+                collect_components(self.spec, schema, true, &mut pending);
+            }
+        }
+        false
+    }
+
+    fn library(&mut self) -> String {
+        let title = self.spec.pointer("/info/title").and_then(Value::as_str).unwrap_or("the service");
+        let operations = operations(self.spec);
+        let secured = operations.iter().any(Operation::is_secured);
+        let streams = operations.iter().any(|operation| {
+            operation.success_response().and_then(|response| response.content_type) == Some("text/event-stream")
+        });
+
+        let mut out = HEADER.replacen("// ", "//! ", 1);
+        out.push_str(&format!("//!\n//! Typed client for {}.\n\nmod types;\n\n", title));
+        out.push_str("pub use reqwest::header;\npub use reqwest::StatusCode;\npub use types::*;\n\n");
+        out.push_str("use std::fmt;\nuse std::time::Duration;\n\n");
+        let servers: Vec<String> = server_urls(self.spec).iter().map(|url| rust_string(url)).collect();
+        out.push_str(&format!("/// Servers declared in the specification.\npub const SERVERS: &[&str] = &[{}];\n", servers.join(", ")));
+        out.push_str(RUNTIME);
+        out.push_str(&builder_runtime(secured));
+        if streams {
+            out.push_str(EVENT_STREAM);
+        }
+
+        let mut declarations = Vec::new();
+        let mut methods = Vec::new();
+        for operation in &operations {
+            let (method, mut operation_declarations) = self.operation(operation);
+            methods.push(method);
+            declarations.append(&mut operation_declarations);
+        }
+
+        out.push_str(&format!("\n/// Typed client for {}.\n", title));
+        out.push_str("#[derive(Debug, Clone)]\npub struct Client {\n    http: reqwest::Client,\n    base_url: reqwest::Url,\n");
+        if secured {
+            out.push_str("    bearer_token: Option<String>,\n");
+        }
+        out.push_str("}\n\nimpl Client {\n");
+        if secured {
+            out.push_str(&CLIENT_RUNTIME.replace("connect_timeout: None,\n", "connect_timeout: None,\n            bearer_token: None,\n"));
+        } else {
+            out.push_str(CLIENT_RUNTIME);
+        }
+        if secured {
+            out.push_str(AUTHORIZE);
+        }
+        for method in methods {
+            out.push('\n');
+            out.push_str(&method);
+        }
+        out.push_str("}\n");
+        if out.contains("decode(") {
+            out.push_str(DECODE);
+        }
+        for declaration in declarations {
+            out.push('\n');
+            out.push_str(&declaration);
+        }
+        out
+    }
+
+    /// 操作对应的方法，以及它用到的 query 结构体与错误枚举
+    fn operation(&mut self, operation: &Operation) -> (String, Vec<String>) {
+        let method_name = field_name(&operation.id);
+        let prefix = type_name(&operation.id);
+        let parameters = operation.parameters();
+        let by_location = |location: &str| -> Vec<&Value> {
+            parameters.iter().copied().filter(|parameter| parameter.get("in").and_then(Value::as_str) == Some(location)).collect()
+        };
+        let mut declarations = Vec::new();
+        let mut arguments = vec!["&self".to_string()];
+        let mut setup = Vec::new();
+
+        // path 参数
+        let mut path_types = Vec::new();
+        for parameter in by_location("path") {
+            let name = parameter_name(parameter);
+            let schema = parameter.get("schema").unwrap_or(&Value::Null);
+            let ty = match self.rust_type(schema, &format!("{}{}", prefix, pascal_case(name)), None) {
+                ty if ty == "String" => "&str".to_string(),
+                ty => ty,
+            };
+            arguments.push(format!("{}: {}", field_name(name), ty));
+            path_types.push((name, ty));
+        }
+        let segments: Vec<String> = operation
+            .path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| path_segment(segment, &path_types))
+            .collect();
+        setup.push(format!("let url = self.url(&[{}]);", segments.join(", ")));
+        setup.push(format!("let request = self.http.request(reqwest::Method::{}, url);", operation.method.to_uppercase()));
+
+        // query 参数
+        let query = by_location("query");
+        if !query.is_empty() {
+            let query_type = self.fresh_name(&format!("{}Query", prefix));
+            let query_schema = parameters_schema(&query);
+            let all_optional = query.iter().all(|parameter| !is_required(parameter));
+            let mut declaration = format!("/// Query parameters of [`Client::{}`].\n", method_name.trim_start_matches("r#"));
+            declaration.push_str(if all_optional {
+                "#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]\n"
+            } else {
+                "#[derive(Debug, Clone, PartialEq, serde::Serialize)]\n"
+            });
+            declaration.push_str(&format!("pub struct {} {{\n", query_type));
+            declaration.push_str(&self.fields(&query_type, &query_schema, None, None, "    ", "pub "));
+            declaration.push_str("}\n");
+            declarations.push(declaration);
+            arguments.push(format!("query: &{}", query_type));
+            setup.push("let request = request.query(query);".to_string());
+        }
+
+        // header 参数
+        for parameter in by_location("header") {
+            let name = parameter_name(parameter);
+            let argument = field_name(name);
+            let schema = parameter.get("schema").unwrap_or(&Value::Null);
+            let ty = self.rust_type(schema, &format!("{}{}", prefix, pascal_case(name)), None);
+            let (ty, value) = if ty == "String" { ("&str".to_string(), "value") } else { (ty, "value.to_string()") };
+            if is_required(parameter) {
+                arguments.push(format!("{}: {}", argument, ty));
+                let value = value.replace("value", &argument);
+                setup.push(format!("let request = request.header({}, {});", rust_string(name), value));
+            } else {
+                let ty = ty.strip_prefix("Option<").and_then(|ty| ty.strip_suffix('>')).unwrap_or(&ty);
+                arguments.push(format!("{}: Option<{}>", argument, ty));
+                setup.push(format!(
+                    "let request = match {} {{\n            Some(value) => request.header({}, {}),\n            None => request,\n        }};",
+                    argument,
+                    rust_string(name),
+                    value
+                ));
+            }
+        }
+
+        // 请求体
+        if let Some(body) = operation.request_body() {
+            let (ty, call) = match body.content_type {
+                "multipart/form-data" => ("reqwest::multipart::Form".to_string(), "request.multipart(body)".to_string()),
+                "application/x-www-form-urlencoded" => {
+                    let ty = self.body_type(body.schema, &prefix);
+                    (format!("&{}", ty), "request.form(body)".to_string())
+                }
+                content_type if is_json(content_type) => {
+                    let ty = self.body_type(body.schema, &prefix);
+                    (format!("&{}", ty), "request.json(body)".to_string())
+                }
+                content_type => {
+                    let ty = if content_type.starts_with("text/") { "String" } else { "Vec<u8>" };
+                    let call = format!("request.header(header::CONTENT_TYPE, {}).body(body)", rust_string(content_type));
+                    (ty.to_string(), call)
+                }
+            };
+            if body.required {
+                arguments.push(format!("body: {}", ty));
+                setup.push(format!("let request = {};", call));
+            } else {
+                arguments.push(format!("body: Option<{}>", ty));
+                setup.push(format!("let request = match body {{\n            Some(body) => {},\n            None => request,\n        }};", call));
+            }
+        }
+        if operation.is_secured() {
+            setup.push("let request = self.authorize(request);".to_string());
+        }
+
+        // 响应
+        let (returns, success) = self.success(operation, &prefix);
+        let error_type = format!("{}Error", prefix);
+        let errors = operation.error_responses();
+        let (declaration, arms, uses_body) = self.errors(&error_type, &method_name, &errors, &prefix);
+        declarations.push(declaration);
+
+        let mut out = String::from("    /// ");
+        out.push_str(operation.summary().unwrap_or(operation.id.as_str()));
+        out.push_str(&format!("\n    ///\n    /// `{} {}`\n", operation.method.to_uppercase(), operation.path));
+        out.push_str(&format!(
+            "    pub async fn {}({}) -> Result<{}, Error<{}>> {{\n",
+            method_name,
+            arguments.join(", "),
+            returns,
+            error_type
+        ));
+        for line in setup {
+            out.push_str(&format!("        {}\n", line));
+        }
+        out.push_str("        let response = request.send().await?;\n");
+        out.push_str("        let status = response.status();\n");
+        out.push_str(&format!("        if status.is_success() {{\n            {}\n        }}\n", success));
+        if uses_body {
+            out.push_str("        let body = response.text().await?;\n");
+        }
+        if arms.is_empty() {
+            out.push_str("        Err(Error::UnexpectedStatus { status, body })\n");
+        } else if let [arm] = arms.as_slice() {
+            out.push_str(&format!("        Err({})\n", arm.trim_start_matches("_ => ")));
+        } else {
+            out.push_str("        Err(match status.as_u16() {\n");
+            for arm in arms {
+                out.push_str(&format!("            {},\n", arm));
+            }
+            out.push_str("        })\n");
+        }
+        out.push_str("    }\n");
+        (out, declarations)
+    }
+
+    fn body_type(&mut self, schema: Option<&Value>, prefix: &str) -> String {
+        match schema {
+            Some(schema) => self.rust_type(schema, &format!("{}Body", prefix), None),
+            None => "serde_json::Value".to_string(),
+        }
+    }
+
+    /// 成功响应的返回类型与解码语句
+    fn success(&mut self, operation: &Operation, prefix: &str) -> (String, String) {
+        let response = operation.success_response();
+        let content = response.as_ref().and_then(|response| response.content_type.map(|content_type| (content_type, response.schema)));
+        match content {
+            Some(("text/event-stream", schema)) => {
+                let item = match schema {
+                    Some(schema) => self.rust_type(schema, &format!("{}Event", prefix), None),
+                    None => "serde_json::Value".to_string(),
+                };
+                (format!("EventStream<{}>", item), "return Ok(EventStream::new(response));".to_string())
+            }
+            Some((content_type, schema)) if is_json(content_type) => {
+                let ty = match schema {
+                    Some(schema) => self.rust_type(schema, &format!("{}Response", prefix), None),
+                    None => "serde_json::Value".to_string(),
+                };
+                (ty, "return decode(&response.bytes().await?);".to_string())
+            }
+            Some((content_type, _)) if content_type.starts_with("text/") => {
+                ("String".to_string(), "return Ok(response.text().await?);".to_string())
+            }
+            Some(_) => ("Vec<u8>".to_string(), "return Ok(response.bytes().await?.to_vec());".to_string()),
+            None if response.is_some_and(|response| response.status == "204") => ("()".to_string(), "return Ok(());".to_string()),
+            None => ("String".to_string(), "return Ok(response.text().await?);".to_string()),
+        }
+    }
+
+    /// 错误枚举的声明、`match` 分支，以及分支是否用到响应体文本
+    fn errors(&mut self, error_type: &str, method_name: &str, errors: &[Response], prefix: &str) -> (String, Vec<String>, bool) {
+        let mut declaration = format!("/// Error responses documented for [`Client::{}`].\n", method_name.trim_start_matches("r#"));
+        declaration.push_str(&format!("#[derive(Debug, Clone, PartialEq)]\npub enum {} {{", error_type));
+        if !errors.is_empty() {
+            declaration.push('\n');
+        }
+        let mut arms = Vec::new();
+        let mut uses_body = false;
+        let mut has_fallback = false;
+        for response in errors {
+            let (variant, pattern) = match response.status {
+                "default" => ("Other".to_string(), "_".to_string()),
+                status => match status.parse::<u16>() {
+                    Ok(code) => (status_variant(code), code.to_string()),
+                    Err(_) => {
+                        let class = status.chars().next().and_then(|c| c.to_digit(10)).unwrap_or(0);
+                        (format!("Status{}xx", class), format!("code if code / 100 == {}", class))
+                    }
+                },
+            };
+            let with_status = pattern.parse::<u16>().is_err();
+            has_fallback |= pattern == "_";
+            let body_type = match (response.content_type, response.schema) {
+                (Some(content_type), Some(schema)) if is_json(content_type) => {
+                    Some(self.rust_type(schema, &format!("{}{}", prefix, variant), None))
+                }
+                (Some(_), _) => Some("String".to_string()),
+                (None, _) => None,
+            };
+            let value = match &body_type {
+                Some(ty) if ty == "String" => "body",
+                Some(_) => "decode(body.as_bytes())?",
+                None => "",
+            };
+            uses_body |= body_type.is_some();
+            if let Some(description) = response.description.filter(|description| !description.is_empty()) {
+                declaration.push_str(&format!("    /// {}\n", description));
+            }
+            match (with_status, &body_type) {
+                (false, Some(ty)) => {
+                    declaration.push_str(&format!("    {}({}),\n", variant, ty));
+                    arms.push(format!("{} => Error::Api({}::{}({}))", pattern, error_type, variant, value));
+                }
+                (false, None) => {
+                    declaration.push_str(&format!("    {},\n", variant));
+                    arms.push(format!("{} => Error::Api({}::{})", pattern, error_type, variant));
+                }
+                (true, Some(ty)) => {
+                    declaration.push_str(&format!("    {} {{ status: StatusCode, body: {} }},\n", variant, ty));
+                    let value = if value == "body" { "body".to_string() } else { format!("body: {}", value) };
+                    arms.push(format!("{} => Error::Api({}::{} {{ status, {} }})", pattern, error_type, variant, value));
+                }
+                (true, None) => {
+                    declaration.push_str(&format!("    {} {{ status: StatusCode }},\n", variant));
+                    arms.push(format!("{} => Error::Api({}::{} {{ status }})", pattern, error_type, variant));
+                }
+            }
+        }
+        declaration.push_str("}\n");
+        if !arms.is_empty() && !has_fallback {
+            arms.push("_ => Error::UnexpectedStatus { status, body }".to_string());
+            uses_body = true;
+        }
+        if arms.is_empty() {
+            uses_body = true;
+        }
+        // `default` 分支放在最后
+        arms.sort_by_key(|arm| arm.starts_with("_ "));
+        (declaration, arms, uses_body)
+    }
+}
+
+fn status_variant(code: u16) -> String {
+    let name = match code {
+        400 => "BadRequest",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "NotFound",
+        405 => "MethodNotAllowed",
+        409 => "Conflict",
+        410 => "Gone",
+        412 => "PreconditionFailed",
+        413 => "PayloadTooLarge",
+        415 => "UnsupportedMediaType",
+        422 => "UnprocessableEntity",
+        429 => "TooManyRequests",
+        500 => "InternalServerError",
+        501 => "NotImplemented",
+        502 => "BadGateway",
+        503 => "ServiceUnavailable",
+        504 => "GatewayTimeout",
+        _ => return format!("Status{}", code),
+    };
+    name.to_string()
+}
+
+/// `{id}` -> `id`；其他类型的参数先转为字符串，`v{version}` 之类的片段用 `format!`
+fn path_segment(segment: &str, parameters: &[(&str, String)]) -> String {
+    if let Some(name) = segment.strip_prefix('{').and_then(|rest| rest.strip_suffix('}')) {
+        let argument = field_name(name);
+        return match parameters.iter().find(|(parameter, _)| *parameter == name) {
+            Some((_, ty)) if ty == "&str" => argument,
+            _ => format!("&{}.to_string()", argument),
+        };
+    }
+    if segment.contains('{') {
+        let mut template = String::new();
+        let mut arguments = Vec::new();
+        let mut rest = segment;
+        while let Some(start) = rest.find('{') {
+            let Some(end) = rest[start..].find('}').map(|end| start + end) else { break };
+            template.push_str(&rest[..start].replace('{', "{{").replace('}', "}}"));
+            template.push_str("{}");
+            arguments.push(field_name(&rest[start + 1..end]));
+            rest = &rest[end + 1..];
+        }
+        template.push_str(&rest.replace('{', "{{").replace('}', "}}"));
+        return format!("&format!({}, {})", rust_string(&template), arguments.join(", "));
+    }
+    rust_string(segment)
+}
+
+/// 把 query 参数拼成一个对象 schema，复用结构体字段的生成逻辑
+fn parameters_schema(parameters: &[&Value]) -> Value {
+    let mut properties = serde_json::Map::new();
+    let mut required = Vec::new();
+    for parameter in parameters {
+        let name = parameter_name(parameter);
+        let mut schema = parameter.get("schema").cloned().unwrap_or(Value::Null);
+        if let (Some(description), Value::Object(object)) = (parameter.get("description"), &mut schema) {
+            object.entry("description").or_insert_with(|| description.clone());
+        }
+        properties.insert(name.to_string(), schema);
+        if is_required(parameter) {
+            required.push(Value::from(name));
+        }
+    }
+    serde_json::json!({ "type": "object", "properties": properties, "required": required })
+}
+
+/// 收集 schema 中引用或内联的组件名；`root` 为组件自身时不计入
+fn collect_components(spec: &Value, schema: &Value, root: bool, found: &mut Vec<String>) {
+    if let Some(name) = ref_name(schema) {
+        found.push(name.to_string());
+        return;
+    }
+    if !root {
+        if let Some(name) = component_for(spec, schema) {
+            found.push(name.to_string());
+            return;
+        }
+    }
+    match schema {
+        Value::Object(object) => object.values().for_each(|value| collect_components(spec, value, false, found)),
+        Value::Array(values) => values.iter().for_each(|value| collect_components(spec, value, false, found)),
+        _ => {}
+    }
+}
+
+/// 只含字符串取值的 `enum`
+fn string_enum(schema: &Value) -> Option<Vec<&str>> {
+    let values = schema.get("enum")?.as_array()?;
+    let strings: Vec<&str> = values.iter().filter_map(Value::as_str).collect();
+    (!strings.is_empty() && strings.len() == values.len() && !is_nullable(schema)).then_some(strings)
+}
+
+/// 去掉 `null` 分支后只剩一个分支的 `oneOf`/`anyOf`
+fn is_single_branch(schema: &Value) -> bool {
+    schema
+        .get("oneOf")
+        .or_else(|| schema.get("anyOf"))
+        .and_then(Value::as_array)
+        .is_some_and(|branches| branches.iter().filter(|branch| !is_null_schema(branch)).count() == 1)
+}
+
+fn strip_nullable(schema: &Value) -> Value {
+    let mut schema = schema.clone();
+    if let Some(object) = schema.as_object_mut() {
+        object.remove("nullable");
+        if let Some(Value::Array(types)) = object.get("type") {
+            let types: Vec<Value> = types.iter().filter(|ty| *ty != "null").cloned().collect();
+            object.insert("type".to_string(), types.into_iter().next().unwrap_or(Value::Null));
+        }
+    }
+    schema
+}
+
+/// 整数：按 `format` 与 `minimum` 选择 `i32`/`u32`/`i64`/`u64`
+fn integer_type(schema: &Value) -> &'static str {
+    let unsigned = schema.get("minimum").and_then(Value::as_f64).is_some_and(|minimum| minimum >= 0.0);
+    match (schema.get("format").and_then(Value::as_str), unsigned) {
+        (Some("int32"), true) => "u32",
+        (Some("int32"), false) => "i32",
+        (_, true) => "u64",
+        (_, false) => "i64",
+    }
+}
+
+fn parameter_name(parameter: &Value) -> &str {
+    parameter.get("name").and_then(Value::as_str).unwrap_or_default()
+}
+
+fn is_required(parameter: &Value) -> bool {
+    parameter.get("required").and_then(Value::as_bool).unwrap_or(false)
+}
+
+fn is_json(content_type: &str) -> bool {
+    content_type == "application/json" || content_type.ends_with("+json")
+}
+
+fn builder_runtime(secured: bool) -> String {
+    let mut out = String::from(
+        r#"
+/// Builder for [`Client`].
+#[derive(Debug)]
+pub struct ClientBuilder {
+    base_url: String,
+    headers: header::HeaderMap,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+"#,
+    );
+    if secured {
+        out.push_str("    bearer_token: Option<String>,\n");
+    }
+    out.push_str(
+        r#"}
+
+impl ClientBuilder {
+    /// Adds a header sent with every request.
+    pub fn header(mut self, name: header::HeaderName, value: header::HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Sets the timeout for each request, from sending it to reading the whole response.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for establishing connections.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+"#,
+    );
+    if secured {
+        out.push_str(
+            r#"
+    /// Sets the bearer token sent to operations that declare a security requirement.
+    pub fn bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+"#,
+        );
+    }
+    out.push_str(
+        r#"
+    /// Builds the client.
+    pub fn build(self) -> Result<Client, BuildError> {
+        let base_url = reqwest::Url::parse(&self.base_url)
+            .map_err(|error| BuildError::InvalidBaseUrl(format!("{}: {}", self.base_url, error)))?;
+        if base_url.cannot_be_a_base() {
+            return Err(BuildError::InvalidBaseUrl(self.base_url));
+        }
+        let mut http = reqwest::Client::builder().default_headers(self.headers);
+        if let Some(timeout) = self.timeout {
+            http = http.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            http = http.connect_timeout(timeout);
+        }
+        Ok(Client {
+            http: http.build().map_err(BuildError::Http)?,
+            base_url,
+"#,
+    );
+    if secured {
+        out.push_str("            bearer_token: self.bearer_token,\n");
+    }
+    out.push_str("        })\n    }\n}\n");
+    out
+}
+
+const RUNTIME: &str = r#"
+/// Error returned by the client; `E` holds the error responses documented for the operation.
+#[derive(Debug)]
+pub enum Error<E = std::convert::Infallible> {
+    /// The request could not be sent or its response could not be read.
+    Request(reqwest::Error),
+    /// A response body did not match the documented schema.
+    Decode(serde_json::Error),
+    /// The service answered with a documented error response.
+    Api(E),
+    /// The service answered with a status the specification does not document.
+    UnexpectedStatus { status: StatusCode, body: String },
+}
+
+impl<E: fmt::Debug> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Request(error) => write!(f, "request failed: {}", error),
+            Error::Decode(error) => write!(f, "unexpected response body: {}", error),
+            Error::Api(error) => write!(f, "error response: {:?}", error),
+            Error::UnexpectedStatus { status, body } => write!(f, "unexpected status {}: {}", status, body),
+        }
+    }
+}
+
+impl<E: fmt::Debug> std::error::Error for Error<E> {}
+
+impl<E> From<reqwest::Error> for Error<E> {
+    fn from(error: reqwest::Error) -> Self {
+        Error::Request(error)
+    }
+}
+
+/// Error returned by [`ClientBuilder::build`].
+#[derive(Debug)]
+pub enum BuildError {
+    /// The base URL is not an absolute http(s) URL.
+    InvalidBaseUrl(String),
+    /// The underlying HTTP client could not be created.
+    Http(reqwest::Error),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::InvalidBaseUrl(url) => write!(f, "invalid base URL {}", url),
+            BuildError::Http(error) => write!(f, "cannot create HTTP client: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+"#;
+
+const EVENT_STREAM: &str = r#"
+/// Server-sent events of a streaming operation, each decoded from its `data` field.
+#[derive(Debug)]
+pub struct EventStream<T> {
+    response: reqwest::Response,
+    buffer: Vec<u8>,
+    _event: std::marker::PhantomData<T>,
+}
+
+impl<T: serde::de::DeserializeOwned> EventStream<T> {
+    fn new(response: reqwest::Response) -> Self {
+        EventStream { response, buffer: Vec::new(), _event: std::marker::PhantomData }
+    }
+
+    /// Waits for the next event; `None` once the service closes the stream.
+    pub async fn next(&mut self) -> Option<Result<T, Error>> {
+        loop {
+            if let Some(end) = self.buffer.windows(2).position(|window| window == b"\n\n") {
+                let event: Vec<u8> = self.buffer.drain(..end + 2).collect();
+                let event = String::from_utf8_lossy(&event);
+                let data: Vec<&str> = event.lines().filter_map(|line| line.strip_prefix("data:")).map(str::trim_start).collect();
+                if !data.is_empty() {
+                    return Some(decode(data.join("\n").as_bytes()));
+                }
+                continue;
+            }
+            match self.response.chunk().await {
+                Ok(Some(chunk)) => self.buffer.extend(chunk.iter().filter(|byte| **byte != b'\r')),
+                Ok(None) => return None,
+                Err(error) => return Some(Err(Error::Request(error))),
+            }
+        }
+    }
+}
+"#;
+
+const CLIENT_RUNTIME: &str = r#"    /// Creates a client for the service at `base_url` with default settings.
+    pub fn new(base_url: impl Into<String>) -> Result<Self, BuildError> {
+        Self::builder(base_url).build()
+    }
+
+    /// Starts building a client for the service at `base_url`, e.g. one of [`SERVERS`].
+    pub fn builder(base_url: impl Into<String>) -> ClientBuilder {
+        ClientBuilder {
+            base_url: base_url.into(),
+            headers: header::HeaderMap::new(),
+            timeout: None,
+            connect_timeout: None,
+        }
+    }
+
+    fn url(&self, segments: &[&str]) -> reqwest::Url {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut().expect("checked by ClientBuilder::build").pop_if_empty().extend(segments);
+        url
+    }
+"#;
+
+const AUTHORIZE: &str = r#"
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.bearer_token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+"#;
+
+const DECODE: &str = r#"
+fn decode<T: serde::de::DeserializeOwned, E>(body: &[u8]) -> Result<T, Error<E>> {
+    serde_json::from_slice(body).map_err(Error::Decode)
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec() -> Value {
+        json!({
+            "info": { "title": "Pricing", "version": "1.2.3" },
+            "paths": {
+                "/v1/products/{id}/price-quote": { "post": {
+                    "operationId": "quote_price",
+                    "parameters": [
+                        { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } },
+                        { "name": "x-tenant-id", "in": "header", "required": false, "schema": { "type": "string" } }
+                    ],
+                    "requestBody": { "required": true, "content": { "application/json": {
+                        "schema": { "$ref": "#/components/schemas/PriceRule" }
+                    } } },
+                    "responses": {
+                        "200": { "description": "", "content": { "application/json": {
+                            "schema": { "$ref": "#/components/schemas/PriceTick" }
+                        } } },
+                        "404": { "description": "Product not found", "content": { "application/json": {
+                            "schema": { "$ref": "#/components/schemas/ErrorBody" }
+                        } } }
+                    }
+                } },
+                "/v1/products": { "get": {
+                    "operationId": "list_products",
+                    "parameters": [{ "name": "page", "in": "query", "required": false, "schema": { "type": "integer", "format": "int32" } }],
+                    "responses": { "200": { "description": "", "content": { "application/json": {
+                        "schema": { "type": "array", "items": { "$ref": "#/components/schemas/PriceTick" } }
+                    } } } }
+                } },
+                "/v1/prices/stream": { "get": {
+                    "operationId": "stream_prices",
+                    "responses": { "200": { "description": "", "content": { "text/event-stream": {
+                        "schema": { "$ref": "#/components/schemas/PriceTick" }
+                    } } } }
+                } }
+            },
+            "components": { "schemas": {
+                "PriceTick": { "type": "object", "required": ["productCode", "price"], "properties": {
+                    "productCode": { "type": "string" },
+                    "price": { "type": "number", "format": "double" },
+                    "parent": { "oneOf": [{ "type": "null" }, { "$ref": "#/components/schemas/PriceTick" }] }
+                } },
+                "ErrorBody": { "type": "object", "required": ["message"], "properties": { "message": { "type": "string" } } },
+                "PriceRule": { "oneOf": [
+                    { "type": "object", "required": ["type", "percent"], "properties": {
+                        "type": { "type": "string", "enum": ["percentOff"] }, "percent": { "type": "number" }
+                    } },
+                    { "type": "object", "required": ["type"], "properties": { "type": { "type": "string", "enum": ["free"] } } }
+                ], "discriminator": { "propertyName": "type" } }
+            } }
+        })
+    }
+
+    fn file<'a>(files: &'a [(&str, String)], path: &str) -> &'a str {
+        files.iter().find(|(name, _)| *name == path).map(|(_, contents)| contents.as_str()).unwrap()
+    }
+
+    #[test]
+    fn names_follow_rust_conventions() {
+        assert_eq!(type_name("Page_Product"), "PageProduct");
+        assert_eq!(field_name("productId"), "product_id");
+        assert_eq!(field_name("x-tenant-id"), "x_tenant_id");
+        assert_eq!(field_name("HTTPStatus"), "http_status");
+        assert_eq!(field_name("type"), "r#type");
+        assert_eq!(field_name("self"), "self_");
+        assert_eq!(package_name(" Product Service "), "product-service");
+    }
+
+    #[test]
+    fn manifest_uses_the_spec_version() {
+        let files = generate_client(&spec(), "Pricing Client");
+        let manifest = file(&files, "Cargo.toml");
+
+        assert!(manifest.contains("name = \"pricing-client\"\nversion = \"1.2.3\"\n"));
+        assert!(manifest.contains("description = \"Client for Pricing\"\n"));
+    }
+
+    #[test]
+    fn schemas_become_serde_types() {
+        let files = generate_client(&spec(), "pricing-client");
+        let types = file(&files, "src/types.rs");
+
+        assert!(types.starts_with(HEADER));
+        assert!(types.contains(
+            "pub struct PriceTick {\n    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n    pub parent: Option<Box<PriceTick>>,\n    pub price: f64,\n    #[serde(rename = \"productCode\")]\n    pub product_code: String,\n}\n"
+        ));
+        assert!(types.contains("#[serde(tag = \"type\")]\npub enum PriceRule {\n    #[serde(rename = \"percentOff\")]\n    PercentOff {\n        percent: f64,\n    },\n"));
+    }
+
+    #[test]
+    fn operations_become_client_methods() {
+        let files = generate_client(&spec(), "pricing-client");
+        let lib = file(&files, "src/lib.rs");
+
+        assert!(lib.contains(
+            "    pub async fn quote_price(&self, id: &str, x_tenant_id: Option<&str>, body: &PriceRule) -> Result<PriceTick, Error<QuotePriceError>> {\n        let url = self.url(&[\"v1\", \"products\", id, \"price-quote\"]);\n"
+        ));
+        assert!(lib.contains("            404 => Error::Api(QuotePriceError::NotFound(decode(body.as_bytes())?)),\n"));
+        assert!(lib.contains("pub enum QuotePriceError {\n    /// Product not found\n    NotFound(ErrorBody),\n}\n"));
+        assert!(lib.contains(
+            "    pub async fn list_products(&self, query: &ListProductsQuery) -> Result<Vec<PriceTick>, Error<ListProductsError>> {\n"
+        ));
+        assert!(lib.contains("pub struct ListProductsQuery {\n    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n    pub page: Option<i32>,\n}\n"));
+        assert!(lib.contains("    pub async fn stream_prices(&self) -> Result<EventStream<PriceTick>, Error<StreamPricesError>> {\n"));
+        assert_eq!(files, generate_client(&spec(), "pricing-client"));
+    }
+}
@@ -0,0 +1,260 @@
+//! OpenAPI 规范 -> TypeScript 类型定义。
+//!
+//! `components.schemas` 中的每个 schema 生成一个导出声明：带属性的对象生成 `interface`，
+//! 字符串枚举生成 `enum`，`oneOf`/`anyOf` 生成联合类型，其余生成类型别名。
+//! 属性名沿用规范中（已按 `rename_all` 重命名）的名称；未列入 `required` 的属性为可选（`?`），
+//! 可为 `null` 的属性追加 `| null`。
+
+use super::{component_schemas, is_nullable, is_null_schema, pascal_case, primary_type, ref_name, required_properties};
+use serde_json::Value;
+
+const HEADER: &str = "// Generated by `cargo forge generate-types` from an OpenAPI specification. Do not edit.\n";
+
+/// 生成 `components.schemas` 对应的 TypeScript 声明
+pub fn generate_types(spec: &Value) -> String {
+    format!("{}{}", HEADER, declarations(spec))
+}
+
+/// 各组件的声明，每个声明前空一行
+pub fn declarations(spec: &Value) -> String {
+    let mut out = String::new();
+    for (name, schema) in component_schemas(spec) {
+        out.push('\n');
+        out.push_str(&declaration(&type_name(name), schema));
+    }
+    out
+}
+
+/// 组件名 -> TypeScript 类型名（`Page_Product` 保持不变，`.`、`-` 替换为 `_`）
+pub fn type_name(component: &str) -> String {
+    let name: String = component
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '$' { c } else { '_' })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+/// schema 对应的 TypeScript 类型表达式；`indent` 为内联对象所在行的缩进
+pub fn ts_type(schema: &Value, indent: &str) -> String {
+    let base = base_type(schema, indent);
+    if is_nullable(schema) && base != "null" && base != "unknown" {
+        format!("{} | null", base)
+    } else {
+        base
+    }
+}
+
+/// 单行或多行的 JSDoc 注释；无描述时为空
+pub fn doc_comment(schema: &Value, indent: &str) -> String {
+    let Some(description) = schema.get("description").and_then(Value::as_str).map(str::trim) else {
+        return String::new();
+    };
+    if description.is_empty() {
+        return String::new();
+    }
+    let description = description.replace("*/", "*\\/");
+    if description.contains('\n') {
+        let lines: String = description.lines().map(|line| format!("{} * {}\n", indent, line).replace(" * \n", " *\n")).collect();
+        format!("{}/**\n{}{} */\n", indent, lines, indent)
+    } else {
+        format!("{}/** {} */\n", indent, description)
+    }
+}
+
+fn declaration(name: &str, schema: &Value) -> String {
+    let mut out = doc_comment(schema, "");
+    match string_enum(schema) {
+        Some(values) => {
+            out.push_str(&format!("export enum {} {{\n", name));
+            for value in values {
+                out.push_str(&format!("  {} = {},\n", pascal_case(value), literal(&Value::from(value))));
+            }
+            out.push_str("}\n");
+        }
+        None if schema.get("properties").is_some() && !is_nullable(schema) => {
+            out.push_str(&format!("export interface {} {}\n", name, object_type(schema, "")));
+        }
+        None => out.push_str(&format!("export type {} = {};\n", name, ts_type(schema, ""))),
+    }
+    out
+}
+
+/// 只含字符串取值的 `enum`
+fn string_enum(schema: &Value) -> Option<Vec<&str>> {
+    let values = schema.get("enum")?.as_array()?;
+    let strings: Vec<&str> = values.iter().filter_map(Value::as_str).collect();
+    (!strings.is_empty() && strings.len() == values.len() && !is_nullable(schema)).then_some(strings)
+}
+
+fn base_type(schema: &Value, indent: &str) -> String {
+    if let Some(name) = ref_name(schema) {
+        return type_name(name);
+    }
+    if let Some(value) = schema.get("const") {
+        return literal(value);
+    }
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return union(values.iter().filter(|v| !v.is_null()).map(literal), " | ");
+    }
+    for (key, separator) in [("oneOf", " | "), ("anyOf", " | "), ("allOf", " & ")] {
+        if let Some(branches) = schema.get(key).and_then(Value::as_array) {
+            let types = branches.iter().filter(|branch| !is_null_schema(branch)).map(|branch| ts_type(branch, indent));
+            return union(types, separator);
+        }
+    }
+    match primary_type(schema) {
+        Some("string") if schema.get("format").and_then(Value::as_str) == Some("binary") => "Blob".to_string(),
+        Some("string") => "string".to_string(),
+        Some("integer") | Some("number") => "number".to_string(),
+        Some("boolean") => "boolean".to_string(),
+        Some("null") => "null".to_string(),
+        Some("array") => {
+            let item = schema.get("items").map(|items| ts_type(items, indent)).unwrap_or_else(|| "unknown".to_string());
+            format!("{}[]", parenthesize(item))
+        }
+        _ if schema.get("properties").is_some() => object_type(schema, indent),
+        Some("object") => match schema.get("additionalProperties") {
+            Some(Value::Object(_)) => {
+                format!("Record<string, {}>", ts_type(&schema["additionalProperties"], indent))
+            }
+            _ => "Record<string, unknown>".to_string(),
+        },
+        _ => "unknown".to_string(),
+    }
+}
+
+/// `{ name: string; price?: number | null }`，每个属性一行
+fn object_type(schema: &Value, indent: &str) -> String {
+    let required = required_properties(schema);
+    let inner = format!("{}  ", indent);
+    let mut out = String::from("{\n");
+    for (name, property) in schema.get("properties").and_then(Value::as_object).into_iter().flatten() {
+        let optional = if required.contains(&name.as_str()) { "" } else { "?" };
+        out.push_str(&doc_comment(property, &inner));
+        out.push_str(&format!("{}{}{}: {};\n", inner, property_name(name), optional, ts_type(property, &inner)));
+    }
+    out.push_str(indent);
+    out.push('}');
+    out
+}
+
+/// 合法标识符原样输出，否则加引号（如 `x-request-id`）
+pub fn property_name(name: &str) -> String {
+    let is_identifier = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        name.to_string()
+    } else {
+        literal(&Value::from(name))
+    }
+}
+
+fn literal(value: &Value) -> String {
+    value.to_string()
+}
+
+/// 去重后以 `separator` 连接；复合成员加括号
+fn union(types: impl Iterator<Item = String>, separator: &str) -> String {
+    let mut members: Vec<String> = Vec::new();
+    for ty in types {
+        if !members.contains(&ty) {
+            members.push(ty);
+        }
+    }
+    match members.len() {
+        0 => "unknown".to_string(),
+        1 => members.remove(0),
+        _ => members.into_iter().map(parenthesize).collect::<Vec<_>>().join(separator),
+    }
+}
+
+/// 顶层含 `|` 或 `&` 的类型在数组、联合中需要加括号
+fn parenthesize(ty: String) -> String {
+    let mut depth = 0i32;
+    let compound = ty.chars().any(|c| {
+        match c {
+            '{' | '(' | '[' | '<' => depth += 1,
+            '}' | ')' | ']' | '>' => depth -= 1,
+            _ => {}
+        }
+        depth == 0 && (c == '|' || c == '&')
+    });
+    if compound {
+        format!("({})", ty)
+    } else {
+        ty
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec() -> Value {
+        json!({ "components": { "schemas": {
+            "ProductStatus": { "type": "string", "enum": ["draft", "in-progress"] },
+            "Page_Product": {
+                "type": "object",
+                "required": ["items", "total"],
+                "properties": {
+                    "items": { "type": "array", "items": { "$ref": "#/components/schemas/Product" } },
+                    "total": { "type": "integer" }
+                }
+            },
+            "Product": {
+                "type": "object",
+                "description": "A catalog entry",
+                "required": ["productCode"],
+                "properties": {
+                    "productCode": { "type": "string", "description": "Stock keeping unit" },
+                    "parent": { "oneOf": [{ "type": "null" }, { "$ref": "#/components/schemas/Product" }] },
+                    "x-tenant-id": { "type": ["string", "null"] },
+                    "labels": { "type": "object", "additionalProperties": { "type": "string" } },
+                    "photo": { "type": "string", "format": "binary" }
+                }
+            },
+            "PriceRule": { "oneOf": [
+                { "type": "object", "required": ["type"], "properties": { "type": { "type": "string", "enum": ["percentOff"] } } },
+                { "type": "object", "required": ["type"], "properties": { "type": { "type": "string", "enum": ["fixed"] } } }
+            ] },
+            "Codes": { "type": "array", "items": { "type": ["string", "integer"] } },
+            "String": { "type": "string" }
+        } } })
+    }
+
+    #[test]
+    fn components_become_declarations() {
+        let types = generate_types(&spec());
+
+        assert!(types.starts_with(HEADER));
+        assert!(types.contains("export enum ProductStatus {\n  Draft = \"draft\",\n  InProgress = \"in-progress\",\n}\n"));
+        assert!(types.contains("export interface Page_Product {\n  items: Product[];\n  total: number;\n}\n"));
+        assert!(types.contains(
+            "/** A catalog entry */\nexport interface Product {\n  labels?: Record<string, string>;\n  parent?: Product | null;\n  photo?: Blob;\n  /** Stock keeping unit */\n  productCode: string;\n  \"x-tenant-id\"?: string | null;\n}\n"
+        ));
+        assert!(types.contains(
+            "export type PriceRule = {\n  type: \"percentOff\";\n} | {\n  type: \"fixed\";\n};\n"
+        ));
+        assert!(!types.contains("export type String"));
+        assert_eq!(types, generate_types(&spec()));
+    }
+
+    #[test]
+    fn compound_types_are_parenthesized() {
+        assert_eq!(ts_type(&json!({ "type": "array", "items": { "type": ["string", "null"] } }), ""), "(string | null)[]");
+        assert_eq!(ts_type(&json!({ "type": ["string", "null"], "enum": ["a", "b", null] }), ""), "\"a\" | \"b\" | null");
+        assert_eq!(ts_type(&json!({}), ""), "unknown");
+    }
+
+    #[test]
+    fn multi_line_descriptions_become_block_comments() {
+        let comment = doc_comment(&json!({ "description": "First line\n\nThird */ line" }), "  ");
+
+        assert_eq!(comment, "  /**\n   * First line\n   *\n   * Third *\\/ line\n   */\n");
+    }
+}
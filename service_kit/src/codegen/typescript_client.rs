@@ -0,0 +1,469 @@
+//! OpenAPI 规范 -> 基于 `fetch` 的 TypeScript 客户端。
+//!
+//! 输出包含 `typescript` 模块生成的类型声明、一小段运行时（`send`、`ApiError`、`configure`），
+//! 以及每个 `operationId` 对应的一个异步函数：path/query/header 参数合并为 `params` 对象，
+//! 请求体为 `body` 参数。请求地址由 `paths` 与 `servers` 拼接而成；非 2xx 响应抛出 `ApiError`，
+//! 每个操作文档中声明的错误响应汇总为 `<Operation>Error` 联合类型。
+
+use super::typescript::{declarations, doc_comment, property_name, ts_type, type_name};
+use super::{camel_case, component_for, component_schemas, operations, pascal_case, primary_type, server_urls, Operation};
+use serde_json::Value;
+use std::collections::HashSet;
+
+const HEADER: &str = "// Generated by `cargo forge generate-client --lang ts` from an OpenAPI specification. Do not edit.\n";
+
+const RUNTIME: &str = r#"
+/** Options accepted by every operation; values passed to a call override those set with `configure`. */
+export interface ClientOptions {
+  /** Base URL of the service; defaults to the first entry of `SERVERS`. */
+  baseUrl?: string;
+  /** Extra headers sent with every request. */
+  headers?: Record<string, string>;
+  /** Bearer token sent to operations that declare a security requirement. */
+  token?: string;
+  /** `fetch` implementation; defaults to the global `fetch`. */
+  fetch?: typeof fetch;
+}
+
+/** Thrown when the service answers with a non-2xx status. */
+export class ApiError<S extends number = number, B = unknown> extends Error {
+  constructor(
+    readonly operationId: string,
+    readonly status: S,
+    readonly body: B,
+  ) {
+    super(`${operationId} failed with HTTP ${status}`);
+    this.name = "ApiError";
+  }
+}
+
+const defaults: ClientOptions = {};
+
+/** Sets options shared by every call, e.g. the base URL or a bearer token. */
+export function configure(options: ClientOptions): void {
+  Object.assign(defaults, options);
+}
+
+interface ApiRequest {
+  operationId: string;
+  method: string;
+  path: string;
+  query?: Record<string, unknown>;
+  headers?: Record<string, unknown>;
+  body?: BodyInit;
+  contentType?: string;
+  secured?: boolean;
+}
+
+async function send(request: ApiRequest, options?: ClientOptions): Promise<Response> {
+  const settings = { ...defaults, ...options };
+  const baseUrl = (settings.baseUrl ?? SERVERS[0] ?? "").replace(/\/+$/, "");
+  const search = new URLSearchParams();
+  for (const [name, value] of Object.entries(request.query ?? {})) {
+    for (const item of Array.isArray(value) ? value : [value]) {
+      if (item !== undefined && item !== null) search.append(name, String(item));
+    }
+  }
+  const query = search.toString();
+  const headers: Record<string, string> = { ...defaults.headers, ...options?.headers };
+  for (const [name, value] of Object.entries(request.headers ?? {})) {
+    if (value !== undefined && value !== null) headers[name] = String(value);
+  }
+  if (request.contentType) headers["content-type"] = request.contentType;
+  if (request.secured && settings.token) headers["authorization"] = `Bearer ${settings.token}`;
+  const url = baseUrl + request.path + (query ? `?${query}` : "");
+  const response = await (settings.fetch ?? fetch)(url, { method: request.method, headers, body: request.body });
+  if (!response.ok) {
+    throw new ApiError(request.operationId, response.status, await readBody(response));
+  }
+  return response;
+}
+
+async function readBody(response: Response): Promise<unknown> {
+  const text = await response.text();
+  if (!text) return null;
+  try {
+    return JSON.parse(text);
+  } catch {
+    return text;
+  }
+}
+
+function formBody(body: object): URLSearchParams {
+  const form = new URLSearchParams();
+  for (const [name, value] of Object.entries(body)) {
+    if (value !== undefined && value !== null) form.append(name, String(value));
+  }
+  return form;
+}
+
+async function* events<T>(response: Response): AsyncGenerator<T> {
+  const reader = response.body!.pipeThrough(new TextDecoderStream()).getReader();
+  let buffer = "";
+  for (;;) {
+    const { done, value } = await reader.read();
+    if (done) return;
+    buffer += value.replace(/\r\n/g, "\n");
+    let end: number;
+    while ((end = buffer.indexOf("\n\n")) >= 0) {
+      const data = buffer
+        .slice(0, end)
+        .split("\n")
+        .filter((line) => line.startsWith("data:"))
+        .map((line) => line.slice(5).trimStart())
+        .join("\n");
+      buffer = buffer.slice(end + 2);
+      if (data) yield JSON.parse(data) as T;
+    }
+  }
+}
+"#;
+
+/// 运行时代码中占用的顶层名称
+const RUNTIME_NAMES: &[&str] = &[
+    "ClientOptions", "ApiError", "defaults", "configure", "ApiRequest", "send", "readBody", "formBody", "events", "SERVERS",
+];
+
+/// 不能用作函数名的 JavaScript / TypeScript 保留字，生成时加上 `_` 后缀
+const RESERVED_WORDS: &[&str] = &[
+    "await", "break", "case", "catch", "class", "const", "continue", "debugger", "default", "delete", "do", "else",
+    "enum", "export", "extends", "false", "finally", "for", "function", "if", "implements", "import", "in",
+    "instanceof", "interface", "let", "new", "null", "package", "private", "protected", "public", "return", "static",
+    "super", "switch", "this", "throw", "true", "try", "typeof", "var", "void", "while", "with", "yield",
+];
+
+/// 模块顶层已占用的名称：组件类型、运行时以及已生成的操作函数与类型
+struct Names(HashSet<String>);
+
+impl Names {
+    fn new(spec: &Value) -> Self {
+        let components = component_schemas(spec).into_iter().map(|(name, _)| type_name(name));
+        Self(RUNTIME_NAMES.iter().map(|name| name.to_string()).chain(components).collect())
+    }
+
+    /// 分配一个未占用的名称：`AddParams` 已是组件名时得到 `AddParams2`
+    fn fresh(&mut self, base: &str) -> String {
+        let mut name = base.to_string();
+        let mut index = 2;
+        while !self.0.insert(name.clone()) {
+            name = format!("{}{}", base, index);
+            index += 1;
+        }
+        name
+    }
+}
+
+/// 生成 TypeScript 客户端模块
+pub fn generate_client(spec: &Value) -> String {
+    let mut out = String::from(HEADER);
+    out.push_str(&declarations(spec));
+    let servers: Vec<Value> = server_urls(spec).into_iter().map(Value::from).collect();
+    out.push_str(&format!(
+        "\n/** Servers declared in the specification. */\nexport const SERVERS: string[] = {};\n",
+        Value::from(servers)
+    ));
+    out.push_str(RUNTIME);
+    let mut names = Names::new(spec);
+    for operation in operations(spec) {
+        out.push('\n');
+        out.push_str(&operation_code(spec, &operation, &mut names));
+    }
+    out
+}
+
+fn operation_code(spec: &Value, operation: &Operation, names: &mut Names) -> String {
+    let function = camel_case(&operation.id);
+    let function = if RESERVED_WORDS.contains(&function.as_str()) { format!("{}_", function) } else { function };
+    let function = names.fresh(&function);
+    let prefix = pascal_case(&operation.id);
+    let parameters = operation.parameters();
+    let body = operation.request_body();
+    let mut out = String::new();
+
+    // 参数类型
+    let params_type = if parameters.is_empty() { String::new() } else { names.fresh(&format!("{}Params", prefix)) };
+    if !parameters.is_empty() {
+        out.push_str(&format!("/** Path, query and header parameters of `{}`. */\n", function));
+        out.push_str(&format!("export interface {} {{\n", params_type));
+        for parameter in &parameters {
+            let name = parameter_name(parameter);
+            let optional = if is_required(parameter) { "" } else { "?" };
+            let schema = parameter.get("schema").unwrap_or(&Value::Null);
+            out.push_str(&doc_comment(parameter, "  "));
+            out.push_str(&format!("  {}{}: {};\n", property_name(name), optional, schema_type(spec, schema, "  ")));
+        }
+        out.push_str("}\n\n");
+    }
+
+    // 错误类型
+    let error_type = names.fresh(&format!("{}Error", prefix));
+    let errors: Vec<String> = operation
+        .error_responses()
+        .iter()
+        .map(|response| {
+            let status = response.status.parse::<u16>().map(|status| status.to_string()).unwrap_or_else(|_| "number".to_string());
+            let body = match (response.content_type, response.schema) {
+                (Some(content_type), Some(schema)) if is_json(content_type) => schema_type(spec, schema, ""),
+                _ => "unknown".to_string(),
+            };
+            format!("ApiError<{}, {}>", status, body)
+        })
+        .collect();
+    out.push_str(&format!("/** Errors documented for `{}`. */\n", function));
+    if errors.is_empty() {
+        out.push_str(&format!("export type {} = ApiError;\n\n", error_type));
+    } else {
+        out.push_str(&format!("export type {} = {};\n\n", error_type, errors.join(" | ")));
+    }
+
+    // 函数签名
+    let mut arguments = Vec::new();
+    if !parameters.is_empty() {
+        let all_optional = parameters.iter().all(|parameter| !is_required(parameter));
+        arguments.push(if all_optional {
+            format!("params: {} = {{}}", params_type)
+        } else {
+            format!("params: {}", params_type)
+        });
+    }
+    if let Some(body) = &body {
+        let ty = body_type(spec, body.content_type, body.schema);
+        arguments.push(if body.required { format!("body: {}", ty) } else { format!("body?: {}", ty) });
+    }
+    arguments.push("options?: ClientOptions".to_string());
+
+    let success = operation.success_response();
+    let success_content = success.as_ref().and_then(|response| response.content_type.map(|content_type| (content_type, response.schema)));
+    let (returns, result) = match (success_content, &success) {
+        (Some(("text/event-stream", schema)), _) => {
+            let item = schema.map(|schema| schema_type(spec, schema, "")).unwrap_or_else(|| "unknown".to_string());
+            (format!("AsyncGenerator<{}>", item), format!("  yield* events<{}>(response);\n", item))
+        }
+        (Some((content_type, schema)), _) if is_json(content_type) => {
+            let ty = schema.map(|schema| schema_type(spec, schema, "")).unwrap_or_else(|| "unknown".to_string());
+            let result = format!("  return (await response.json()) as {};\n", ty);
+            (format!("Promise<{}>", ty), result)
+        }
+        (Some((content_type, _)), _) if content_type.starts_with("text/") => {
+            ("Promise<string>".to_string(), "  return response.text();\n".to_string())
+        }
+        (Some(_), _) => ("Promise<Blob>".to_string(), "  return response.blob();\n".to_string()),
+        (None, Some(response)) if response.status == "204" => ("Promise<void>".to_string(), String::new()),
+        (None, _) => ("Promise<unknown>".to_string(), "  return readBody(response);\n".to_string()),
+    };
+    let keyword = if returns.starts_with("AsyncGenerator") { "async function*" } else { "async function" };
+
+    // 文档
+    out.push_str("/**\n");
+    if let Some(summary) = operation.summary() {
+        out.push_str(&format!(" * {}\n *\n", summary.replace("*/", "*\\/")));
+    }
+    out.push_str(&format!(" * `{} {}`\n *\n", operation.method.to_uppercase(), operation.path));
+    out.push_str(&format!(" * @throws {{{}}} when the service answers with a non-2xx status\n", error_type));
+    out.push_str(" */\n");
+
+    // 函数体
+    out.push_str(&format!("export {} {}({}): {} {{\n", keyword, function, arguments.join(", "), returns));
+    out.push_str(if result.is_empty() { "  await send(\n    {\n" } else { "  const response = await send(\n    {\n" });
+    out.push_str(&format!("      operationId: {},\n", Value::from(operation.id.as_str())));
+    out.push_str(&format!("      method: \"{}\",\n", operation.method.to_uppercase()));
+    out.push_str(&format!("      path: {},\n", path_expression(operation.path)));
+    for (field, location) in [("query", "query"), ("headers", "header")] {
+        let entries: Vec<String> = parameters
+            .iter()
+            .filter(|parameter| parameter.get("in").and_then(Value::as_str) == Some(location))
+            .map(|parameter| {
+                let name = parameter_name(parameter);
+                format!("{}: {}", property_name(name), param_access(name))
+            })
+            .collect();
+        if !entries.is_empty() {
+            out.push_str(&format!("      {}: {{ {} }},\n", field, entries.join(", ")));
+        }
+    }
+    if let Some(body) = &body {
+        let encoded = match body.content_type {
+            content_type if is_json(content_type) => "JSON.stringify(body)",
+            "application/x-www-form-urlencoded" => "formBody(body)",
+            _ => "body",
+        };
+        if encoded == "body" {
+            out.push_str("      body,\n");
+        } else if body.required {
+            out.push_str(&format!("      body: {},\n", encoded));
+        } else {
+            out.push_str(&format!("      body: body === undefined ? undefined : {},\n", encoded));
+        }
+        // multipart 的 boundary 由 fetch 根据 FormData 生成
+        if body.content_type != "multipart/form-data" {
+            out.push_str(&format!("      contentType: {},\n", Value::from(body.content_type)));
+        }
+    }
+    if operation.is_secured() {
+        out.push_str("      secured: true,\n");
+    }
+    out.push_str("    },\n    options,\n  );\n");
+    out.push_str(&result);
+    out.push_str("}\n");
+    out
+}
+
+/// 与组件相同的内联 schema 使用组件名，其余按 schema 展开
+fn schema_type(spec: &Value, schema: &Value, indent: &str) -> String {
+    if let Some(name) = component_for(spec, schema) {
+        return type_name(name);
+    }
+    if primary_type(schema) == Some("array") {
+        if let Some(name) = schema.get("items").and_then(|items| component_for(spec, items)) {
+            return format!("{}[]", type_name(name));
+        }
+    }
+    ts_type(schema, indent)
+}
+
+fn body_type(spec: &Value, content_type: &str, schema: Option<&Value>) -> String {
+    match content_type {
+        "multipart/form-data" => "FormData".to_string(),
+        "application/octet-stream" => "Blob".to_string(),
+        content_type if content_type.starts_with("text/") => "string".to_string(),
+        _ => schema.map(|schema| schema_type(spec, schema, "")).unwrap_or_else(|| "unknown".to_string()),
+    }
+}
+
+/// `/v1/products/{id}` -> `` `/v1/products/${encodeURIComponent(String(params.id))}` ``
+fn path_expression(path: &str) -> String {
+    if !path.contains('{') {
+        return Value::from(path).to_string();
+    }
+    let mut out = String::from("`");
+    let mut rest = path;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else { break };
+        out.push_str(&rest[..start]);
+        out.push_str(&format!("${{encodeURIComponent(String({}))}}", param_access(&rest[start + 1..end])));
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    out.push('`');
+    out
+}
+
+fn param_access(name: &str) -> String {
+    let property = property_name(name);
+    if property == name {
+        format!("params.{}", name)
+    } else {
+        format!("params[{}]", property)
+    }
+}
+
+fn parameter_name(parameter: &Value) -> &str {
+    parameter.get("name").and_then(Value::as_str).unwrap_or_default()
+}
+
+fn is_required(parameter: &Value) -> bool {
+    parameter.get("required").and_then(Value::as_bool).unwrap_or(false)
+}
+
+fn is_json(content_type: &str) -> bool {
+    content_type == "application/json" || content_type.ends_with("+json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec() -> Value {
+        json!({
+            "openapi": "3.1.0",
+            "paths": {
+                "/add": { "get": {
+                    "operationId": "add",
+                    "parameters": [{ "name": "a", "in": "query", "required": true, "schema": { "type": "number" } }],
+                    "responses": { "200": { "description": "" } }
+                } },
+                "/items/{id}": { "delete": {
+                    "operationId": "delete",
+                    "parameters": [{ "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }],
+                    "responses": { "204": { "description": "" } }
+                } },
+                "/send": { "post": { "operationId": "send", "responses": { "204": { "description": "" } } } }
+            },
+            "components": { "schemas": {
+                "AddParams": { "type": "object", "properties": { "a": { "type": "number" } } }
+            } }
+        })
+    }
+
+    #[test]
+    fn operations_become_functions() {
+        let spec = json!({
+            "paths": {
+                "/v1/products/{id}/price-quote": { "post": {
+                    "operationId": "quote_price",
+                    "parameters": [
+                        { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } },
+                        { "name": "page", "in": "query", "required": false, "schema": { "type": "integer" } },
+                        { "name": "x-tenant-id", "in": "header", "required": false, "schema": { "type": "string" } }
+                    ],
+                    "requestBody": { "required": true, "content": { "application/json": {
+                        "schema": { "$ref": "#/components/schemas/PriceRule" }
+                    } } },
+                    "responses": {
+                        "200": { "description": "", "content": { "application/json": {
+                            "schema": { "$ref": "#/components/schemas/PriceTick" }
+                        } } },
+                        "404": { "description": "", "content": { "application/json": {
+                            "schema": { "$ref": "#/components/schemas/ErrorBody" }
+                        } } },
+                        "default": { "description": "", "content": { "application/json": {
+                            "schema": { "$ref": "#/components/schemas/ErrorBody" }
+                        } } }
+                    }
+                } },
+                "/v1/prices/stream": { "get": {
+                    "operationId": "stream_prices",
+                    "responses": { "200": { "description": "", "content": { "text/event-stream": {
+                        "schema": { "$ref": "#/components/schemas/PriceTick" }
+                    } } } }
+                } }
+            },
+            "components": { "schemas": {
+                "PriceTick": { "type": "object", "properties": { "price": { "type": "number" } } },
+                "PriceRule": { "type": "object", "properties": { "percent": { "type": "number" } } },
+                "ErrorBody": { "type": "object", "properties": { "message": { "type": "string" } } }
+            } }
+        });
+        let client = generate_client(&spec);
+
+        assert!(client.contains("export interface PriceTick {\n"));
+        assert!(client.contains(
+            "export async function quotePrice(params: QuotePriceParams, body: PriceRule, options?: ClientOptions): Promise<PriceTick> {\n"
+        ));
+        assert!(client.contains("      path: `/v1/products/${encodeURIComponent(String(params.id))}/price-quote`,\n"));
+        assert!(client.contains("      query: { page: params.page },\n"));
+        assert!(client.contains("      headers: { \"x-tenant-id\": params[\"x-tenant-id\"] },\n"));
+        assert!(client.contains("export type QuotePriceError = ApiError<404, ErrorBody> | ApiError<number, ErrorBody>;\n"));
+        assert!(client.contains("export async function* streamPrices(options?: ClientOptions): AsyncGenerator<PriceTick> {\n"));
+        assert_eq!(client, generate_client(&spec));
+    }
+
+    #[test]
+    fn generated_names_do_not_collide_with_components_or_runtime() {
+        let client = generate_client(&spec());
+
+        assert_eq!(client.matches("export interface AddParams {").count(), 1);
+        assert!(client.contains("export interface AddParams2 {\n"));
+        assert!(client.contains("export async function add(params: AddParams2, options?: ClientOptions): Promise<unknown> {\n"));
+        assert!(client.contains("export async function send2(options?: ClientOptions): Promise<void> {\n"));
+    }
+
+    #[test]
+    fn reserved_words_are_suffixed() {
+        let client = generate_client(&spec());
+
+        assert!(!client.contains("function delete("));
+        assert!(client.contains("export async function delete_(params: DeleteParams, options?: ClientOptions): Promise<void> {\n"));
+    }
+}
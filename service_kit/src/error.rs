@@ -6,6 +6,16 @@ use thiserror::Error;
 pub enum Error {
     #[error("Spec Error: {0}")]
     SpecError(String),
+    #[error("State Error: {0}")]
+    StateError(String),
+    #[error("Bad Request: {0}")]
+    BadRequest(String),
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    #[error("Payload Too Large: {0}")]
+    PayloadTooLarge(String),
+    #[error("Validation Error: {0}")]
+    Validation(crate::validation::ValidationErrors),
     #[cfg(all(not(target_arch = "wasm32"), feature = "api-cli"))]
     #[error("Reqwest Error: {0}")]
     Reqwest(#[from] reqwest::Error),
@@ -19,7 +29,27 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 impl IntoResponse for Error {
     fn into_response(self) -> Response {
-        (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()).into_response()
+        let status = match &self {
+            Error::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Error::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Error::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            Error::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        match &self {
+            Error::Unauthorized(_) => {
+                (status, [(axum::http::header::WWW_AUTHENTICATE, "Bearer")], self.to_string()).into_response()
+            }
+            Error::Validation(errors) => {
+                let body = serde_json::json!({
+                    "code": "validation_failed",
+                    "message": self.to_string(),
+                    "errors": errors.errors,
+                });
+                (status, axum::Json(body)).into_response()
+            }
+            _ => (status, self.to_string()).into_response(),
+        }
     }
 }
 
@@ -1,9 +1,14 @@
+use std::any::Any;
 use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
+use axum::body::{Body, Bytes};
+use axum::extract::{FromRequest, Multipart};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Request};
 use axum::response::Response;
 use once_cell::sync::Lazy;
+use serde::de::DeserializeOwned;
 use serde_json::Value;
 
 pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
@@ -11,24 +16,38 @@ pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 static API_HANDLERS: Lazy<Arc<Mutex<HashMap<&'static str, ApiMethodHandler>>>> =
     Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
 
+/// 注册动态处理函数；operation_id 已存在时打印警告并保留先注册的处理函数
 pub fn register_handler(handler: ApiMethodHandler) {
+    if let Err(e) = try_register_handler(handler) {
+        eprintln!("[service_kit] {}; keeping the handler registered first", e);
+    }
+}
+
+/// 注册动态处理函数；operation_id 已存在时返回错误并保留先注册的处理函数
+pub fn try_register_handler(handler: ApiMethodHandler) -> crate::error::Result<()> {
     let operation_id = handler.operation_id;
-    API_HANDLERS
-        .lock()
-        .expect("Failed to lock API_HANDLERS mutex")
-        .insert(handler.operation_id, handler);
+    let mut handlers = API_HANDLERS.lock().expect("Failed to lock API_HANDLERS mutex");
+    if handlers.contains_key(operation_id) {
+        return Err(crate::error::Error::SpecError(format!(
+            "Duplicate API handler `{}`",
+            operation_id
+        )));
+    }
+    handlers.insert(operation_id, handler);
     println!("[service_kit] Registered API handler: {}", operation_id);
+    Ok(())
 }
 
 pub fn get_api_handlers() -> Arc<Mutex<HashMap<&'static str, ApiMethodHandler>>> {
     API_HANDLERS.clone()
 }
 
+pub type ApiMethodFn =
+    Arc<dyn for<'a> Fn(&'a Value) -> BoxFuture<'a, crate::error::Result<Response>> + Send + Sync>;
+
 pub struct ApiMethodHandler {
     pub operation_id: &'static str,
-    pub handler: Arc<
-        dyn for<'a> Fn(&'a Value) -> BoxFuture<'a, crate::error::Result<Response>> + Send + Sync,
-    >,
+    pub handler: ApiMethodFn,
 }
 
 impl Clone for ApiMethodHandler {
@@ -40,23 +59,6 @@ impl Clone for ApiMethodHandler {
     }
 }
 
-impl ApiMethodHandler {
-    pub(crate) fn clone_for_mcp(
-        &self,
-    ) -> (
-        String,
-        Arc<
-            dyn for<'a> Fn(
-                    &'a Value,
-                ) -> crate::handler::BoxFuture<'a, crate::error::Result<Response>>
-                + Send
-                + Sync,
-        >,
-    ) {
-        (self.operation_id.to_string(), self.handler.clone())
-    }
-}
-
 impl std::fmt::Debug for ApiMethodHandler {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ApiMethodHandler")
@@ -67,11 +69,467 @@ impl std::fmt::Debug for ApiMethodHandler {
 
 pub type DynHandlerFuture = Pin<Box<dyn Future<Output = crate::error::Result<Response>> + Send + 'static>>;
 
+/// 由 `#[api]` 生成的执行函数签名：接收合并后的参数、应用状态与未解析的原始请求体
+pub type ApiExecFn = fn(&Value, &ApiState, RawBody) -> DynHandlerFuture;
+
 pub struct ApiHandlerInventory {
     pub operation_id: &'static str,
-    pub handler: fn(&Value) -> DynHandlerFuture,
+    pub handler: ApiExecFn,
 }
 
 inventory::collect!(ApiHandlerInventory);
 
+/// 收集 `#[api]` 注册的执行函数；operation_id 重复时返回错误，而不是让后注册的静默覆盖
+pub fn collect_api_handlers() -> crate::error::Result<HashMap<&'static str, ApiExecFn>> {
+    let mut handlers = HashMap::new();
+    for inv in inventory::iter::<ApiHandlerInventory> {
+        if handlers.insert(inv.operation_id, inv.handler).is_some() {
+            let routes: Vec<String> = inventory::iter::<crate::ApiMetadata>
+                .into_iter()
+                .filter(|m| m.operation_id == inv.operation_id)
+                .map(|m| format!("{} {}", m.method, m.path))
+                .collect();
+            return Err(crate::error::Error::SpecError(format!(
+                "Duplicate operation_id `{}` registered by [{}]; use #[api(..., operation_id = \"...\")] to disambiguate",
+                inv.operation_id,
+                routes.join(", ")
+            )));
+        }
+    }
+    Ok(handlers)
+}
+
+/// 类型擦除的应用状态，由 `RestRouterBuilder::with_state` 等注入，
+/// 并在生成的 `__API_EXEC_*` 函数中还原为 `axum::extract::State<T>`。
+#[derive(Clone, Default)]
+pub struct ApiState(Option<Arc<dyn Any + Send + Sync>>);
+
+impl ApiState {
+    pub fn new<S: Clone + Send + Sync + 'static>(state: S) -> Self {
+        Self(Some(Arc::new(state)))
+    }
+
+    /// 取出指定类型的状态副本；未注入或类型不匹配时返回 `None`
+    pub fn get<S: Clone + Send + Sync + 'static>(&self) -> Option<S> {
+        self.0.as_ref().and_then(|s| s.downcast_ref::<S>()).cloned()
+    }
+
+    /// 与 `get` 相同，但在缺失时返回 `Error::StateError`
+    pub fn require<S: Clone + Send + Sync + 'static>(&self) -> crate::error::Result<S> {
+        self.get::<S>().ok_or_else(|| {
+            crate::error::Error::StateError(format!(
+                "State of type `{}` was not provided; call `.with_state(..)` on the router builder",
+                std::any::type_name::<S>()
+            ))
+        })
+    }
+}
+
+impl std::fmt::Debug for ApiState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ApiState").field(&self.0.is_some()).finish()
+    }
+}
+
+/// 将路径参数按 URL 语义（均为字符串）编码，供 `serde_urlencoded` 按目标类型解析
+fn encode_path_params(params: &Value, names: &[&str]) -> crate::error::Result<String> {
+    let mut pairs = Vec::with_capacity(names.len());
+    for name in names {
+        let raw = match params.get(*name) {
+            Some(Value::String(s)) => s.clone(),
+            Some(Value::Null) | None => {
+                return Err(crate::error::Error::BadRequest(format!("Missing path parameter `{}`", name)))
+            }
+            Some(other) => other.to_string(),
+        };
+        pairs.push((*name, raw));
+    }
+    serde_urlencoded::to_string(pairs).map_err(|e| crate::error::Error::BadRequest(e.to_string()))
+}
+
+/// 将单个路径参数反序列化为处理函数声明的类型 `T`（如 `u64`、`Uuid`、`String`）
+pub fn extract_path_param<T: DeserializeOwned>(params: &Value, name: &str) -> crate::error::Result<T> {
+    let encoded = encode_path_params(params, &[name])?;
+    serde_urlencoded::from_str::<Vec<(String, T)>>(&encoded)
+        .map_err(|e| crate::error::Error::BadRequest(format!("Invalid path parameter `{}`: {}", name, e)))?
+        .pop()
+        .map(|(_, v)| v)
+        .ok_or_else(|| crate::error::Error::BadRequest(format!("Missing path parameter `{}`", name)))
+}
+
+/// 将一组路径参数反序列化为结构体 `T`（`Path<MyParams>`）；
+/// 仅有一个占位符时也接受标量类型。
+pub fn extract_path_params<T: DeserializeOwned>(params: &Value, names: &[&str]) -> crate::error::Result<T> {
+    if let [name] = names {
+        if let Ok(v) = extract_path_param::<T>(params, name) {
+            return Ok(v);
+        }
+    }
+    let encoded = encode_path_params(params, names)?;
+    serde_urlencoded::from_str::<T>(&encoded)
+        .map_err(|e| crate::error::Error::BadRequest(format!("Invalid path parameters: {}", e)))
+}
+
+/// `Query<T>`：去掉保留键后按 `type_name` 对应 DTO 的 schema 约束校验，再反序列化为 `T`
+pub fn extract_query<T: DeserializeOwned>(params: &Value, type_name: &str) -> crate::error::Result<T> {
+    let query = strip_request_meta(params);
+    crate::validation::validate(type_name, &query)?;
+    serde_json::from_value(query)
+        .map_err(|e| crate::error::Error::BadRequest(format!("Invalid query parameters: {}", e)))
+}
+
+/// `Option<Query<T>>`：参数中没有 `T` 的任何字段时为 `None`，否则与 `extract_query` 一样校验并反序列化，
+/// 不合法的参数不会被静默当作 `None`。
+pub fn extract_optional_query<T: DeserializeOwned>(params: &Value, type_name: &str) -> crate::error::Result<Option<T>> {
+    let present = match (strip_request_meta(params), crate::validation::property_names(type_name)) {
+        (Value::Object(map), Some(names)) => names.iter().any(|name| map.contains_key(name)),
+        (Value::Object(map), None) => !map.is_empty(),
+        (other, _) => !other.is_null(),
+    };
+    if !present {
+        return Ok(None);
+    }
+    extract_query(params, type_name).map(Some)
+}
+
+/// REST 路由在合并参数时放入原始请求头/Cookie 的保留键
+pub const HEADERS_KEY: &str = "__headers";
+pub const COOKIES_KEY: &str = "__cookies";
+
+/// 去掉保留键，得到可直接反序列化为 `Query<T>` / `Json<T>` 的参数对象
+pub fn strip_request_meta(params: &Value) -> Value {
+    match params {
+        Value::Object(map) if map.contains_key(HEADERS_KEY) || map.contains_key(COOKIES_KEY) => {
+            let mut map = map.clone();
+            map.remove(HEADERS_KEY);
+            map.remove(COOKIES_KEY);
+            Value::Object(map)
+        }
+        other => other.clone(),
+    }
+}
+
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Null | Value::Object(_) => None,
+        Value::Array(items) => items.first().and_then(scalar_to_string),
+        other => Some(other.to_string()),
+    }
+}
+
+/// REST 路由总会写入 `__headers` / `__cookies`（并覆盖客户端在 query、请求体中伪造的同名键），
+/// 此时请求头与 Cookie 只能来自真实请求；没有这两个键时视为 MCP 调用，从平铺参数读取。
+fn is_rest_request(params: &Value) -> bool {
+    params.get(HEADERS_KEY).is_some() || params.get(COOKIES_KEY).is_some()
+}
+
+/// 读取请求头：REST 请求只取原始请求头，MCP 调用取同名的平铺参数
+pub fn header_value(params: &Value, name: &str) -> Option<String> {
+    if is_rest_request(params) {
+        return params
+            .get(HEADERS_KEY)
+            .and_then(|headers| headers.get(name.to_ascii_lowercase()))
+            .and_then(scalar_to_string);
+    }
+    params.get(name).and_then(scalar_to_string)
+}
+
+/// 读取 Cookie：REST 请求只取解析自 `Cookie` 请求头的值，MCP 调用取同名的平铺参数
+pub fn cookie_value(params: &Value, name: &str) -> Option<String> {
+    if is_rest_request(params) {
+        return params
+            .get(COOKIES_KEY)
+            .and_then(|cookies| cookies.get(name))
+            .and_then(scalar_to_string);
+    }
+    params.get(name).and_then(scalar_to_string)
+}
+
+pub fn require_header(params: &Value, name: &str) -> crate::error::Result<String> {
+    header_value(params, name)
+        .ok_or_else(|| crate::error::Error::BadRequest(format!("Missing required header `{}`", name)))
+}
+
+pub fn require_cookie(params: &Value, name: &str) -> crate::error::Result<String> {
+    cookie_value(params, name)
+        .ok_or_else(|| crate::error::Error::BadRequest(format!("Missing required cookie `{}`", name)))
+}
+
+/// 取某个请求头的全部原始值，供 `TypedHeader<T>` 解码
+pub fn extract_header_values(params: &Value, name: &str) -> Vec<HeaderValue> {
+    let raw: Vec<String> = match params.get(HEADERS_KEY).and_then(|h| h.get(name.to_ascii_lowercase())) {
+        Some(Value::Array(items)) => items.iter().filter_map(scalar_to_string).collect(),
+        Some(value) => scalar_to_string(value).into_iter().collect(),
+        None => header_value(params, name).into_iter().collect(),
+    };
+    raw.iter().filter_map(|v| HeaderValue::from_str(v).ok()).collect()
+}
 
+/// 还原 `HeaderMap`：REST 请求的全部请求头；MCP 调用则由以平铺参数传入的已声明请求头/Cookie 构成
+pub fn extract_header_map(params: &Value, header_names: &[&str], cookie_names: &[&str]) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Some(Value::Object(raw)) = params.get(HEADERS_KEY) {
+        for (name, value) in raw {
+            let Ok(header_name) = HeaderName::from_bytes(name.as_bytes()) else { continue };
+            let values = match value {
+                Value::Array(items) => items.iter().filter_map(scalar_to_string).collect(),
+                other => scalar_to_string(other).into_iter().collect::<Vec<_>>(),
+            };
+            for v in values {
+                if let Ok(header_value) = HeaderValue::from_str(&v) {
+                    headers.append(header_name.clone(), header_value);
+                }
+            }
+        }
+    }
+    for name in header_names {
+        let Ok(header_name) = HeaderName::from_bytes(name.as_bytes()) else { continue };
+        if headers.contains_key(&header_name) {
+            continue;
+        }
+        if let Some(header_value) = header_value(params, name).and_then(|v| HeaderValue::from_str(&v).ok()) {
+            headers.insert(header_name, header_value);
+        }
+    }
+    if !headers.contains_key(axum::http::header::COOKIE) {
+        let cookies: Vec<String> = cookie_names
+            .iter()
+            .filter_map(|name| cookie_value(params, name).map(|v| format!("{}={}", name, v)))
+            .collect();
+        if !cookies.is_empty() {
+            if let Ok(cookie_header) = HeaderValue::from_str(&cookies.join("; ")) {
+                headers.insert(axum::http::header::COOKIE, cookie_header);
+            }
+        }
+    }
+    headers
+}
+
+/// 请求体默认的大小上限（2 MiB），与 axum `DefaultBodyLimit` 的默认值一致
+pub const DEFAULT_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+/// 未被解析进参数的原始请求体（multipart、二进制与纯文本请求体）及其大小上限。
+/// 仅 REST 路由会提供；MCP 等没有原始请求体的调用方使用 `RawBody::default()`，
+/// 此时请求体从平铺参数中的 `body`（或 multipart 的各个字段）重建。
+pub struct RawBody {
+    request: Option<Request<Body>>,
+    limit: usize,
+}
+
+impl RawBody {
+    pub fn new(body: Body) -> Self {
+        Self::from_request(Request::new(body), DEFAULT_BODY_LIMIT)
+    }
+
+    /// 保留原始请求（请求头与 `DefaultBodyLimit` 等扩展），请求体最多读取 `limit` 字节
+    pub fn from_request(request: Request<Body>, limit: usize) -> Self {
+        Self { request: Some(request), limit }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.request.is_none()
+    }
+}
+
+impl Default for RawBody {
+    fn default() -> Self {
+        Self { request: None, limit: DEFAULT_BODY_LIMIT }
+    }
+}
+
+impl std::fmt::Debug for RawBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RawBody")
+            .field("present", &self.request.is_some())
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+/// 读取请求体，最多 `limit` 字节；超出时返回 413
+pub async fn read_body(body: Body, limit: usize) -> crate::error::Result<Bytes> {
+    axum::body::to_bytes(body, limit).await.map_err(|e| {
+        let e = e.into_inner();
+        if e.downcast_ref::<http_body_util::LengthLimitError>().is_some() {
+            crate::error::Error::PayloadTooLarge(format!("Request body exceeds {} bytes", limit))
+        } else {
+            crate::error::Error::BadRequest(format!("Failed to read request body: {}", e))
+        }
+    })
+}
+
+/// 没有原始请求体时，`Bytes` / `String` 请求体取自该平铺参数
+pub const BODY_KEY: &str = "body";
+
+/// `Bytes` 请求体：优先读取原始请求体（不超过其大小上限），否则取平铺参数 `body`
+pub async fn extract_bytes(params: &Value, body: RawBody) -> crate::error::Result<Bytes> {
+    match body.request {
+        Some(request) => read_body(request.into_body(), body.limit).await,
+        None => Ok(params
+            .get(BODY_KEY)
+            .and_then(scalar_to_string)
+            .map(Bytes::from)
+            .unwrap_or_default()),
+    }
+}
+
+/// `String` 请求体，要求为 UTF-8
+pub async fn extract_string(params: &Value, body: RawBody) -> crate::error::Result<String> {
+    let bytes = extract_bytes(params, body).await?;
+    String::from_utf8(bytes.to_vec())
+        .map_err(|e| crate::error::Error::BadRequest(format!("Request body is not valid UTF-8: {}", e)))
+}
+
+/// `Form<T>` 请求体：REST 下按 urlencoded 解析原始请求体，字段值保持为字符串，由 `T` 的字段类型决定如何解析
+/// （例如 `zip=01234` 对 `String` 字段仍是 `"01234"`）；没有原始请求体时（MCP）从平铺参数反序列化。
+/// 两种情况都会先按 `type_name` 对应 DTO 的 schema 约束校验。
+pub async fn extract_form<T: DeserializeOwned>(params: &Value, body: RawBody, type_name: &str) -> crate::error::Result<T> {
+    if body.is_empty() {
+        let fields = strip_request_meta(params);
+        crate::validation::validate(type_name, &fields)?;
+        return serde_json::from_value(fields).map_err(crate::error::Error::SerdeJson);
+    }
+    let bytes = extract_bytes(params, body).await?;
+    let pairs: Vec<(String, String)> = serde_urlencoded::from_bytes(&bytes)
+        .map_err(|e| crate::error::Error::BadRequest(format!("Invalid form body: {}", e)))?;
+    let fields: serde_json::Map<String, Value> = pairs.into_iter().map(|(k, v)| (k, Value::String(v))).collect();
+    crate::validation::validate(type_name, &Value::Object(fields))?;
+    serde_urlencoded::from_bytes(&bytes)
+        .map_err(|e| crate::error::Error::BadRequest(format!("Invalid form body: {}", e)))
+}
+
+/// `Multipart` 请求体：REST 下直接流式解析原始请求体；
+/// 没有原始请求体时（MCP），将平铺参数（`exclude` 中的 path/header 等参数除外）
+/// 逐个编码为表单字段后再交给 `Multipart` 解析。
+pub async fn extract_multipart(params: &Value, body: RawBody, exclude: &[&str]) -> crate::error::Result<Multipart> {
+    let request = match body.request {
+        // 保留原始请求的扩展，`Multipart` 据此应用路由配置的 `DefaultBodyLimit`
+        Some(mut request) => {
+            if !request.headers().contains_key(axum::http::header::CONTENT_TYPE) {
+                let content_type = header_value(params, "content-type").unwrap_or_default();
+                let content_type = HeaderValue::from_str(&content_type)
+                    .map_err(|e| crate::error::Error::BadRequest(e.to_string()))?;
+                request.headers_mut().insert(axum::http::header::CONTENT_TYPE, content_type);
+            }
+            request
+        }
+        None => {
+            let (boundary, encoded) = encode_multipart_fields(&strip_request_meta(params), exclude);
+            Request::builder()
+                .header(axum::http::header::CONTENT_TYPE, format!("multipart/form-data; boundary={}", boundary))
+                .body(Body::from(encoded))
+                .map_err(|e| crate::error::Error::BadRequest(e.to_string()))?
+        }
+    };
+    Multipart::from_request(request, &())
+        .await
+        .map_err(|rejection| crate::error::Error::BadRequest(rejection.body_text()))
+}
+
+/// 每次编码随机生成的分隔符；`RandomState` 的密钥按进程随机初始化，无需额外的随机数依赖
+fn random_boundary() -> String {
+    use std::hash::BuildHasher;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let state = std::collections::hash_map::RandomState::new();
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("service-kit-{:016x}{:016x}", state.hash_one(n), state.hash_one(!n))
+}
+
+/// 字段名按 HTML 表单的规则转义 `"`、CR、LF，避免提前结束 `Content-Disposition` 头
+fn escape_multipart_name(name: &str) -> String {
+    name.replace('"', "%22").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// 将平铺参数编码为 multipart 请求体，返回 `(分隔符, 请求体)`；
+/// 分隔符若恰好出现在某个字段中则重新生成，保证不会被字段内容截断。
+fn encode_multipart_fields(fields: &Value, exclude: &[&str]) -> (String, String) {
+    let parts: Vec<(String, String)> = match fields {
+        Value::Object(map) => map
+            .iter()
+            .filter(|(name, _)| !exclude.contains(&name.as_str()))
+            .map(|(name, value)| {
+                let value = match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                (escape_multipart_name(name), value)
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let boundary = loop {
+        let boundary = random_boundary();
+        if !parts.iter().any(|(name, value)| name.contains(&boundary) || value.contains(&boundary)) {
+            break boundary;
+        }
+    };
+    let mut encoded = String::new();
+    for (name, value) in &parts {
+        encoded.push_str(&format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+            boundary, name, value
+        ));
+    }
+    encoded.push_str(&format!("--{}--\r\n", boundary));
+    (boundary, encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handler(operation_id: &'static str) -> ApiMethodHandler {
+        ApiMethodHandler {
+            operation_id,
+            handler: Arc::new(|_| Box::pin(async { Ok(Response::default()) })),
+        }
+    }
+
+    #[test]
+    fn duplicate_handlers_are_rejected() {
+        try_register_handler(handler("register_handler_duplicate")).unwrap();
+        let err = try_register_handler(handler("register_handler_duplicate")).unwrap_err();
+
+        assert!(matches!(err, crate::error::Error::SpecError(msg) if msg.contains("register_handler_duplicate")));
+        assert!(get_api_handlers().lock().unwrap().contains_key("register_handler_duplicate"));
+    }
+
+    #[test]
+    fn register_handler_keeps_the_first_duplicate() {
+        let first = handler("register_handler_first");
+        let first_fn = first.handler.clone();
+        register_handler(first);
+        register_handler(handler("register_handler_first"));
+
+        let handlers = get_api_handlers();
+        let registered = &handlers.lock().unwrap()["register_handler_first"];
+        assert!(Arc::ptr_eq(&registered.handler, &first_fn));
+    }
+
+    #[tokio::test]
+    async fn multipart_fields_cannot_break_out_of_their_part() {
+        let (first, _) = encode_multipart_fields(&serde_json::json!({}), &[]);
+        let (second, _) = encode_multipart_fields(&serde_json::json!({}), &[]);
+        assert_ne!(first, second);
+
+        let params = serde_json::json!({
+            "id": "prod-1",
+            "a\"b\r\nc": "v",
+            "note": "--service-kit-0\r\nContent-Disposition: form-data; name=\"id\"\r\n\r\nforged",
+        });
+        let mut multipart = extract_multipart(&params, RawBody::default(), &["id"]).await.unwrap();
+        let mut fields = Vec::new();
+        while let Some(field) = multipart.next_field().await.unwrap() {
+            let name = field.name().unwrap_or_default().to_string();
+            fields.push((name, field.text().await.unwrap()));
+        }
+
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0], ("a%22b%0D%0Ac".to_string(), "v".to_string()));
+        assert_eq!(fields[1].0, "note");
+        assert!(fields[1].1.ends_with("forged"));
+    }
+}
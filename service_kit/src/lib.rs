@@ -16,20 +16,20 @@
 //!   `axum` route handlers by automatically generating `utoipa` OpenAPI path
 //!   definitions from the function signature.
 //!
-//! - **`ApiDocBuilder`**: (Planned) A builder to automatically discover all `#[api_route]`
-//!   and `#[api_dto]` definitions within your project to generate a complete
-//!   OpenAPI specification with minimal manual effort.
+//! - **`ApiDocBuilder`**: A fluent builder that assembles the OpenAPI specification from
+//!   every `#[api]` and `#[api_dto]` in the inventory, plus servers, contact, license,
+//!   tag descriptions and external docs.
 //!
 
-// Here, we will later add the ApiDocBuilder and other runtime utilities.
-
 // --- Unified facade exports and modules ---
 #[cfg(feature = "macros")]
 pub use service_kit_macros::{api, api_dto};
+pub use api_doc_builder::ApiDocBuilder;
 
 pub use inventory;
 pub use utoipa;
 
+pub mod auth;
 pub mod error;
 pub mod handler;
 
@@ -45,18 +45,50 @@ pub mod rest_router_builder;
 #[cfg(all(not(target_arch = "wasm32"), feature = "api-cli"))]
 pub mod client;
 
+// 由 `#[api]` 元数据构建的类型化客户端（`service_kit::client!`），仅非 wasm
+#[cfg(all(not(target_arch = "wasm32"), feature = "client"))]
+pub mod typed_client;
+
+/// `#[api]` 通过它输出类型化客户端的扩展 trait；未启用 `client` 特性时展开为空，处理函数所在的 crate 无需额外依赖
+#[cfg(all(not(target_arch = "wasm32"), feature = "client"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __api_client {
+    ($($item:tt)*) => { $($item)* };
+}
+
+#[cfg(not(all(not(target_arch = "wasm32"), feature = "client")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __api_client {
+    ($($item:tt)*) => {};
+}
+
 // CLI 构建与补全：在启用 cli-core 特性时提供（兼容 wasm 与 native）
 #[cfg(feature = "cli-core")]
 pub mod cli;
 #[cfg(feature = "cli-core")]
 pub mod wasm_completer;
 pub mod openapi_utils;
+// 带 servers、contact、license、tags 等顶层字段的 OpenAPI 文档构建器
+pub mod api_doc_builder;
+// 以 JSON/YAML、OpenAPI 3.0/3.1 导出排序后的文档（`cargo forge export-spec`）
+pub mod spec_export;
+// 按可配置规则检查 OpenAPI 文档（`cargo forge spec lint`）
+pub mod spec_lint;
+// OpenAPI -> 客户端代码生成（`cargo forge generate-types` 等）
+#[cfg(feature = "codegen")]
+pub mod codegen;
 pub mod bootstrap;
+pub mod sse;
+pub mod validation;
 
 #[derive(Debug, Clone, Copy)]
 pub enum ParamIn {
     Query,
     Path,
+    Header,
+    Cookie,
 }
 
 #[derive(Debug)]
@@ -73,13 +105,32 @@ pub struct ApiRequestBody {
     pub description: &'static str,
     pub required: bool,
     pub type_name: &'static str,
+    /// `application/json`、`application/x-www-form-urlencoded`、`multipart/form-data`、
+    /// `application/octet-stream`（`Bytes`）或 `text/plain`（`String`）
+    pub content_type: &'static str,
+    /// `#[api(..., request_example = json!({...}))]` 声明的请求示例
+    pub example: Option<fn() -> serde_json::Value>,
 }
 
+/// `ApiResponse::status_code` 取该值时表示 OpenAPI 的 `default` 响应
+pub const DEFAULT_RESPONSE_STATUS: u16 = 0;
+
 #[derive(Debug)]
 pub struct ApiResponse {
     pub status_code: u16,
     pub description: &'static str,
     pub type_name: Option<&'static str>,
+    /// `application/json`，或返回 `Sse<...>` 的流式接口的 `text/event-stream`
+    pub content_type: &'static str,
+    /// `#[api(..., response_example = json!({...}))]` 声明的成功响应示例
+    pub example: Option<fn() -> serde_json::Value>,
+}
+
+/// `#[api(..., extensions(x_internal = true))]` 声明的 OpenAPI 扩展字段（`x-internal`）
+#[derive(Debug)]
+pub struct ApiExtension {
+    pub name: &'static str,
+    pub value: fn() -> serde_json::Value,
 }
 
 #[derive(Debug)]
@@ -92,11 +143,20 @@ pub struct ApiMetadata {
     pub parameters: &'static [ApiParameter],
     pub request_body: Option<&'static ApiRequestBody>,
     pub responses: &'static [ApiResponse],
+    /// 为空时使用 `build_openapi_basic` 传入的默认 tag
+    pub tags: &'static [&'static str],
+    pub deprecated: bool,
+    pub extensions: &'static [ApiExtension],
+    /// `#[api(..., security = "bearer")]` 要求的安全方案；为空表示无需认证
+    pub security: &'static [&'static str],
 }
 inventory::collect!(ApiMetadata);
 
 pub struct ApiDtoMetadata {
     pub name: &'static str,
     pub schema_provider: fn() -> (String, utoipa::openapi::RefOr<utoipa::openapi::Schema>),
+    /// 字段引用的其他 `ToSchema` 类型（`ToSchema::schemas`），包括未标注 `#[api_dto]` 的类型，
+    /// 保证 DTO 中的 `$ref` 都能在 `components.schemas` 中找到
+    pub nested_schemas: fn() -> Vec<(String, utoipa::openapi::RefOr<utoipa::openapi::Schema>)>,
 }
 inventory::collect!(ApiDtoMetadata);
@@ -5,7 +5,8 @@
 //! invoked via a local `cargo forge` alias in the generated service project.
 
 use anyhow::{Context, Result};
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::collections::HashSet;
 use std::env;
 use std::fs;
 use std::path::PathBuf;
@@ -94,7 +95,42 @@ Additional usage:
         cargo forge generate-types \
           --input http://127.0.0.1:3000/api-docs/openapi.json \
           --output src/frontend/types/api.ts
-    - Note: requires Node.js with `npx` available; `openapi-typescript` will be run via `npx`.
+    - Note: types are generated natively; Node.js is not required. Reading the spec
+      from a URL requires the `api-cli` feature.
+
+  generate-client (OpenAPI -> typed client)
+    - Usage:
+        cargo forge generate-client --lang <ts|rust> --input <URL_OR_PATH_TO_OPENAPI_JSON> --out <PATH>
+    - Example:
+        cargo forge generate-client --lang ts \
+          --input http://127.0.0.1:3000/api-docs/openapi.json \
+          --out src/frontend/api/client.ts
+        cargo forge generate-client --lang rust \
+          --input http://127.0.0.1:3000/api-docs/openapi.json \
+          --out clients/product-client
+
+  export-spec (service binary -> sorted OpenAPI document)
+    - Usage:
+        cargo forge export-spec --format <json|yaml> --openapi <3.0|3.1> --out <PATH> [--bin <NAME>]
+    - Example:
+        cargo forge export-spec --format yaml --openapi 3.0 --out openapi.yaml
+    - Note: the service's `main` must call
+      `service_kit::spec_export::export_spec_if_requested(&openapi)` after building its spec.
+
+  spec diff (breaking-change detection between two specs)
+    - Usage:
+        cargo forge spec diff <OLD> <NEW> [--format <text|json>]
+    - Example:
+        cargo forge spec diff openapi.json http://127.0.0.1:3000/api-docs/openapi.json
+    - Note: exits with status 1 when any change is breaking.
+
+  spec lint (OpenAPI lint rules)
+    - Usage:
+        cargo forge spec lint [<PATH_OR_URL>] [--bin <NAME>] [--rule <RULE>=<off|info|warning|error>]... [--format <text|json>]
+    - Example:
+        cargo forge spec lint --rule missing-error-response=error --rule missing-description=off
+    - Note: without an input the service is run in spec-dump mode, which also checks that every
+      operation has a handler registered with #[api]. Exits with status 1 on error-level findings.
 "#
 )]
 struct Cli {
@@ -108,6 +144,15 @@ enum Commands {
     /// Generates TypeScript type definitions from an OpenAPI specification.
     GenerateTypes(GenerateTypesArgs),
 
+    /// Generates a typed API client from an OpenAPI specification.
+    GenerateClient(GenerateClientArgs),
+
+    /// Builds and runs the service in spec-dump mode and writes its OpenAPI specification.
+    ExportSpec(ExportSpecArgs),
+
+    /// Checks OpenAPI specifications.
+    Spec(SpecArgs),
+
     /// Lints the codebase using `cargo clippy`.
     Lint,
 
@@ -130,6 +175,125 @@ struct GenerateTypesArgs {
     output: PathBuf,
 }
 
+/// Arguments for the `generate-client` command.
+#[derive(Args, Debug)]
+struct GenerateClientArgs {
+    /// The language of the generated client.
+    #[arg(short, long, value_enum)]
+    lang: ClientLanguage,
+
+    /// The path or URL to the OpenAPI v3 specification file.
+    #[arg(short, long)]
+    input: String,
+
+    /// Where to write the client: a `.ts` file for `--lang ts`, a crate directory for `--lang rust`.
+    #[arg(short, long)]
+    out: PathBuf,
+}
+
+/// Languages supported by `generate-client`.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ClientLanguage {
+    /// A TypeScript module built on `fetch`.
+    Ts,
+    /// A Rust client crate built on `reqwest`.
+    Rust,
+}
+
+/// Arguments for the `export-spec` command.
+#[derive(Args, Debug)]
+struct ExportSpecArgs {
+    /// The output format; inferred from the `--out` extension when omitted.
+    #[arg(short, long, value_enum)]
+    format: Option<SpecFormatArg>,
+
+    /// The OpenAPI version of the written document.
+    #[arg(long, value_enum, default_value = "3.1")]
+    openapi: OpenApiVersionArg,
+
+    /// The path of the written document.
+    #[arg(short, long)]
+    out: PathBuf,
+
+    /// The service binary to run, when the package has several.
+    #[arg(long)]
+    bin: Option<String>,
+}
+
+/// Formats supported by `export-spec`.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum SpecFormatArg {
+    Json,
+    Yaml,
+}
+
+/// OpenAPI versions supported by `export-spec`.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum OpenApiVersionArg {
+    #[value(name = "3.0")]
+    V3_0,
+    #[value(name = "3.1")]
+    V3_1,
+}
+
+/// Arguments for the `spec` command.
+#[derive(Args, Debug)]
+struct SpecArgs {
+    #[command(subcommand)]
+    command: SpecCommands,
+}
+
+/// Subcommands of `spec`.
+#[derive(Subcommand, Debug)]
+enum SpecCommands {
+    /// Classifies the changes between two specifications as breaking or non-breaking.
+    Diff(SpecDiffArgs),
+
+    /// Checks a specification against configurable lint rules.
+    Lint(SpecLintArgs),
+}
+
+/// Arguments for the `spec diff` command.
+#[derive(Args, Debug)]
+struct SpecDiffArgs {
+    /// The path or URL to the previous OpenAPI specification (JSON or YAML).
+    old: String,
+
+    /// The path or URL to the new OpenAPI specification (JSON or YAML).
+    new: String,
+
+    /// The report format.
+    #[arg(short, long, value_enum, default_value = "text")]
+    format: ReportFormat,
+}
+
+/// Arguments for the `spec lint` command.
+#[derive(Args, Debug)]
+struct SpecLintArgs {
+    /// The path or URL to the OpenAPI specification; when omitted, the service is run in spec-dump mode.
+    input: Option<String>,
+
+    /// The service binary to run, when the package has several.
+    #[arg(long, conflicts_with = "input")]
+    bin: Option<String>,
+
+    /// Sets a rule's level, e.g. `missing-error-response=error` or `missing-description=off`.
+    #[arg(long = "rule", value_name = "RULE=LEVEL")]
+    rules: Vec<String>,
+
+    /// The report format.
+    #[arg(short, long, value_enum, default_value = "text")]
+    format: ReportFormat,
+}
+
+/// Report formats supported by `spec` subcommands.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ReportFormat {
+    /// A human-readable report.
+    Text,
+    /// A JSON document for CI pipelines.
+    Json,
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -139,6 +303,7 @@ async fn main() -> Result<()> {
     }
 
     // Manual dispatch for `api-cli` to ensure raw argument forwarding.
+    #[cfg(feature = "api-cli")]
     if args.get(1).map(|s| s.as_str()) == Some("api-cli") {
         return api_cli(args.into_iter().skip(2).collect()).await;
     }
@@ -147,7 +312,13 @@ async fn main() -> Result<()> {
     let cli = Cli::parse_from(args);
 
     match cli.command {
-        Commands::GenerateTypes(args) => generate_types(args)?,
+        Commands::GenerateTypes(args) => generate_types(args).await?,
+        Commands::GenerateClient(args) => generate_client(args).await?,
+        Commands::ExportSpec(args) => export_spec(args)?,
+        Commands::Spec(args) => match args.command {
+            SpecCommands::Diff(args) => spec_diff(args).await?,
+            SpecCommands::Lint(args) => spec_lint(args).await?,
+        },
         Commands::Lint => lint()?,
         Commands::Test => test()?,
     }
@@ -156,31 +327,209 @@ async fn main() -> Result<()> {
 }
 
 /// Handler for the `generate-types` command.
-fn generate_types(args: GenerateTypesArgs) -> Result<()> {
+async fn generate_types(args: GenerateTypesArgs) -> Result<()> {
     println!("▶️  Generating TypeScript types from OpenAPI spec...");
     println!("   Input: {}", args.input);
     println!("   Output: {}", args.output.display());
 
-    let mut command = Command::new("npx");
-    command
-        .arg("openapi-typescript")
-        .arg(&args.input)
-        .arg("--output")
-        .arg(&args.output)
-        .arg("--enum");
+    let spec = load_spec(&args.input).await?;
+    let types = service_kit::codegen::typescript::generate_types(&spec);
 
-    let status = command
-        .status()
-        .context("Failed to execute openapi-typescript. Make sure Node.js, npm, and openapi-typescript are installed and in your PATH.")?;
+    write_file(&args.output, types)?;
+
+    println!("✅ TypeScript types generated successfully.");
+    Ok(())
+}
+
+/// Handler for the `generate-client` command.
+async fn generate_client(args: GenerateClientArgs) -> Result<()> {
+    println!("▶️  Generating {:?} client from OpenAPI spec...", args.lang);
+    println!("   Input: {}", args.input);
+    println!("   Output: {}", args.out.display());
+
+    let spec = load_spec(&args.input).await?;
+    match args.lang {
+        ClientLanguage::Ts => {
+            write_file(&args.out, service_kit::codegen::typescript_client::generate_client(&spec))?;
+        }
+        ClientLanguage::Rust => {
+            // The crate is named after its directory, e.g. `clients/product-client`.
+            let crate_name = args
+                .out
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "api-client".to_string());
+            for (path, contents) in service_kit::codegen::rust_client::generate_client(&spec, &crate_name) {
+                write_file(&args.out.join(path), contents)?;
+            }
+        }
+    }
+
+    println!("✅ Client generated successfully.");
+    Ok(())
+}
 
+/// Handler for the `export-spec` command.
+fn export_spec(args: ExportSpecArgs) -> Result<()> {
+    use service_kit::spec_export::{render_spec, OpenApiVersion, SpecFormat};
+
+    let is_yaml_path = matches!(args.out.extension().and_then(|ext| ext.to_str()), Some("yaml" | "yml"));
+    let format = match args.format {
+        Some(SpecFormatArg::Yaml) => SpecFormat::Yaml,
+        Some(SpecFormatArg::Json) => SpecFormat::Json,
+        None if is_yaml_path => SpecFormat::Yaml,
+        None => SpecFormat::Json,
+    };
+    let version = match args.openapi {
+        OpenApiVersionArg::V3_0 => OpenApiVersion::V3_0,
+        OpenApiVersionArg::V3_1 => OpenApiVersion::V3_1,
+    };
+    println!("▶️  Exporting OpenAPI spec...");
+    println!("   Output: {} ({:?}, OpenAPI {:?})", args.out.display(), format, version);
+
+    let (spec, _) = dump_service_spec(args.bin.as_deref())?;
+    write_file(&args.out, render_spec(&spec, format, version)?)?;
+
+    println!("✅ OpenAPI spec exported successfully.");
+    Ok(())
+}
+
+/// Builds and runs the service in spec-dump mode. Returns its spec and, when the service
+/// reports them, the operation ids of the handlers registered with `#[api]`.
+fn dump_service_spec(bin: Option<&str>) -> Result<(serde_json::Value, Option<HashSet<String>>)> {
+    use service_kit::spec_export::{EXPORT_HANDLERS_ENV, EXPORT_SPEC_ENV};
+
+    // The service writes its spec to this file and exits instead of binding a port.
+    let dump_path = env::temp_dir().join(format!("service-kit-spec-{}.json", std::process::id()));
+    let handlers_path = dump_path.with_extension("handlers.json");
+    let mut cargo_args = vec!["run", "--quiet"];
+    if let Some(bin) = bin {
+        cargo_args.extend(["--bin", bin]);
+    }
+    let status = Command::new("cargo")
+        .current_dir(get_project_root()?)
+        .args(&cargo_args)
+        .env(EXPORT_SPEC_ENV, &dump_path)
+        .env(EXPORT_HANDLERS_ENV, &handlers_path)
+        .status()
+        .context("Failed to run the service in spec-dump mode")?;
     if !status.success() {
-        anyhow::bail!("openapi-typescript command failed.");
+        anyhow::bail!("Failed to run the service in spec-dump mode. Command failed.");
+    }
+    let text = fs::read_to_string(&dump_path).with_context(|| {
+        format!(
+            "The service did not export its spec; make sure its `main` calls \
+             `service_kit::spec_export::export_spec_if_requested` (expected {})",
+            dump_path.display()
+        )
+    })?;
+    let _ = fs::remove_file(&dump_path);
+    let spec = serde_json::from_str(&text).context("The exported spec is not valid JSON")?;
+
+    // Services built against an older service_kit only write the spec.
+    let handlers = match fs::read_to_string(&handlers_path) {
+        Ok(text) => Some(serde_json::from_str(&text).context("The exported handler list is not valid JSON")?),
+        Err(_) => None,
+    };
+    let _ = fs::remove_file(&handlers_path);
+    Ok((spec, handlers))
+}
+
+/// Handler for the `spec lint` command; exits with status 1 on error-level findings.
+async fn spec_lint(args: SpecLintArgs) -> Result<()> {
+    use service_kit::spec_lint::{lint_spec, LintConfig};
+
+    if matches!(args.format, ReportFormat::Text) {
+        println!("▶️  Linting OpenAPI spec...");
+    }
+    let mut config = LintConfig::default();
+    for setting in &args.rules {
+        config = config.apply(setting)?;
+    }
+    let (spec, handlers) = match &args.input {
+        Some(input) => (load_spec(input).await?, None),
+        None => dump_service_spec(args.bin.as_deref())?,
+    };
+    let report = lint_spec(&spec, handlers.as_ref(), &config);
+
+    match args.format {
+        ReportFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        ReportFormat::Text => {
+            print!("{}", report);
+            if report.has_errors() {
+                println!("❌ Lint failed.");
+            } else {
+                println!("✅ Lint passed.");
+            }
+        }
+    }
+    if report.has_errors() {
+        std::process::exit(1);
     }
-    
-    println!("✅ TypeScript types generated successfully.");
     Ok(())
 }
 
+/// Handler for the `spec diff` command; exits with status 1 on breaking changes.
+async fn spec_diff(args: SpecDiffArgs) -> Result<()> {
+    let old = load_spec(&args.old).await?;
+    let new = load_spec(&args.new).await?;
+    let diff = service_kit::openapi_utils::diff_specs(&old, &new);
+
+    match args.format {
+        ReportFormat::Json => {
+            let report = serde_json::json!({
+                "breaking": diff.has_breaking_changes(),
+                "changes": diff.changes,
+            });
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        ReportFormat::Text => {
+            println!("▶️  Comparing {} -> {}", args.old, args.new);
+            print!("{}", diff);
+            if diff.has_breaking_changes() {
+                println!("❌ Breaking changes detected.");
+            } else {
+                println!("✅ No breaking changes.");
+            }
+        }
+    }
+    if diff.has_breaking_changes() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Writes `contents` to `path`, creating parent directories as needed.
+fn write_file(path: &std::path::Path, contents: String) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Loads an OpenAPI document from a local file or an http(s) URL; `.yaml`/`.yml` files are read as YAML.
+async fn load_spec(input: &str) -> Result<serde_json::Value> {
+    let text = if input.starts_with("http://") || input.starts_with("https://") {
+        fetch_spec(input).await?
+    } else {
+        fs::read_to_string(input).with_context(|| format!("Failed to read {}", input))?
+    };
+    if input.ends_with(".yaml") || input.ends_with(".yml") {
+        return serde_norway::from_str(&text).with_context(|| format!("{} is not a valid OpenAPI YAML document", input));
+    }
+    serde_json::from_str(&text).with_context(|| format!("{} is not a valid OpenAPI JSON document", input))
+}
+
+async fn fetch_spec(url: &str) -> Result<String> {
+    let response = reqwest::get(url)
+        .await
+        .with_context(|| format!("Failed to fetch {}", url))?
+        .error_for_status()
+        .with_context(|| format!("Failed to fetch {}", url))?;
+    Ok(response.text().await?)
+}
+
 
 /// Handler for the `lint` command.
 fn lint() -> Result<()> {
@@ -1,8 +1,19 @@
 //! OpenAPI to MCP Router Builder
 
+use crate::auth::{self, Authenticator, SharedAuthenticator};
 use crate::error::{Error, Result};
-use crate::handler::ApiHandlerInventory;
-use axum::response::Response;
+use crate::handler::{collect_api_handlers, ApiExecFn, ApiState, RawBody, BODY_KEY};
+use crate::openapi_utils::resolve_schema;
+use crate::sse::{SseParser, EVENT_STREAM};
+use axum::body::{Body, HttpBody};
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderMap, HeaderValue};
+use axum::response::{IntoResponse, Response};
+use rmcp::model::{Meta, ProgressNotificationParam};
+use rmcp::service::{RequestContext, RoleServer};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Poll;
 use rmcp::handler::server::router::tool::{ToolRoute, ToolRouter};
 use rmcp::model::{CallToolResult, Content, Tool};
 use serde_json::{json, Map, Value};
@@ -10,11 +21,12 @@ use std::borrow::Cow;
 use std::sync::Arc;
 use utoipa::openapi::{OpenApi, PathItem, RefOr};
 use utoipa::openapi::path::Operation;
-use std::collections::HashMap;
 
 #[derive(Default, Clone)]
 pub struct OpenApiMcpRouterBuilder {
     openapi: Option<OpenApi>,
+    state: ApiState,
+    authenticator: Option<SharedAuthenticator>,
 }
 
 impl OpenApiMcpRouterBuilder {
@@ -27,15 +39,24 @@ impl OpenApiMcpRouterBuilder {
         self
     }
 
+    /// 注入应用状态，`#[api]` 处理函数可通过 `State<T>` 取得
+    pub fn with_state<S: Clone + Send + Sync + 'static>(mut self, state: S) -> Self {
+        self.state = ApiState::new(state);
+        self
+    }
+
+    /// 注册认证器，按 MCP 请求的 HTTP 请求头（stdio 下为工具调用的 `_meta.authorization`）
+    /// 校验声明了 `security` 的工具；未通过时返回 401 工具错误
+    pub fn with_authenticator<A: Authenticator>(mut self, authenticator: A) -> Self {
+        self.authenticator = Some(Arc::new(authenticator));
+        self
+    }
+
     pub fn build<S: Send + Sync + 'static>(self) -> Result<ToolRouter<S>> {
         let openapi = self.openapi.ok_or_else(|| {
             Error::SpecError("OpenAPI document not provided".to_string())
         })?;
-        let handlers: HashMap<&'static str, for<'a> fn(&'a Value) -> crate::handler::DynHandlerFuture> =
-            crate::inventory::iter::<ApiHandlerInventory>
-                .into_iter()
-                .map(|inv| (inv.operation_id, inv.handler))
-                .collect();
+        let handlers = collect_api_handlers()?;
 
         let mut router = ToolRouter::new();
 
@@ -43,8 +64,16 @@ impl OpenApiMcpRouterBuilder {
             for operation in operations_from_path_item(path_item) {
                 if let Some(op_id) = operation.operation_id.as_deref() {
                     if let Some(handler_fn) = handlers.get(op_id).cloned() {
-                        let tool_route =
-                            create_tool_route_for_handler((op_id.to_string(), handler_fn), operation)?;
+                        let schemes = auth::required_schemes(&openapi, operation);
+                        auth::ensure_authenticator(self.authenticator.as_ref(), op_id, &schemes)?;
+                        let access = Access { schemes, authenticator: self.authenticator.clone() };
+                        let tool_route = create_tool_route_for_handler(
+                            (op_id.to_string(), handler_fn),
+                            &openapi,
+                            operation,
+                            self.state.clone(),
+                            access,
+                        )?;
                         router.add_route(tool_route);
                     }
                 }
@@ -55,49 +84,31 @@ impl OpenApiMcpRouterBuilder {
     }
 }
 
+/// 工具调用所需的认证信息
+#[derive(Clone)]
+struct Access {
+    schemes: Vec<String>,
+    authenticator: Option<SharedAuthenticator>,
+}
+
 fn create_tool_route_for_handler<S: Send + Sync + 'static>(
-    (operation_id, handler_fn): (
-        String,
-        for<'a> fn(&'a Value) -> crate::handler::DynHandlerFuture,
-    ),
+    (operation_id, handler_fn): (String, ApiExecFn),
+    openapi: &OpenApi,
     operation: &Operation,
+    state: ApiState,
+    access: Access,
 ) -> Result<ToolRoute<S>> {
-    let input_schema = operation
-        .request_body
-        .as_ref()
-        .and_then(|body| body.content.get("application/json"))
-        .and_then(|media_type| media_type.schema.as_ref())
-        .and_then(|schema| match schema {
-            RefOr::T(s) => serde_json::to_value(s).ok(),
-            RefOr::Ref(_) => None,
-        })
-        .or_else(|| {
-            operation
-                .parameters
-                .as_ref()
-                .and_then(|params| {
-                    params.iter().find_map(|p| {
-                        p.schema.as_ref().and_then(|s| {
-                            if let RefOr::T(schema) = s {
-                                Some(serde_json::to_value(schema).unwrap_or(json!({})))
-                            } else {
-                                None
-                            }
-                        })
-                    })
-                })
-        })
-        .unwrap_or(json!({ "type": "object" }));
+    let input_schema_map = Arc::new(build_input_schema(openapi, operation));
 
-    let input_schema_map = if let Value::Object(map) = input_schema {
-        Arc::new(map)
-    } else {
-        Arc::new(Map::new())
+    // 附上示例参数，帮助 LLM 客户端构造合法的调用
+    let description = match (operation.description.clone(), example_arguments(openapi, operation)) {
+        (Some(description), Some(example)) => Some(format!("{}\n\nExample arguments: {}", description, example)),
+        (None, Some(example)) => Some(format!("Example arguments: {}", example)),
+        (description, None) => description,
     };
-
     let tool_def = Tool {
         name: operation_id.clone().into(),
-        description: operation.description.clone().map(Cow::from),
+        description: description.map(Cow::from),
         input_schema: input_schema_map,
         output_schema: None,
         annotations: Default::default(),
@@ -105,6 +116,9 @@ fn create_tool_route_for_handler<S: Send + Sync + 'static>(
 
     let route = ToolRoute::new_dyn(tool_def, move |ctx| {
         let handler_clone = handler_fn;
+        let state = state.clone();
+        let access = access.clone();
+        let operation_id = operation_id.clone();
         Box::pin(async move {
             let params = ctx
                 .arguments
@@ -112,7 +126,21 @@ fn create_tool_route_for_handler<S: Send + Sync + 'static>(
                 .map(|v| Value::Object(v.clone()))
                 .unwrap_or(json!({}));
 
-            match handler_clone(&params).await {
+            let headers = credential_headers(
+                ctx.request_context.extensions.get::<axum::http::request::Parts>(),
+                &ctx.request_context.meta,
+            );
+            let authorized =
+                auth::authorize(access.authenticator.as_deref(), &operation_id, &access.schemes, &headers).await;
+            let result = match authorized {
+                Ok(()) => handler_clone(&params, &state, RawBody::default()).await,
+                Err(e) => Ok(e.into_response()),
+            };
+
+            match result {
+                Ok(response) if is_event_stream(&response) && response.status().is_success() => {
+                    Ok(stream_events_as_progress(response.into_body(), &ctx.request_context).await)
+                }
                 Ok(response) => {
                     let (parts, body) = response.into_parts();
                     let body_bytes =
@@ -138,6 +166,76 @@ fn create_tool_route_for_handler<S: Send + Sync + 'static>(
     Ok(route)
 }
 
+/// 通过 HTTP 传输时，凭证取自 MCP 请求的 HTTP 请求头；stdio 等没有 HTTP 请求头的传输方式，
+/// 取工具调用 `_meta` 中的 `authorization`（格式与 `Authorization` 请求头相同，例如 `Bearer <token>`）
+fn credential_headers(parts: Option<&axum::http::request::Parts>, meta: &Meta) -> HeaderMap {
+    let mut headers = parts.map(|parts| parts.headers.clone()).unwrap_or_default();
+    if !headers.contains_key(AUTHORIZATION) {
+        let meta_value = meta
+            .get(auth::MCP_AUTHORIZATION_META)
+            .and_then(Value::as_str)
+            .and_then(|v| HeaderValue::from_str(v).ok());
+        if let Some(value) = meta_value {
+            headers.insert(AUTHORIZATION, value);
+        }
+    }
+    headers
+}
+
+fn is_event_stream(response: &Response) -> bool {
+    response
+        .headers()
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with(EVENT_STREAM))
+}
+
+/// 逐块读取事件流：每个事件到达时发送一次进度通知（客户端提供了 progressToken 时），
+/// 流结束或调用被取消后，以全部事件的 data 作为工具结果。
+async fn stream_events_as_progress(mut body: Body, request_context: &RequestContext<RoleServer>) -> CallToolResult {
+    let progress_token = request_context.meta.get_progress_token();
+    let mut parser = SseParser::new();
+    let mut contents = Vec::new();
+    let mut cancelled = std::pin::pin!(request_context.ct.cancelled());
+    loop {
+        // 外层 `None` 表示调用已被取消
+        let next = std::future::poll_fn(|cx| {
+            if cancelled.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(None);
+            }
+            Pin::new(&mut body).poll_frame(cx).map(Some)
+        })
+        .await;
+        let (events, done) = match next {
+            None => (Vec::new(), true),
+            Some(Some(Ok(frame))) => (frame.into_data().map(|chunk| parser.feed(&chunk)).unwrap_or_default(), false),
+            Some(Some(Err(e))) => {
+                contents.push(Content::text(format!("Event stream failed: {}", e)));
+                return CallToolResult::error(contents);
+            }
+            Some(None) => (parser.finish().into_iter().collect(), true),
+        };
+        for event in events {
+            if let Some(progress_token) = &progress_token {
+                let _ = request_context
+                    .peer
+                    .notify_progress(ProgressNotificationParam {
+                        progress_token: progress_token.clone(),
+                        progress: (contents.len() + 1) as f64,
+                        total: None,
+                        message: Some(event.data.clone()),
+                    })
+                    .await;
+            }
+            contents.push(Content::text(event.data));
+        }
+        if done {
+            break;
+        }
+    }
+    CallToolResult::success(contents)
+}
+
 fn operations_from_path_item(path_item: &PathItem) -> Vec<&Operation> {
     [
         &path_item.get,
@@ -154,4 +252,279 @@ fn operations_from_path_item(path_item: &PathItem) -> Vec<&Operation> {
     .collect()
 }
 
+/// 由请求体示例（或其 schema 的 `example`）与各参数 schema 的示例拼出一份完整的工具入参示例
+fn example_arguments(openapi: &OpenApi, operation: &Operation) -> Option<Value> {
+    let schema_example = |schema: Option<&RefOr<utoipa::openapi::Schema>>| {
+        schema
+            .and_then(|schema| resolve_schema(openapi, schema))
+            .and_then(|schema| serde_json::to_value(schema).ok())
+            .and_then(|s| s.get("example").cloned())
+    };
+    let mut arguments = operation
+        .request_body
+        .as_ref()
+        .and_then(|body| body.content.values().next())
+        .and_then(|media_type| media_type.example.clone().or_else(|| schema_example(media_type.schema.as_ref())))
+        .and_then(|example| match example {
+            Value::Object(map) => Some(map),
+            _ => None,
+        })
+        .unwrap_or_default();
+    for param in operation.parameters.iter().flatten() {
+        if let Some(example) = schema_example(param.schema.as_ref()) {
+            arguments.insert(param.name.clone(), example);
+        }
+    }
+    (!arguments.is_empty()).then_some(Value::Object(arguments))
+}
+
+/// 工具入参 schema：平铺后再展开其中全部组件引用，客户端无需 OpenAPI 文档即可解析；
+/// 递归类型无法完全展开，放入 `$defs` 并以 `#/$defs/<Name>` 引用
+fn build_input_schema(openapi: &OpenApi, operation: &Operation) -> Map<String, Value> {
+    let components = openapi
+        .components
+        .as_ref()
+        .and_then(|components| serde_json::to_value(&components.schemas).ok())
+        .and_then(|schemas| match schemas {
+            Value::Object(schemas) => Some(schemas),
+            _ => None,
+        })
+        .unwrap_or_default();
+    let mut inliner = RefInliner { components: &components, stack: Vec::new(), defs: Map::new() };
+    let mut schema = Value::Object(flat_input_schema(openapi, operation));
+    inliner.inline(&mut schema);
+    let mut schema = match schema {
+        Value::Object(schema) => schema,
+        _ => Map::new(),
+    };
+    if !inliner.defs.is_empty() {
+        schema.insert("$defs".to_string(), Value::Object(inliner.defs));
+    }
+    schema
+}
+
+const COMPONENT_REF_PREFIX: &str = "#/components/schemas/";
+
+/// 递归展开 `#/components/schemas/<Name>` 引用
+struct RefInliner<'a> {
+    components: &'a Map<String, Value>,
+    /// 正在展开的组件；再次遇到其中之一即为循环引用
+    stack: Vec<String>,
+    defs: Map<String, Value>,
+}
 
+impl RefInliner<'_> {
+    fn inline(&mut self, schema: &mut Value) {
+        match schema {
+            Value::Object(map) => {
+                let target = map
+                    .get("$ref")
+                    .and_then(Value::as_str)
+                    .and_then(|reference| reference.strip_prefix(COMPONENT_REF_PREFIX))
+                    .filter(|name| self.components.contains_key(*name))
+                    .map(str::to_string);
+                let Some(name) = target else {
+                    map.values_mut().for_each(|value| self.inline(value));
+                    return;
+                };
+                if self.stack.contains(&name) {
+                    self.define(&name);
+                    map.insert("$ref".to_string(), Value::String(format!("#/$defs/{}", name)));
+                    return;
+                }
+                self.stack.push(name.clone());
+                let mut expanded = self.components[&name].clone();
+                self.inline(&mut expanded);
+                self.stack.pop();
+                // 与 `$ref` 并列的字段（如 `description`）优先于组件自身的同名字段
+                map.remove("$ref");
+                if let Value::Object(expanded) = expanded {
+                    for (key, value) in expanded {
+                        map.entry(key).or_insert(value);
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.inline(item)),
+            _ => {}
+        }
+    }
+
+    /// 把递归组件写入 `$defs`，其中对自身的引用同样指向 `$defs`
+    fn define(&mut self, name: &str) {
+        if self.defs.contains_key(name) {
+            return;
+        }
+        // 先占位，展开过程中再次遇到时不会重复定义
+        self.defs.insert(name.to_string(), Value::Null);
+        let outer = std::mem::replace(&mut self.stack, vec![name.to_string()]);
+        let mut definition = self.components[name].clone();
+        self.inline(&mut definition);
+        self.stack = outer;
+        self.defs.insert(name.to_string(), definition);
+    }
+}
+
+/// 将请求体的属性与全部参数（path/query/header/cookie）合并为一个扁平的工具入参 schema；
+/// 请求体引用的 DTO 组件在此展开
+fn flat_input_schema(openapi: &OpenApi, operation: &Operation) -> Map<String, Value> {
+    let mut properties = Map::new();
+    let mut required: Vec<Value> = Vec::new();
+
+    // JSON / 表单 / multipart 请求体的字段平铺为工具参数
+    let body_schema = operation
+        .request_body
+        .as_ref()
+        .and_then(|body| body.content.values().next())
+        .and_then(|media_type| media_type.schema.as_ref())
+        .and_then(|schema| resolve_schema(openapi, schema))
+        .and_then(|schema| serde_json::to_value(schema).ok());
+    if let Some(body_schema) = &body_schema {
+        if let Some(body_props) = body_schema.get("properties").and_then(|p| p.as_object()) {
+            properties.extend(body_props.clone());
+        }
+        if let Some(body_required) = body_schema.get("required").and_then(|r| r.as_array()) {
+            required.extend(body_required.iter().cloned());
+        }
+        // 二进制 / 纯文本请求体：以字符串参数 `body` 传入
+        if body_schema.get("type").and_then(|t| t.as_str()) == Some("string") {
+            properties.insert(BODY_KEY.to_string(), body_schema.clone());
+            if operation.request_body.as_ref().is_some_and(|b| matches!(b.required, Some(utoipa::openapi::Required::True))) {
+                required.push(Value::String(BODY_KEY.to_string()));
+            }
+        }
+    }
+
+    for param in operation.parameters.iter().flatten() {
+        let mut param_schema = param
+            .schema
+            .as_ref()
+            .and_then(|schema| resolve_schema(openapi, schema))
+            .and_then(|schema| serde_json::to_value(schema).ok())
+            .unwrap_or(json!({}));
+        if let (Some(obj), Some(description)) = (param_schema.as_object_mut(), param.description.as_ref()) {
+            if !description.is_empty() {
+                obj.insert("description".to_string(), Value::String(description.clone()));
+            }
+        }
+        properties.insert(param.name.clone(), param_schema);
+        if matches!(param.required, utoipa::openapi::Required::True) {
+            required.push(Value::String(param.name.clone()));
+        }
+    }
+
+    // 请求体不是对象（或无请求体且无参数）时保持原样
+    if properties.is_empty() {
+        return match body_schema {
+            Some(Value::Object(map)) => map,
+            _ => json!({ "type": "object" }).as_object().cloned().unwrap_or_default(),
+        };
+    }
+
+    let mut schema = Map::new();
+    schema.insert("type".to_string(), json!("object"));
+    schema.insert("properties".to_string(), Value::Object(properties));
+    if !required.is_empty() {
+        schema.insert("required".to_string(), Value::Array(required));
+    }
+    // 带标签枚举（`oneOf`）的请求体无法平铺，保留其变体约束
+    if let Some(one_of) = body_schema.as_ref().and_then(|s| s.get("oneOf")) {
+        schema.insert("oneOf".to_string(), one_of.clone());
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input_schema(spec: Value, path: &str) -> Value {
+        let openapi: OpenApi = serde_json::from_value(spec).unwrap();
+        let operation = openapi.paths.paths[path].post.clone().unwrap();
+        Value::Object(build_input_schema(&openapi, &operation))
+    }
+
+    #[test]
+    fn nested_references_are_inlined() {
+        let schema = input_schema(
+            json!({
+                "openapi": "3.1.0",
+                "info": { "title": "Test", "version": "0.0.0" },
+                "paths": { "/items": { "post": {
+                    "parameters": [{ "name": "status", "in": "query", "required": false, "schema": {
+                        "oneOf": [{ "type": "null" }, { "$ref": "#/components/schemas/Status" }]
+                    } }],
+                    "requestBody": { "content": { "application/json": {
+                        "schema": { "$ref": "#/components/schemas/Item" }
+                    } } },
+                    "responses": {}
+                } } },
+                "components": { "schemas": {
+                    "Status": { "type": "string", "enum": ["active", "archived"] },
+                    "Item": { "type": "object", "properties": { "dimensions": { "$ref": "#/components/schemas/Dimensions" } } },
+                    "Dimensions": { "type": "object", "properties": { "width": { "type": "integer" } } }
+                } }
+            }),
+            "/items",
+        );
+
+        assert_eq!(schema["properties"]["status"]["oneOf"][1]["enum"], json!(["active", "archived"]));
+        assert_eq!(schema["properties"]["dimensions"]["properties"]["width"]["type"], "integer");
+        assert!(!schema.to_string().contains("$ref"));
+        assert!(schema.get("$defs").is_none());
+    }
+
+    #[test]
+    fn recursive_references_point_into_defs() {
+        let schema = input_schema(
+            json!({
+                "openapi": "3.1.0",
+                "info": { "title": "Test", "version": "0.0.0" },
+                "paths": { "/categories": { "post": {
+                    "requestBody": { "content": { "application/json": {
+                        "schema": { "$ref": "#/components/schemas/Category" }
+                    } } },
+                    "responses": {}
+                } } },
+                "components": { "schemas": {
+                    "Category": { "type": "object", "properties": {
+                        "name": { "type": "string" },
+                        "parent": { "oneOf": [{ "type": "null" }, { "$ref": "#/components/schemas/Category" }] }
+                    } }
+                } }
+            }),
+            "/categories",
+        );
+
+        let parent = &schema["properties"]["parent"]["oneOf"][1];
+        assert_eq!(parent["properties"]["parent"]["oneOf"][1]["$ref"], "#/$defs/Category");
+        assert_eq!(schema["$defs"]["Category"]["properties"]["parent"]["oneOf"][1]["$ref"], "#/$defs/Category");
+        assert!(!schema.to_string().contains("#/components/"));
+    }
+
+    fn meta(authorization: &str) -> Meta {
+        let mut meta = Meta::new();
+        meta.insert(auth::MCP_AUTHORIZATION_META.to_string(), json!(authorization));
+        meta
+    }
+
+    #[test]
+    fn stdio_credentials_are_read_from_meta() {
+        let headers = credential_headers(None, &meta("Bearer secret"));
+        let credentials = auth::credentials_from_headers(&[auth::BEARER.to_string()], &headers).unwrap();
+
+        assert_eq!(credentials.token, "secret");
+        assert!(credential_headers(None, &Meta::new()).is_empty());
+    }
+
+    #[test]
+    fn http_authorization_header_takes_precedence_over_meta() {
+        let (parts, _) = axum::http::Request::get("/mcp")
+            .header(AUTHORIZATION, "Bearer from-header")
+            .body(())
+            .unwrap()
+            .into_parts();
+        let headers = credential_headers(Some(&parts), &meta("Bearer from-meta"));
+
+        assert_eq!(headers[AUTHORIZATION], "Bearer from-header");
+    }
+}
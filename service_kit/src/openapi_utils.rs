@@ -1,5 +1,7 @@
 use crate::{ApiDtoMetadata, ApiMetadata, inventory};
-use std::collections::HashMap;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use utoipa::openapi::{self, ComponentsBuilder, Schema};
 use utoipa::openapi::path::{OperationBuilder, ParameterBuilder, ParameterIn};
 
@@ -16,82 +18,111 @@ pub fn build_openapi_basic(title: &str, version: &str, description: &str, tag: &
         .paths(openapi::Paths::new())
         .build();
 
-    // 1) 汇总 DTO schemas
-    let mut schemas: HashMap<String, openapi::RefOr<Schema>> = inventory::iter::<ApiDtoMetadata>
-        .into_iter()
-        .map(|dto| (dto.schema_provider)())
-        .collect();
+    // 1) 汇总 DTO schemas：以去掉路径前缀的类型名为键，输出到 `components.schemas`，其余位置以 `$ref` 引用
+    let schemas = registered_schemas();
 
-    // 2) 内置常用 primitive schema
-    use utoipa::openapi::schema::{ObjectBuilder, Type};
-    let string_schema = openapi::RefOr::T(Schema::Object(ObjectBuilder::new().schema_type(Type::String).build()));
-    let integer_schema = openapi::RefOr::T(Schema::Object(ObjectBuilder::new().schema_type(Type::Integer).build()));
-    let number_schema = openapi::RefOr::T(Schema::Object(ObjectBuilder::new().schema_type(Type::Number).build()));
-    let boolean_schema = openapi::RefOr::T(Schema::Object(ObjectBuilder::new().schema_type(Type::Boolean).build()));
-    schemas.entry("String".into()).or_insert(string_schema.clone());
-    schemas.entry("&str".into()).or_insert(string_schema.clone());
-    schemas.entry("i32".into()).or_insert(integer_schema.clone());
-    schemas.entry("i64".into()).or_insert(integer_schema.clone());
-    schemas.entry("u32".into()).or_insert(integer_schema.clone());
-    schemas.entry("u64".into()).or_insert(integer_schema.clone());
-    schemas.entry("f32".into()).or_insert(number_schema.clone());
-    schemas.entry("f64".into()).or_insert(number_schema.clone());
-    schemas.entry("bool".into()).or_insert(boolean_schema.clone());
+    // 2) 由类型名解析 schema：DTO 引用组件，容器与常见类型展开；未知类型为 `None`
+    let resolver = SchemaResolver::new(schemas.keys().map(String::as_str));
+    let schema_for = |type_name: &str| resolver.resolve(type_name);
 
     // 3) 根据 ApiMetadata 生成 paths/operations
+    let mut security_schemes = std::collections::BTreeMap::new();
     for metadata in inventory::iter::<ApiMetadata> {
         let mut operation_builder = OperationBuilder::new()
             .operation_id(Some(metadata.operation_id.to_string()))
             .summary(Some(metadata.summary.to_string()))
-            .description(Some(metadata.description.to_string()))
-            .tag(tag);
+            .description(Some(metadata.description.to_string()));
+        operation_builder = if metadata.tags.is_empty() {
+            operation_builder.tag(tag)
+        } else {
+            operation_builder.tags(Some(metadata.tags.iter().copied()))
+        };
+        if metadata.deprecated {
+            operation_builder = operation_builder.deprecated(Some(openapi::Deprecated::True));
+        }
+        if !metadata.extensions.is_empty() {
+            let extensions = metadata
+                .extensions
+                .iter()
+                .fold(openapi::extensions::ExtensionsBuilder::new(), |builder, ext| {
+                    builder.add(ext.name, (ext.value)())
+                })
+                .build();
+            operation_builder = operation_builder.extensions(Some(extensions));
+        }
+
+        for scheme in metadata.security {
+            operation_builder = operation_builder
+                .security(openapi::security::SecurityRequirement::new(*scheme, Vec::<String>::new()));
+            if let Some(security_scheme) = crate::auth::security_scheme(scheme) {
+                security_schemes.insert(scheme.to_string(), security_scheme);
+            }
+        }
 
         for param in metadata.parameters {
-            let schema_ref = schemas
-                .get(param.type_name)
-                .cloned()
-                .unwrap_or_else(|| openapi::RefOr::T(Schema::default()));
+            let schema_ref = schema_for(param.type_name).unwrap_or_else(|| openapi::RefOr::T(Schema::default()));
+            // `Path<MyParams>` 与 `Query<MyParams>` 将 DTO 的字段展开为各自的参数，此时需要内联的 schema
+            let flattened = match resolver.dto_name(param.type_name).and_then(|name| schemas.get(name)) {
+                Some(openapi::RefOr::T(Schema::Object(obj))) => Some(obj),
+                _ => None,
+            };
 
             match param.param_in {
                 crate::ParamIn::Path => {
+                    // `Path<MyParams>`：结构体的每个字段都是一个路径参数
+                    if let Some(obj) = flattened.filter(|obj| !obj.properties.is_empty()) {
+                        for (prop_name, prop_schema) in obj.properties.iter() {
+                            let built_parameter = ParameterBuilder::new()
+                                .name(prop_name)
+                                .required(utoipa::openapi::Required::True)
+                                .description(property_description(prop_schema))
+                                .parameter_in(ParameterIn::Path)
+                                .schema(Some(parameter_schema(prop_schema)))
+                                .build();
+                            operation_builder = operation_builder.parameter(built_parameter);
+                        }
+                        continue;
+                    }
                     let built_parameter = ParameterBuilder::new()
                         .name(param.name)
                         .required(utoipa::openapi::Required::True)
-                        .description(Some(param.description))
+                        .description(non_empty(param.description))
                         .parameter_in(ParameterIn::Path)
                         .schema(Some(schema_ref))
                         .build();
                     operation_builder = operation_builder.parameter(built_parameter);
                 }
+                crate::ParamIn::Header | crate::ParamIn::Cookie => {
+                    // 注意：`parameter_in` 需先于 `required` 设置，否则按默认的 Path 处理为必填
+                    let built_parameter = ParameterBuilder::new()
+                        .name(param.name)
+                        .parameter_in(if matches!(param.param_in, crate::ParamIn::Header) { ParameterIn::Header } else { ParameterIn::Cookie })
+                        .required(if param.required { utoipa::openapi::Required::True } else { utoipa::openapi::Required::False })
+                        .description(non_empty(param.description))
+                        .schema(Some(schema_ref))
+                        .build();
+                    operation_builder = operation_builder.parameter(built_parameter);
+                }
                 crate::ParamIn::Query => {
-                    if let openapi::RefOr::T(Schema::Object(obj)) = &schema_ref {
+                    if let Some(obj) = flattened.filter(|obj| !obj.properties.is_empty()) {
                         for (prop_name, prop_schema) in obj.properties.iter() {
-                            let is_required = obj.required.iter().any(|r| r == prop_name);
+                            // 整个 Query 可选（`Option<Query<T>>`）时，其字段均不再必填
+                            let is_required = param.required && obj.required.iter().any(|r| r == prop_name);
                             let built_parameter = ParameterBuilder::new()
                                 .name(prop_name)
-                                .required(if is_required { utoipa::openapi::Required::True } else { utoipa::openapi::Required::False })
-                                .description(None::<&str>)
-                                .parameter_in(ParameterIn::Query)
-                                .schema(Some(prop_schema.clone()))
-                                .build();
-                            operation_builder = operation_builder.parameter(built_parameter);
-                        }
-                        if obj.properties.is_empty() {
-                            let built_parameter = ParameterBuilder::new()
-                                .name(param.name)
-                                .required(if param.required { utoipa::openapi::Required::True } else { utoipa::openapi::Required::False })
-                                .description(Some(param.description))
                                 .parameter_in(ParameterIn::Query)
-                                .schema(Some(schema_ref))
+                                .required(if is_required { utoipa::openapi::Required::True } else { utoipa::openapi::Required::False })
+                                .description(property_description(prop_schema))
+                                .schema(Some(parameter_schema(prop_schema)))
                                 .build();
                             operation_builder = operation_builder.parameter(built_parameter);
                         }
                     } else {
                         let built_parameter = ParameterBuilder::new()
                             .name(param.name)
-                            .required(if param.required { utoipa::openapi::Required::True } else { utoipa::openapi::Required::False })
-                            .description(Some(param.description))
                             .parameter_in(ParameterIn::Query)
+                            .required(if param.required { utoipa::openapi::Required::True } else { utoipa::openapi::Required::False })
+                            .description(non_empty(param.description))
                             .schema(Some(schema_ref))
                             .build();
                         operation_builder = operation_builder.parameter(built_parameter);
@@ -101,18 +132,27 @@ pub fn build_openapi_basic(title: &str, version: &str, description: &str, tag: &
         }
 
         if let Some(req_body_meta) = metadata.request_body {
-            let schema_ref = schemas
-                .get(req_body_meta.type_name)
-                .cloned()
-                .unwrap_or_else(|| openapi::RefOr::T(Schema::default()));
+            let schema_ref = match req_body_meta.content_type {
+                "application/octet-stream" => openapi::RefOr::T(Schema::Object(
+                    openapi::ObjectBuilder::new()
+                        .schema_type(openapi::schema::Type::String)
+                        .format(Some(openapi::SchemaFormat::KnownFormat(openapi::KnownFormat::Binary)))
+                        .build(),
+                )),
+                "text/plain" => openapi::RefOr::T(Schema::Object(
+                    openapi::ObjectBuilder::new().schema_type(openapi::schema::Type::String).build(),
+                )),
+                _ => schema_for(req_body_meta.type_name).unwrap_or_else(|| openapi::RefOr::T(Schema::default())),
+            };
 
             let request_body = utoipa::openapi::request_body::RequestBodyBuilder::new()
-                .description(Some(req_body_meta.description))
+                .description(non_empty(req_body_meta.description))
                 .required(Some(if req_body_meta.required { utoipa::openapi::Required::True } else { utoipa::openapi::Required::False }))
                 .content(
-                    "application/json",
+                    req_body_meta.content_type,
                     utoipa::openapi::ContentBuilder::new()
                         .schema(Some(schema_ref))
+                        .example(req_body_meta.example.map(|example| example()))
                         .build(),
                 )
                 .build();
@@ -124,16 +164,32 @@ pub fn build_openapi_basic(title: &str, version: &str, description: &str, tag: &
             let mut response_builder = utoipa::openapi::ResponseBuilder::new()
                 .description(resp.description);
 
-            if let Some(type_name) = resp.type_name {
-                if let Some(schema_ref) = schemas.get(type_name) {
-                    response_builder = response_builder.content(
-                        "application/json",
-                        utoipa::openapi::ContentBuilder::new().schema(Some(schema_ref.clone())).build()
-                    );
-                }
+            let schema_ref = resp.type_name.and_then(schema_for);
+            let example = resp.example.map(|example| example());
+            if resp.content_type == crate::sse::EVENT_STREAM {
+                // 事件流：schema 描述单个事件的 data，未声明时为字符串
+                let schema_ref = schema_ref.unwrap_or_else(|| {
+                    openapi::RefOr::T(Schema::Object(
+                        openapi::ObjectBuilder::new().schema_type(openapi::schema::Type::String).build(),
+                    ))
+                });
+                response_builder = response_builder.content(
+                    resp.content_type,
+                    utoipa::openapi::ContentBuilder::new().schema(Some(schema_ref)).example(example).build()
+                );
+            } else if schema_ref.is_some() || example.is_some() {
+                response_builder = response_builder.content(
+                    resp.content_type,
+                    utoipa::openapi::ContentBuilder::new().schema(schema_ref).example(example).build()
+                );
             }
 
-            responses_builder = responses_builder.response(resp.status_code.to_string(), response_builder.build());
+            let status = if resp.status_code == crate::DEFAULT_RESPONSE_STATUS {
+                "default".to_string()
+            } else {
+                resp.status_code.to_string()
+            };
+            responses_builder = responses_builder.response(status, response_builder.build());
         }
         operation_builder = operation_builder.responses(responses_builder.build());
 
@@ -168,12 +224,1061 @@ pub fn build_openapi_basic(title: &str, version: &str, description: &str, tag: &
         }
     }
 
-    let components = ComponentsBuilder::new()
-        .schemas_from_iter(schemas)
+    let schemas = schemas.into_iter().map(|(name, schema)| (component_name(&name), schema));
+    let components = security_schemes
+        .into_iter()
+        .fold(ComponentsBuilder::new().schemas_from_iter(schemas), |builder, (name, scheme)| {
+            builder.security_scheme(name, scheme)
+        })
         .build();
     openapi.components = Some(components);
 
     openapi
 }
 
+/// inventory 中全部 DTO 的 schema，以去掉路径前缀的类型名为键；
+/// DTO 字段引用的其他 `ToSchema` 类型一并收集，但不覆盖同名的已注册 DTO
+pub fn registered_schemas() -> HashMap<String, openapi::RefOr<Schema>> {
+    let mut schemas: HashMap<String, openapi::RefOr<Schema>> = inventory::iter::<ApiDtoMetadata>
+        .into_iter()
+        .map(|dto| {
+            let (name, schema) = (dto.schema_provider)();
+            (canonical_type_name(&name), schema)
+        })
+        .collect();
+    for dto in inventory::iter::<ApiDtoMetadata> {
+        for (name, schema) in (dto.nested_schemas)() {
+            schemas.entry(canonical_type_name(&name)).or_insert(schema);
+        }
+    }
+    schemas
+}
+
+/// 由 `#[api]` 元数据中的类型名（如 `Vec<crate::dtos::Product>`）解析 schema：
+/// 已注册的 DTO 引用其组件；`Vec`/`HashSet`、`HashMap`、`Option`、`Box` 等容器展开为
+/// array、map、可空 schema；`Uuid`、`DateTime`、`Decimal` 等常见类型带上对应的 `format`
+pub struct SchemaResolver {
+    /// 去掉路径前缀的 DTO 类型名
+    dtos: Vec<String>,
+}
+
+impl SchemaResolver {
+    pub fn new<'a>(dto_names: impl IntoIterator<Item = &'a str>) -> Self {
+        Self { dtos: dto_names.into_iter().map(canonical_type_name).collect() }
+    }
+
+    /// 无法解析的类型（未注册的自定义类型等）返回 `None`
+    pub fn resolve(&self, type_name: &str) -> Option<openapi::RefOr<Schema>> {
+        self.resolve_expr(&TypeExpr::parse(type_name)?)
+    }
+
+    /// 类型是已注册的 DTO 时，返回其去掉路径前缀的类型名（即 `components.schemas` 的键的来源）
+    pub fn dto_name(&self, type_name: &str) -> Option<&str> {
+        let name = canonical_type_name(type_name);
+        self.dtos.iter().find(|dto| **dto == name).map(String::as_str)
+    }
+
+    fn resolve_expr(&self, ty: &TypeExpr) -> Option<openapi::RefOr<Schema>> {
+        use utoipa::openapi::schema::{ArrayBuilder, KnownFormat, ObjectBuilder, SchemaFormat, Type};
+
+        let (name, args) = match ty {
+            TypeExpr::Path { name, args } => (name.as_str(), args.as_slice()),
+            TypeExpr::Reference(inner) => return self.resolve_expr(inner),
+            TypeExpr::Slice(inner) => {
+                return Some(openapi::RefOr::T(Schema::Array(ArrayBuilder::new().items(self.item(inner)).build())));
+            }
+            TypeExpr::Tuple(elems) if elems.is_empty() => return None,
+            TypeExpr::Tuple(elems) => {
+                let mut items: Vec<openapi::RefOr<Schema>> = Vec::new();
+                for item in elems.iter().map(|elem| self.item(elem)) {
+                    if !items.contains(&item) {
+                        items.push(item);
+                    }
+                }
+                let items = match items.len() {
+                    1 => items.remove(0),
+                    _ => openapi::RefOr::T(Schema::OneOf(
+                        items.into_iter().fold(openapi::schema::OneOfBuilder::new(), |b, item| b.item(item)).build(),
+                    )),
+                };
+                let array = ArrayBuilder::new()
+                    .items(items)
+                    .min_items(Some(elems.len()))
+                    .max_items(Some(elems.len()))
+                    .build();
+                return Some(openapi::RefOr::T(Schema::Array(array)));
+            }
+        };
+
+        if self.dtos.iter().any(|dto| *dto == ty.to_string()) {
+            return Some(openapi::RefOr::Ref(openapi::Ref::from_schema_name(component_name(&ty.to_string()))));
+        }
+
+        let arg = |index: usize| args.get(index);
+        let object = |schema_type: Type, format: Option<SchemaFormat>| {
+            Some(openapi::RefOr::T(Schema::Object(ObjectBuilder::new().schema_type(schema_type).format(format).build())))
+        };
+        let known = |format: KnownFormat| Some(SchemaFormat::KnownFormat(format));
+        let custom = |format: &str| Some(SchemaFormat::Custom(format.to_string()));
+        let integer = |format: KnownFormat, unsigned: bool| {
+            let builder = ObjectBuilder::new().schema_type(Type::Integer).format(known(format));
+            let builder = if unsigned { builder.minimum(Some(0)) } else { builder };
+            Some(openapi::RefOr::T(Schema::Object(builder.build())))
+        };
+
+        match name {
+            // 包装类型：与内层类型的 JSON 表示相同
+            "Box" | "Arc" | "Rc" | "Cow" | "RefCell" | "Cell" | "Mutex" | "RwLock" | "Json" | "Form" | "Wrapping" => {
+                self.resolve_expr(arg(0)?)
+            }
+            "Option" => Some(nullable(self.item(arg(0)?))),
+            "Vec" | "VecDeque" | "LinkedList" | "BinaryHeap" => {
+                let items = self.item(arg(0)?);
+                Some(openapi::RefOr::T(Schema::Array(ArrayBuilder::new().items(items).build())))
+            }
+            "HashSet" | "BTreeSet" | "IndexSet" => {
+                let items = self.item(arg(0)?);
+                Some(openapi::RefOr::T(Schema::Array(ArrayBuilder::new().items(items).unique_items(true).build())))
+            }
+            // JSON 对象的键总是字符串，只需描述值的 schema
+            "HashMap" | "BTreeMap" | "IndexMap" => {
+                let values = self.item(arg(1)?);
+                let map = ObjectBuilder::new().schema_type(Type::Object).additional_properties(Some(values));
+                Some(openapi::RefOr::T(Schema::Object(map.build())))
+            }
+            "String" | "str" | "char" => object(Type::String, None),
+            "bool" => object(Type::Boolean, None),
+            "i8" | "i16" | "i32" => integer(KnownFormat::Int32, false),
+            "u8" | "u16" | "u32" => integer(KnownFormat::Int32, true),
+            "i64" | "i128" | "isize" => integer(KnownFormat::Int64, false),
+            "u64" | "u128" | "usize" => integer(KnownFormat::Int64, true),
+            "f32" => object(Type::Number, known(KnownFormat::Float)),
+            "f64" => object(Type::Number, known(KnownFormat::Double)),
+            "Uuid" => object(Type::String, custom("uuid")),
+            "DateTime" | "NaiveDateTime" | "OffsetDateTime" | "PrimitiveDateTime" | "Timestamp" => {
+                object(Type::String, known(KnownFormat::DateTime))
+            }
+            "NaiveDate" | "Date" => object(Type::String, known(KnownFormat::Date)),
+            "NaiveTime" | "Time" => object(Type::String, known(KnownFormat::Time)),
+            // `rust_decimal` 与 `bigdecimal` 默认序列化为字符串，以免丢失精度
+            "Decimal" | "BigDecimal" => object(Type::String, custom("decimal")),
+            "Url" | "Uri" => object(Type::String, custom("uri")),
+            "Bytes" => object(Type::String, known(KnownFormat::Binary)),
+            // `serde_json::Value`：任意 JSON 值
+            "Value" => Some(openapi::RefOr::T(Schema::Object(
+                ObjectBuilder::new().schema_type(openapi::schema::SchemaType::AnyValue).build(),
+            ))),
+            "Map" => object(Type::Object, None),
+            _ => None,
+        }
+    }
+
+    /// 容器的元素：无法解析时接受任意值，容器本身的结构仍然保留
+    fn item(&self, ty: &TypeExpr) -> openapi::RefOr<Schema> {
+        self.resolve_expr(ty).unwrap_or_else(|| {
+            openapi::RefOr::T(Schema::Object(
+                openapi::ObjectBuilder::new().schema_type(openapi::schema::SchemaType::AnyValue).build(),
+            ))
+        })
+    }
+}
+
+/// `Option<T>` 的 schema：与 utoipa 一致，内联 schema 在 `type` 中加入 `null`，引用则写成 `oneOf: [null, $ref]`
+fn nullable(schema: openapi::RefOr<Schema>) -> openapi::RefOr<Schema> {
+    use utoipa::openapi::schema::{SchemaType, Type};
+    let with_null = |schema_type: SchemaType| match schema_type {
+        SchemaType::Type(Type::Null) => SchemaType::Type(Type::Null),
+        SchemaType::Type(ty) => SchemaType::from_iter([ty, Type::Null]),
+        SchemaType::Array(mut types) => {
+            if !types.contains(&Type::Null) {
+                types.push(Type::Null);
+            }
+            SchemaType::Array(types)
+        }
+        SchemaType::AnyValue => SchemaType::AnyValue,
+    };
+    match schema {
+        openapi::RefOr::T(Schema::Object(mut obj)) => {
+            obj.schema_type = with_null(obj.schema_type);
+            openapi::RefOr::T(Schema::Object(obj))
+        }
+        openapi::RefOr::T(Schema::Array(mut arr)) => {
+            arr.schema_type = with_null(arr.schema_type);
+            openapi::RefOr::T(Schema::Array(arr))
+        }
+        schema => {
+            let null = openapi::ObjectBuilder::new().schema_type(Type::Null).build();
+            openapi::RefOr::T(Schema::OneOf(openapi::schema::OneOfBuilder::new().item(null).item(schema).build()))
+        }
+    }
+}
+
+/// 去掉每一段路径前缀后的类型名：`crate::dtos::Page<crate::dtos::Product>` -> `Page<Product>`；
+/// 无法解析时原样返回
+pub fn canonical_type_name(type_name: &str) -> String {
+    TypeExpr::parse(type_name).map_or_else(|| type_name.to_string(), |ty| ty.to_string())
+}
+
+/// 类型名的语法树，路径只保留最后一段，生命周期与数组长度被忽略
+#[derive(Debug, Clone, PartialEq)]
+enum TypeExpr {
+    Path { name: String, args: Vec<TypeExpr> },
+    Tuple(Vec<TypeExpr>),
+    /// `[T]` 与 `[T; N]`
+    Slice(Box<TypeExpr>),
+    Reference(Box<TypeExpr>),
+}
+
+impl TypeExpr {
+    /// 类型名来自 `#[api]` 生成的元数据，已去掉全部空白（如 `HashMap<String,f64>`、`&'staticstr`）
+    fn parse(type_name: &str) -> Option<TypeExpr> {
+        let compact: String = type_name.chars().filter(|c| !c.is_whitespace()).collect();
+        let mut parser = TypeParser { input: &compact, pos: 0 };
+        let ty = parser.parse_type()?;
+        (parser.pos == compact.len()).then_some(ty)
+    }
+}
+
+impl std::fmt::Display for TypeExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let join = |types: &[TypeExpr]| types.iter().map(ToString::to_string).collect::<Vec<_>>().join(",");
+        match self {
+            TypeExpr::Path { name, args } if args.is_empty() => f.write_str(name),
+            TypeExpr::Path { name, args } => write!(f, "{}<{}>", name, join(args)),
+            TypeExpr::Tuple(elems) => write!(f, "({})", join(elems)),
+            TypeExpr::Slice(inner) => write!(f, "[{}]", inner),
+            TypeExpr::Reference(inner) => write!(f, "&{}", inner),
+        }
+    }
+}
+
+struct TypeParser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl TypeParser<'_> {
+    fn rest(&self) -> &str {
+        &self.input[self.pos..]
+    }
 
+    fn eat(&mut self, token: &str) -> bool {
+        let matched = self.rest().starts_with(token);
+        if matched {
+            self.pos += token.len();
+        }
+        matched
+    }
+
+    fn ident(&mut self) -> Option<&str> {
+        let start = self.pos;
+        let len = self.rest().find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(self.rest().len());
+        self.pos += len;
+        (len > 0).then(|| &self.input[start..self.pos])
+    }
+
+    fn parse_type(&mut self) -> Option<TypeExpr> {
+        if self.eat("&") {
+            // 空白已被去掉，只能可靠地识别 `'static`
+            self.eat("'static");
+            self.eat("mut");
+            return Some(TypeExpr::Reference(Box::new(self.parse_type()?)));
+        }
+        if self.eat("(") {
+            let elems = self.parse_list(")")?;
+            return Some(match elems.len() {
+                // `(T)` 只是带括号的类型
+                1 if !self.input[..self.pos].ends_with(",)") => elems.into_iter().next()?,
+                _ => TypeExpr::Tuple(elems),
+            });
+        }
+        if self.eat("[") {
+            let inner = self.parse_type()?;
+            if self.eat(";") {
+                let len = self.rest().find(']')?;
+                self.pos += len;
+            }
+            return self.eat("]").then(|| TypeExpr::Slice(Box::new(inner)));
+        }
+
+        self.eat("::");
+        let mut name = self.ident()?.to_string();
+        let mut args = Vec::new();
+        loop {
+            if self.eat("<") {
+                args = self.parse_list(">")?;
+            }
+            if !self.eat("::") {
+                break;
+            }
+            name = self.ident()?.to_string();
+            args.clear();
+        }
+        Some(TypeExpr::Path { name, args })
+    }
+
+    /// 以逗号分隔、以 `close` 结尾的类型列表；跳过生命周期参数与 `Item=T` 形式的关联类型绑定
+    fn parse_list(&mut self, close: &str) -> Option<Vec<TypeExpr>> {
+        let mut types = Vec::new();
+        while !self.eat(close) {
+            if self.eat("'") {
+                self.ident()?;
+            } else {
+                types.push(self.parse_type()?);
+                if self.eat("=") {
+                    types.pop();
+                    types.push(self.parse_type()?);
+                }
+            }
+            if !self.eat(",") && !self.rest().starts_with(close) {
+                return None;
+            }
+        }
+        Some(types)
+    }
+}
+
+/// 解析 `#/components/schemas/<Name>` 引用；内联 schema 原样返回，找不到组件时为 `None`
+pub fn resolve_schema<'a>(openapi: &'a openapi::OpenApi, schema: &'a openapi::RefOr<Schema>) -> Option<&'a Schema> {
+    match schema {
+        openapi::RefOr::T(schema) => Some(schema),
+        openapi::RefOr::Ref(reference) => {
+            let name = reference.ref_location.strip_prefix("#/components/schemas/")?;
+            match openapi.components.as_ref()?.schemas.get(name)? {
+                openapi::RefOr::T(schema) => Some(schema),
+                // 组件本身也可以是引用，此处不再继续展开
+                openapi::RefOr::Ref(_) => None,
+            }
+        }
+    }
+}
+
+/// `components.schemas` 的键只允许 `[A-Za-z0-9._-]`：泛型实例 `Page<Product>` 记为 `Page_Product`
+pub fn component_name(type_name: &str) -> String {
+    type_name
+        .replace("::", ".")
+        .chars()
+        .filter_map(|c| match c {
+            '<' | ',' => Some('_'),
+            c if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 为带标签的枚举（`#[serde(tag = "...")]`）的 `oneOf` schema 设置 discriminator
+pub fn set_discriminator(schema: &mut openapi::RefOr<Schema>, property_name: &str) {
+    if let openapi::RefOr::T(Schema::OneOf(one_of)) = schema {
+        one_of.discriminator = Some(openapi::schema::Discriminator::new(property_name));
+    }
+}
+
+/// 空描述不输出到规范中
+fn non_empty(text: &str) -> Option<&str> {
+    (!text.is_empty()).then_some(text)
+}
+
+/// 展开 DTO 字段为参数时，沿用字段 doc 注释生成的 schema 描述
+fn property_description(schema: &openapi::RefOr<Schema>) -> Option<String> {
+    match schema {
+        openapi::RefOr::T(Schema::Object(obj)) => obj.description.clone(),
+        openapi::RefOr::T(Schema::Array(arr)) => arr.description.clone(),
+        openapi::RefOr::T(Schema::OneOf(one_of)) => one_of.description.clone(),
+        openapi::RefOr::T(Schema::AllOf(all_of)) => all_of.description.clone(),
+        _ => None,
+    }
+}
+
+/// 参数能否省略由 `required` 表达：去掉 `Option<T>` 字段 schema 中的 `null` 类型，
+/// 使参数 schema 保持 OpenAPI 3.0 的单一类型形式（CLI 使用的解析器不支持类型数组）
+fn parameter_schema(schema: &openapi::RefOr<Schema>) -> openapi::RefOr<Schema> {
+    let mut schema = schema.clone();
+    if let openapi::RefOr::T(Schema::Object(obj)) = &mut schema {
+        if let openapi::schema::SchemaType::Array(types) = &obj.schema_type {
+            let non_null: Vec<_> = types.iter().filter(|t| **t != openapi::schema::Type::Null).cloned().collect();
+            if let [single] = non_null.as_slice() {
+                obj.schema_type = openapi::schema::SchemaType::Type(single.clone());
+            }
+        }
+    }
+    schema
+}
+
+// --- 规范差异（`cargo forge spec diff`） ---
+
+/// 变更对既有客户端的影响
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeLevel {
+    Breaking,
+    NonBreaking,
+}
+
+/// 两份规范之间的一处变更
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SpecChange {
+    pub level: ChangeLevel,
+    /// 变更所在的操作，如 `GET /v1/products/{id}`
+    pub operation: String,
+    /// 变更说明，如 ``response `200` field `items[].name` was removed``
+    pub message: String,
+}
+
+/// [`diff_specs`] 的结果；`Display` 输出按破坏性分组的可读报告
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SpecDiff {
+    pub changes: Vec<SpecChange>,
+}
+
+impl SpecDiff {
+    pub fn has_breaking_changes(&self) -> bool {
+        self.changes.iter().any(|change| change.level == ChangeLevel::Breaking)
+    }
+
+    pub fn changes_at(&self, level: ChangeLevel) -> impl Iterator<Item = &SpecChange> {
+        self.changes.iter().filter(move |change| change.level == level)
+    }
+}
+
+impl std::fmt::Display for SpecDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.changes.is_empty() {
+            return writeln!(f, "No changes.");
+        }
+        for (level, title) in [(ChangeLevel::Breaking, "Breaking changes"), (ChangeLevel::NonBreaking, "Non-breaking changes")] {
+            let changes: Vec<&SpecChange> = self.changes_at(level).collect();
+            if changes.is_empty() {
+                continue;
+            }
+            writeln!(f, "{} ({}):", title, changes.len())?;
+            for change in changes {
+                writeln!(f, "  - {}: {}", change.operation, change.message)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+const HTTP_METHODS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+/// 比较两份 OpenAPI JSON 文档（3.0 或 3.1），按既有客户端的视角将变更分为破坏性与非破坏性。
+///
+/// 请求中的 schema 收窄（新增必填字段或参数、枚举值减少、不再接受 `null`）与响应中的
+/// schema 放宽（字段被删除或变为可选、新增枚举值、可能为 `null`）都是破坏性变更；
+/// 类型改变与操作被删除总是破坏性的。路径按模板匹配，占位符改名不视为变更。
+pub fn diff_specs(old: &Value, new: &Value) -> SpecDiff {
+    let mut differ = SpecDiffer { old, new, operation: String::new(), visiting: HashSet::new(), changes: Vec::new() };
+    let (old_paths, new_paths) = (object(old, "paths"), object(new, "paths"));
+    let new_by_template: HashMap<String, (&String, &Value)> =
+        new_paths.iter().map(|(path, item)| (path_template(path), (path, item))).collect();
+    let old_templates: HashSet<String> = old_paths.keys().map(|path| path_template(path)).collect();
+
+    for (old_path, old_item) in old_paths {
+        let new_entry = new_by_template.get(&path_template(old_path));
+        for method in HTTP_METHODS {
+            let Some(old_op) = old_item.get(*method) else { continue };
+            match new_entry.and_then(|(new_path, item)| Some((*new_path, item.get(*method)?))) {
+                Some((new_path, new_op)) => {
+                    differ.operation = format!("{} {}", method.to_uppercase(), new_path);
+                    differ.diff_operation(old_path, old_op, new_path, new_op);
+                }
+                None => {
+                    differ.operation = format!("{} {}", method.to_uppercase(), old_path);
+                    differ.record(ChangeLevel::Breaking, "operation was removed".to_string());
+                }
+            }
+        }
+        if let Some((new_path, new_item)) = new_entry {
+            for method in HTTP_METHODS.iter().filter(|m| old_item.get(**m).is_none() && new_item.get(**m).is_some()) {
+                differ.operation = format!("{} {}", method.to_uppercase(), new_path);
+                differ.record(ChangeLevel::NonBreaking, "operation was added".to_string());
+            }
+        }
+    }
+    for (new_path, new_item) in new_paths.iter().filter(|(path, _)| !old_templates.contains(&path_template(path))) {
+        for method in HTTP_METHODS.iter().filter(|m| new_item.get(**m).is_some()) {
+            differ.operation = format!("{} {}", method.to_uppercase(), new_path);
+            differ.record(ChangeLevel::NonBreaking, "operation was added".to_string());
+        }
+    }
+    SpecDiff { changes: differ.changes }
+}
+
+/// 路径模板去掉占位符名称：`/v1/products/{id}` 与 `/v1/products/{product_id}` 是同一路径
+fn path_template(path: &str) -> String {
+    let mut template = String::with_capacity(path.len());
+    let mut in_placeholder = false;
+    for c in path.chars() {
+        match c {
+            '{' => {
+                in_placeholder = true;
+                template.push_str("{}");
+            }
+            '}' => in_placeholder = false,
+            c if !in_placeholder => template.push(c),
+            _ => {}
+        }
+    }
+    template
+}
+
+/// schema 所在的方向决定收窄还是放宽会破坏客户端
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Direction {
+    Request,
+    Response,
+}
+
+impl Direction {
+    /// 请求中收窄、响应中放宽是破坏性的
+    fn level(self, narrowed: bool) -> ChangeLevel {
+        match (self, narrowed) {
+            (Direction::Request, true) | (Direction::Response, false) => ChangeLevel::Breaking,
+            _ => ChangeLevel::NonBreaking,
+        }
+    }
+}
+
+struct SpecDiffer<'a> {
+    old: &'a Value,
+    new: &'a Value,
+    operation: String,
+    /// 正在比较的 `$ref` 组合，避免递归 DTO 无限展开
+    visiting: HashSet<(String, String, Direction)>,
+    changes: Vec<SpecChange>,
+}
+
+impl<'a> SpecDiffer<'a> {
+    fn record(&mut self, level: ChangeLevel, message: String) {
+        self.changes.push(SpecChange { level, operation: self.operation.clone(), message });
+    }
+
+    fn diff_operation(&mut self, old_path: &str, old_op: &'a Value, new_path: &str, new_op: &'a Value) {
+        self.diff_parameters(old_path, old_op, new_path, new_op);
+        self.diff_request_body(old_op, new_op);
+        self.diff_responses(old_op, new_op);
+    }
+
+    fn diff_parameters(&mut self, old_path: &str, old_op: &'a Value, new_path: &str, new_op: &'a Value) {
+        // path 参数按占位符的位置匹配，其余参数按 (位置, 名称) 匹配
+        let key = |path: &str, param: &Value| -> (String, String) {
+            let location = param.get("in").and_then(Value::as_str).unwrap_or_default().to_string();
+            let name = param.get("name").and_then(Value::as_str).unwrap_or_default();
+            if location == "path" {
+                let index = path.find(&format!("{{{}}}", name)).map(|at| path[..at].matches('{').count());
+                (location, format!("#{}", index.unwrap_or(usize::MAX)))
+            } else {
+                (location, name.to_string())
+            }
+        };
+        let old_params: Vec<(&Value, (String, String))> =
+            parameters(self.old, old_op).into_iter().map(|param| (param, key(old_path, param))).collect();
+        let new_params: Vec<(&Value, (String, String))> =
+            parameters(self.new, new_op).into_iter().map(|param| (param, key(new_path, param))).collect();
+        let name = |param: &Value| param.get("name").and_then(Value::as_str).unwrap_or_default().to_string();
+        let required = |param: &Value| param.get("required").and_then(Value::as_bool).unwrap_or(false);
+
+        for (old_param, old_key) in &old_params {
+            let label = format!("{} parameter `{}`", old_key.0, name(old_param));
+            let Some((new_param, _)) = new_params.iter().find(|(_, new_key)| new_key == old_key) else {
+                self.record(ChangeLevel::NonBreaking, format!("{} was removed", label));
+                continue;
+            };
+            let label = format!("{} parameter `{}`", old_key.0, name(new_param));
+            match (required(old_param), required(new_param)) {
+                (false, true) => self.record(ChangeLevel::Breaking, format!("{} became required", label)),
+                (true, false) => self.record(ChangeLevel::NonBreaking, format!("{} became optional", label)),
+                _ => {}
+            }
+            if let (Some(old_schema), Some(new_schema)) = (old_param.get("schema"), new_param.get("schema")) {
+                self.diff_schema(old_schema, new_schema, Direction::Request, &label, "");
+            }
+        }
+        for (new_param, new_key) in &new_params {
+            if old_params.iter().any(|(_, old_key)| old_key == new_key) {
+                continue;
+            }
+            if required(new_param) {
+                self.record(ChangeLevel::Breaking, format!("required {} parameter `{}` was added", new_key.0, name(new_param)));
+            } else {
+                self.record(ChangeLevel::NonBreaking, format!("optional {} parameter `{}` was added", new_key.0, name(new_param)));
+            }
+        }
+    }
+
+    fn diff_request_body(&mut self, old_op: &'a Value, new_op: &'a Value) {
+        let old_body = old_op.get("requestBody").map(|body| resolve_ref(self.old, body));
+        let new_body = new_op.get("requestBody").map(|body| resolve_ref(self.new, body));
+        let required = |body: &Value| body.get("required").and_then(Value::as_bool).unwrap_or(false);
+        match (old_body, new_body) {
+            (None, None) => {}
+            (None, Some(new_body)) if required(new_body) => {
+                self.record(ChangeLevel::Breaking, "required request body was added".to_string())
+            }
+            (None, Some(_)) => self.record(ChangeLevel::NonBreaking, "optional request body was added".to_string()),
+            (Some(_), None) => self.record(ChangeLevel::NonBreaking, "request body was removed".to_string()),
+            (Some(old_body), Some(new_body)) => {
+                match (required(old_body), required(new_body)) {
+                    (false, true) => self.record(ChangeLevel::Breaking, "request body became required".to_string()),
+                    (true, false) => self.record(ChangeLevel::NonBreaking, "request body became optional".to_string()),
+                    _ => {}
+                }
+                self.diff_content(old_body, new_body, Direction::Request, "request body");
+            }
+        }
+    }
+
+    fn diff_responses(&mut self, old_op: &'a Value, new_op: &'a Value) {
+        let (old_responses, new_responses) = (object(old_op, "responses"), object(new_op, "responses"));
+        for (status, old_response) in old_responses {
+            let label = format!("response `{}`", status);
+            let Some(new_response) = new_responses.get(status) else {
+                // 客户端依赖成功响应；撤下已声明的错误响应不影响既有调用
+                let level = if status.starts_with('2') { ChangeLevel::Breaking } else { ChangeLevel::NonBreaking };
+                self.record(level, format!("{} was removed", label));
+                continue;
+            };
+            let (old_response, new_response) = (resolve_ref(self.old, old_response), resolve_ref(self.new, new_response));
+            self.diff_content(old_response, new_response, Direction::Response, &label);
+        }
+        for status in new_responses.keys().filter(|status| !old_responses.contains_key(*status)) {
+            self.record(ChangeLevel::NonBreaking, format!("response `{}` was added", status));
+        }
+    }
+
+    /// 比较请求体或响应的 `content`：按媒体类型匹配后比较 schema
+    fn diff_content(&mut self, old: &'a Value, new: &'a Value, direction: Direction, label: &str) {
+        let (old_content, new_content) = (object(old, "content"), object(new, "content"));
+        for (media_type, old_media) in old_content {
+            let Some(new_media) = new_content.get(media_type) else {
+                let level = direction.level(true);
+                let verb = if direction == Direction::Request { "accepts" } else { "returns" };
+                self.record(level, format!("{} no longer {} `{}`", label, verb, media_type));
+                continue;
+            };
+            let label = if old_content.len() > 1 { format!("{} `{}`", label, media_type) } else { label.to_string() };
+            if let (Some(old_schema), Some(new_schema)) = (old_media.get("schema"), new_media.get("schema")) {
+                self.diff_schema(old_schema, new_schema, direction, &label, "");
+            }
+        }
+        for media_type in new_content.keys().filter(|media_type| !old_content.contains_key(*media_type)) {
+            let verb = if direction == Direction::Request { "accepts" } else { "may return" };
+            self.record(ChangeLevel::NonBreaking, format!("{} now {} `{}`", label, verb, media_type));
+        }
+    }
+
+    /// 递归比较两个 schema；`field` 是相对 `label` 的字段路径，如 `items[].name`
+    fn diff_schema(&mut self, old: &'a Value, new: &'a Value, direction: Direction, label: &str, field: &str) {
+        let (old, new) = (normalize_schema(self.old, old), normalize_schema(self.new, new));
+        let guard = match (old.reference, new.reference) {
+            (Some(old_ref), Some(new_ref)) => Some((old_ref.to_string(), new_ref.to_string(), direction)),
+            _ => None,
+        };
+        if let Some(guard) = &guard {
+            if !self.visiting.insert(guard.clone()) {
+                return;
+            }
+        }
+        self.diff_resolved_schema(old, new, direction, label, field);
+        if let Some(guard) = &guard {
+            self.visiting.remove(guard);
+        }
+    }
+
+    fn diff_resolved_schema(&mut self, old: NormalizedSchema<'a>, new: NormalizedSchema<'a>, direction: Direction, label: &str, field: &str) {
+        let subject = if field.is_empty() { label.to_string() } else { format!("{} field `{}`", label, field) };
+
+        let (old_types, new_types) = (schema_types(old.schema), schema_types(new.schema));
+        if !old_types.is_empty() && !new_types.is_empty() && old_types != new_types {
+            let level = if new_types.is_subset(&old_types) {
+                direction.level(true)
+            } else if old_types.is_subset(&new_types) {
+                direction.level(false)
+            } else {
+                ChangeLevel::Breaking
+            };
+            let join = |types: &BTreeSet<&str>| types.iter().map(|ty| format!("`{}`", ty)).collect::<Vec<_>>().join(" | ");
+            self.record(level, format!("{} type changed from {} to {}", subject, join(&old_types), join(&new_types)));
+            return;
+        }
+
+        match (old.nullable, new.nullable) {
+            (true, false) => self.record(direction.level(true), format!("{} is no longer nullable", subject)),
+            (false, true) => self.record(direction.level(false), format!("{} became nullable", subject)),
+            _ => {}
+        }
+
+        let format_of = |schema: &'a Value| schema.get("format").and_then(Value::as_str);
+        if let (Some(old_format), Some(new_format)) = (format_of(old.schema), format_of(new.schema)) {
+            if old_format != new_format {
+                self.record(ChangeLevel::Breaking, format!("{} format changed from `{}` to `{}`", subject, old_format, new_format));
+            }
+        }
+
+        self.diff_enum(old.schema, new.schema, direction, &subject);
+        self.diff_properties(old.schema, new.schema, direction, label, field);
+
+        if let (Some(old_items), Some(new_items)) = (old.schema.get("items"), new.schema.get("items")) {
+            self.diff_schema(old_items, new_items, direction, label, &format!("{}[]", field));
+        }
+        if let (Some(old_values @ Value::Object(_)), Some(new_values @ Value::Object(_))) =
+            (old.schema.get("additionalProperties"), new.schema.get("additionalProperties"))
+        {
+            let field = if field.is_empty() { "*".to_string() } else { format!("{}.*", field) };
+            self.diff_schema(old_values, new_values, direction, label, &field);
+        }
+        self.diff_variants(old.schema, new.schema, direction, label, field, &subject);
+    }
+
+    fn diff_enum(&mut self, old: &Value, new: &Value, direction: Direction, subject: &str) {
+        let values = |schema: &Value| -> Option<Vec<Value>> {
+            let mut values: Vec<Value> = schema.get("enum")?.as_array()?.clone();
+            values.retain(|value| !value.is_null());
+            Some(values)
+        };
+        let list = |values: Vec<&Value>| values.iter().map(|value| format!("`{}`", enum_value_text(value))).collect::<Vec<_>>().join(", ");
+        match (values(old), values(new)) {
+            (Some(old_values), Some(new_values)) => {
+                let removed: Vec<&Value> = old_values.iter().filter(|value| !new_values.contains(value)).collect();
+                let added: Vec<&Value> = new_values.iter().filter(|value| !old_values.contains(value)).collect();
+                if !removed.is_empty() {
+                    self.record(direction.level(true), format!("{} enum values removed: {}", subject, list(removed)));
+                }
+                if !added.is_empty() {
+                    self.record(direction.level(false), format!("{} enum values added: {}", subject, list(added)));
+                }
+            }
+            (None, Some(_)) => self.record(direction.level(true), format!("{} is now restricted to an enum", subject)),
+            (Some(_), None) => self.record(direction.level(false), format!("{} is no longer restricted to an enum", subject)),
+            (None, None) => {}
+        }
+    }
+
+    fn diff_properties(&mut self, old: &'a Value, new: &'a Value, direction: Direction, label: &str, field: &str) {
+        let (Some(old_properties), Some(new_properties)) =
+            (old.get("properties").and_then(Value::as_object), new.get("properties").and_then(Value::as_object))
+        else {
+            return;
+        };
+        let required = |schema: &Value, name: &str| {
+            schema.get("required").and_then(Value::as_array).is_some_and(|names| names.iter().any(|n| n == name))
+        };
+        let child = |name: &str| if field.is_empty() { name.to_string() } else { format!("{}.{}", field, name) };
+
+        for (name, old_property) in old_properties {
+            let path = child(name);
+            let Some(new_property) = new_properties.get(name) else {
+                self.record(direction.level(false), format!("{} field `{}` was removed", label, path));
+                continue;
+            };
+            match (required(old, name), required(new, name)) {
+                (false, true) => self.record(direction.level(true), format!("{} field `{}` became required", label, path)),
+                (true, false) => self.record(direction.level(false), format!("{} field `{}` became optional", label, path)),
+                _ => {}
+            }
+            self.diff_schema(old_property, new_property, direction, label, &path);
+        }
+        for name in new_properties.keys().filter(|name| !old_properties.contains_key(*name)) {
+            if required(new, name) {
+                self.record(direction.level(true), format!("{} required field `{}` was added", label, child(name)));
+            } else {
+                self.record(ChangeLevel::NonBreaking, format!("{} optional field `{}` was added", label, child(name)));
+            }
+        }
+    }
+
+    /// `oneOf`/`anyOf` 的分支按引用的组件名或标签值匹配，如 `rule(percent_off).max_discount`
+    fn diff_variants(&mut self, old: &'a Value, new: &'a Value, direction: Direction, label: &str, field: &str, subject: &str) {
+        for combinator in ["oneOf", "anyOf"] {
+            let (Some(old_variants), Some(new_variants)) =
+                (old.get(combinator).and_then(Value::as_array), new.get(combinator).and_then(Value::as_array))
+            else {
+                continue;
+            };
+            let old_keyed: Vec<(String, &Value)> =
+                old_variants.iter().enumerate().map(|(i, variant)| (variant_key(self.old, variant, i), variant)).collect();
+            let new_keyed: Vec<(String, &Value)> =
+                new_variants.iter().enumerate().map(|(i, variant)| (variant_key(self.new, variant, i), variant)).collect();
+            for (key, old_variant) in &old_keyed {
+                match new_keyed.iter().find(|(new_key, _)| new_key == key) {
+                    Some((_, new_variant)) => {
+                        self.diff_schema(old_variant, new_variant, direction, label, &format!("{}({})", field, key));
+                    }
+                    None => self.record(direction.level(true), format!("{} variant `{}` was removed", subject, key)),
+                }
+            }
+            for (key, _) in new_keyed.iter().filter(|(key, _)| old_keyed.iter().all(|(old_key, _)| old_key != key)) {
+                self.record(direction.level(false), format!("{} variant `{}` was added", subject, key));
+            }
+        }
+    }
+}
+
+/// 展开 `$ref` 与可空包装后的 schema
+struct NormalizedSchema<'a> {
+    schema: &'a Value,
+    nullable: bool,
+    /// 最后展开的 `$ref`，递归 DTO 以此判断是否已在比较中
+    reference: Option<&'a str>,
+}
+
+/// 展开引用，并把 3.1 的 `[T, "null"]`、`oneOf: [null, T]` 与 3.0 的 `nullable`、
+/// 单分支 `allOf` 统一为「schema + 是否可空」
+fn normalize_schema<'a>(spec: &'a Value, schema: &'a Value) -> NormalizedSchema<'a> {
+    let mut reference = schema.get("$ref").and_then(Value::as_str);
+    let mut schema = resolve_ref(spec, schema);
+    let mut nullable = false;
+    loop {
+        nullable |= schema.get("nullable").and_then(Value::as_bool).unwrap_or(false);
+        nullable |= schema.get("type").and_then(Value::as_array).is_some_and(|types| types.iter().any(|ty| ty == "null"));
+        let is_null = |branch: &Value| branch.get("type").and_then(Value::as_str) == Some("null");
+        let single_branch = ["oneOf", "anyOf", "allOf"].into_iter().find_map(|combinator| {
+            let branches = schema.get(combinator)?.as_array()?;
+            let non_null: Vec<&Value> = branches.iter().filter(|branch| !is_null(branch)).collect();
+            let has_null = non_null.len() < branches.len();
+            match non_null.as_slice() {
+                [single] if has_null || combinator == "allOf" => Some((*single, has_null)),
+                _ => None,
+            }
+        });
+        match single_branch {
+            Some((branch, has_null)) => {
+                nullable |= has_null;
+                reference = branch.get("$ref").and_then(Value::as_str).or(reference);
+                schema = resolve_ref(spec, branch);
+            }
+            None => return NormalizedSchema { schema, nullable, reference },
+        }
+    }
+}
+
+/// 跟随文档内的 `$ref`（如 `#/components/schemas/Product`）；无法解析时原样返回
+fn resolve_ref<'a>(spec: &'a Value, mut value: &'a Value) -> &'a Value {
+    // 组件之间的引用链有限，上限只为防止自引用的组件
+    for _ in 0..32 {
+        let Some(target) = value.get("$ref").and_then(Value::as_str).and_then(|r| r.strip_prefix('#')) else { break };
+        match spec.pointer(target) {
+            Some(resolved) => value = resolved,
+            None => break,
+        }
+    }
+    value
+}
+
+/// `value[key]` 对象；缺失时为空对象
+fn object<'a>(value: &'a Value, key: &str) -> &'a serde_json::Map<String, Value> {
+    static EMPTY: once_cell::sync::Lazy<serde_json::Map<String, Value>> = once_cell::sync::Lazy::new(serde_json::Map::new);
+    value.get(key).and_then(Value::as_object).unwrap_or(&EMPTY)
+}
+
+fn parameters<'a>(spec: &'a Value, operation: &'a Value) -> Vec<&'a Value> {
+    operation
+        .get("parameters")
+        .and_then(Value::as_array)
+        .map(|params| params.iter().map(|param| resolve_ref(spec, param)).collect())
+        .unwrap_or_default()
+}
+
+/// 不含 `null` 的类型集合；未声明类型（组合 schema 或任意值）时为空
+fn schema_types(schema: &Value) -> BTreeSet<&str> {
+    match schema.get("type") {
+        Some(Value::String(ty)) => BTreeSet::from([ty.as_str()]),
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).filter(|ty| *ty != "null").collect(),
+        _ => BTreeSet::new(),
+    }
+}
+
+fn variant_key(spec: &Value, variant: &Value, index: usize) -> String {
+    if let Some(reference) = variant.get("$ref").and_then(Value::as_str) {
+        return reference.rsplit('/').next().unwrap_or(reference).to_string();
+    }
+    let variant = resolve_ref(spec, variant);
+    // 带标签枚举的分支以单值 enum 的标签属性区分
+    let tag = variant.get("properties").and_then(Value::as_object).and_then(|properties| {
+        properties.values().find_map(|property| match property.get("enum").and_then(Value::as_array)?.as_slice() {
+            [value] => Some(enum_value_text(value)),
+            _ => None,
+        })
+    });
+    tag.or_else(|| variant.get("title").and_then(Value::as_str).map(str::to_string))
+        .unwrap_or_else(|| format!("#{}", index))
+}
+
+fn enum_value_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec() -> Value {
+        json!({
+            "openapi": "3.1.0",
+            "paths": {
+                "/v1/products/{id}": {
+                    "get": {
+                        "parameters": [{ "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }],
+                        "responses": { "200": { "description": "", "content": { "application/json": {
+                            "schema": { "$ref": "#/components/schemas/Product" }
+                        } } } }
+                    },
+                    "put": {
+                        "parameters": [{ "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }],
+                        "requestBody": { "required": true, "content": { "application/json": {
+                            "schema": { "$ref": "#/components/schemas/Product" }
+                        } } },
+                        "responses": { "204": { "description": "" } }
+                    }
+                },
+                "/v1/products": { "get": {
+                    "parameters": [{ "name": "status", "in": "query", "required": false, "schema": {
+                        "oneOf": [{ "type": "null" }, { "$ref": "#/components/schemas/ProductStatus" }]
+                    } }],
+                    "responses": { "200": { "description": "", "content": { "application/json": {
+                        "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Product" } }
+                    } } } }
+                } },
+                "/v1/bundles": { "delete": { "responses": { "204": { "description": "" } } } }
+            },
+            "components": { "schemas": {
+                "Product": {
+                    "type": "object",
+                    "required": ["name", "price"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "price": { "type": "number" },
+                        "category": { "oneOf": [{ "type": "null" }, { "$ref": "#/components/schemas/Category" }] }
+                    }
+                },
+                "Category": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "parent": { "oneOf": [{ "type": "null" }, { "$ref": "#/components/schemas/Category" }] }
+                    }
+                },
+                "ProductStatus": { "type": "string", "enum": ["draft", "active", "discontinued"] }
+            } }
+        })
+    }
+
+    fn change(level: ChangeLevel, operation: &str, message: &str) -> SpecChange {
+        SpecChange { level, operation: operation.to_string(), message: message.to_string() }
+    }
+
+    #[test]
+    fn resolver_handles_paths_containers_and_well_known_types() {
+        let resolver = SchemaResolver::new(["Product", "Page<Product>"]);
+        let resolve = |type_name: &str| serde_json::to_value(resolver.resolve(type_name)).unwrap();
+
+        assert_eq!(resolve("crate::dtos::Product"), json!({ "$ref": "#/components/schemas/Product" }));
+        assert_eq!(resolve("Vec<dtos::Page<crate::dtos::Product>>")["items"]["$ref"], "#/components/schemas/Page_Product");
+        assert_eq!(
+            resolve("Option<Product>"),
+            json!({ "oneOf": [{ "type": "null" }, { "$ref": "#/components/schemas/Product" }] })
+        );
+        assert_eq!(resolve("Option<String>"), json!({ "type": ["string", "null"] }));
+        assert_eq!(resolve("HashSet<u32>")["uniqueItems"], true);
+        assert_eq!(resolve("HashSet<u32>")["items"], json!({ "type": "integer", "format": "int32", "minimum": 0 }));
+        assert_eq!(resolve("BTreeMap<String,Vec<Product>>")["additionalProperties"]["items"]["$ref"], "#/components/schemas/Product");
+        assert_eq!(resolve("uuid::Uuid"), json!({ "type": "string", "format": "uuid" }));
+        assert_eq!(resolve("chrono::DateTime<chrono::Utc>"), json!({ "type": "string", "format": "date-time" }));
+        assert_eq!(resolve("chrono::NaiveDate"), json!({ "type": "string", "format": "date" }));
+        assert_eq!(resolve("rust_decimal::Decimal"), json!({ "type": "string", "format": "decimal" }));
+        assert_eq!(resolve("Box<Product>"), resolve("Product"));
+        assert_eq!(resolve("Unknown"), Value::Null);
+        assert_eq!(resolver.dto_name("crate::dtos::Page<Product>"), Some("Page<Product>"));
+    }
+
+    #[test]
+    fn identical_specs_have_no_changes() {
+        let diff = diff_specs(&spec(), &spec());
+
+        assert!(diff.changes.is_empty());
+        assert_eq!(diff.to_string(), "No changes.\n");
+    }
+
+    #[test]
+    fn operations_are_matched_by_path_template() {
+        let old = spec();
+        let mut new = old.clone();
+        let paths = new["paths"].as_object_mut().unwrap();
+        paths.remove("/v1/bundles");
+        let mut item = paths.remove("/v1/products/{id}").unwrap();
+        for operation in item.as_object_mut().unwrap().values_mut() {
+            operation["parameters"][0]["name"] = json!("product_id");
+        }
+        paths.insert("/v1/products/{product_id}".to_string(), item);
+        paths.insert("/v1/health".to_string(), json!({ "get": { "responses": { "200": { "description": "" } } } }));
+
+        let diff = diff_specs(&old, &new);
+
+        assert_eq!(
+            diff.changes,
+            [
+                change(ChangeLevel::Breaking, "DELETE /v1/bundles", "operation was removed"),
+                change(ChangeLevel::NonBreaking, "GET /v1/health", "operation was added"),
+            ]
+        );
+    }
+
+    #[test]
+    fn schema_changes_are_classified_by_direction() {
+        let old = spec();
+        let mut new = old.clone();
+        let product = &mut new["components"]["schemas"]["Product"];
+        product["properties"].as_object_mut().unwrap().remove("price");
+        product["properties"]["sku"] = json!({ "type": "string" });
+        product["required"] = json!(["name", "sku"]);
+        new["components"]["schemas"]["Category"]["properties"]["name"]["type"] = json!("integer");
+        new["components"]["schemas"]["ProductStatus"]["enum"] = json!(["draft", "active"]);
+        let params = new["paths"]["/v1/products"]["get"]["parameters"].as_array_mut().unwrap();
+        params.push(json!({ "name": "tenant", "in": "query", "required": true, "schema": { "type": "string" } }));
+
+        let diff = diff_specs(&old, &new);
+        let get = "GET /v1/products/{id}";
+        let put = "PUT /v1/products/{id}";
+        let list = "GET /v1/products";
+        let expected = [
+            change(ChangeLevel::Breaking, get, "response `200` field `price` was removed"),
+            change(ChangeLevel::NonBreaking, get, "response `200` required field `sku` was added"),
+            change(ChangeLevel::Breaking, get, "response `200` field `category.name` type changed from `string` to `integer`"),
+            change(ChangeLevel::NonBreaking, put, "request body field `price` was removed"),
+            change(ChangeLevel::Breaking, put, "request body required field `sku` was added"),
+            change(ChangeLevel::Breaking, list, "query parameter `status` enum values removed: `discontinued`"),
+            change(ChangeLevel::Breaking, list, "required query parameter `tenant` was added"),
+            change(ChangeLevel::Breaking, list, "response `200` field `[].price` was removed"),
+        ];
+        for change in &expected {
+            assert!(diff.changes.contains(change), "missing {:?} in\n{}", change, diff);
+        }
+        assert!(diff.has_breaking_changes());
+        assert!(diff.to_string().starts_with("Breaking changes ("));
+
+        // 反向比较时请求中新增枚举值是兼容的
+        let reverse = diff_specs(&new, &old);
+        let added = change(ChangeLevel::NonBreaking, list, "query parameter `status` enum values added: `discontinued`");
+        assert!(reverse.changes.contains(&added), "{}", reverse);
+    }
+
+    #[test]
+    fn nullable_forms_of_3_0_and_3_1_are_equivalent() {
+        let old = spec();
+        let mut new = old.clone();
+        new["components"]["schemas"]["Product"]["properties"]["category"] =
+            json!({ "allOf": [{ "$ref": "#/components/schemas/Category" }], "nullable": true });
+        assert!(diff_specs(&old, &new).changes.is_empty());
+
+        new["components"]["schemas"]["Product"]["properties"]["category"] = json!({ "$ref": "#/components/schemas/Category" });
+        let diff = diff_specs(&old, &new);
+        let narrowed = change(ChangeLevel::NonBreaking, "GET /v1/products/{id}", "response `200` field `category` is no longer nullable");
+        assert!(diff.changes.contains(&narrowed), "{}", diff);
+        let rejected = change(ChangeLevel::Breaking, "PUT /v1/products/{id}", "request body field `category` is no longer nullable");
+        assert!(diff.changes.contains(&rejected), "{}", diff);
+    }
+}
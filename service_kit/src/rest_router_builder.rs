@@ -1,18 +1,29 @@
+use crate::auth::{self, Authenticator, SharedAuthenticator};
 use crate::error::{Error, Result};
-use crate::handler::ApiHandlerInventory;
+use crate::handler::{collect_api_handlers, read_body, ApiState, RawBody, COOKIES_KEY, DEFAULT_BODY_LIMIT, HEADERS_KEY};
 use axum::{
     body::Body,
-    extract::{FromRequestParts, Path},
+    extract::{DefaultBodyLimit, FromRequestParts, Path},
     response::{IntoResponse, Response},
     routing::{on, MethodFilter},
     Router,
 };
-use axum::http::Request;
+use axum::http::{HeaderMap, Request};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Arc;
+use utoipa::openapi::path::ParameterIn;
 use utoipa::openapi::{OpenApi, PathItem};
 
-async fn extract_and_merge_params(req: Request<Body>) -> std::result::Result<Value, Response> {
+/// 合并 path/query/请求头/JSON 请求体为一个参数对象。
+/// `raw_body` 为真时（urlencoded 表单、multipart、二进制、纯文本请求体）不解析请求体，原样交给处理函数。
+/// 请求体最多读取 `body_limit` 字节，超出时返回 413。
+async fn extract_and_merge_params(
+    req: Request<Body>,
+    raw_body: bool,
+    body_limit: usize,
+    query_types: &HashMap<String, &'static str>,
+) -> std::result::Result<(Value, RawBody), Response> {
     let (mut parts, body) = req.into_parts();
 
     let path_params: HashMap<String, String> =
@@ -27,48 +38,137 @@ async fn extract_and_merge_params(req: Request<Body>) -> std::result::Result<Val
         if let Ok(pairs) = serde_urlencoded::from_str::<Vec<(String, String)>>(query_str) {
             if let Some(merged) = merged_params.as_object_mut() {
                 for (k, v) in pairs {
-                    if let Ok(n) = v.parse::<f64>() {
-                        merged.insert(k, serde_json::json!(n));
-                    } else if v.eq_ignore_ascii_case("true") || v.eq_ignore_ascii_case("false") {
-                        merged.insert(k, serde_json::json!(v.eq_ignore_ascii_case("true")));
-                    } else {
-                        merged.insert(k, Value::String(v));
-                    }
+                    let value = coerce_query_value(v, query_types.get(&k).copied());
+                    merged.insert(k, value);
                 }
             }
         }
     }
 
     let headers = parts.headers.clone();
-    if let Some(content_type) = headers.get(axum::http::header::CONTENT_TYPE) {
-        if content_type.to_str().unwrap_or("").contains("application/json") {
-            let body_bytes = match axum::body::to_bytes(body, usize::MAX).await {
-                Ok(bytes) => bytes,
-                Err(e) => {
-                    return Err((
-                        axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                        format!("Failed to read request body: {}", e),
-                    )
-                        .into_response())
-                }
-            };
-
-            if let Ok(body_json) = serde_json::from_slice::<Value>(&body_bytes) {
-                if let (Some(merged), Some(body_obj)) = (merged_params.as_object_mut(), body_json.as_object()) {
-                    for (k, v) in body_obj {
-                        merged.insert(k.clone(), v.clone());
-                    }
+    if raw_body {
+        insert_request_meta(&mut merged_params, &headers);
+        return Ok((merged_params, RawBody::from_request(Request::from_parts(parts, body), body_limit)));
+    }
+    let is_json = headers
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.contains("application/json"));
+    if is_json {
+        let body_bytes = match read_body(body, body_limit).await {
+            Ok(bytes) => bytes,
+            Err(e) => return Err(e.into_response()),
+        };
+        if let Ok(body_json) = serde_json::from_slice::<Value>(&body_bytes) {
+            if let (Some(merged), Some(body_obj)) = (merged_params.as_object_mut(), body_json.as_object()) {
+                for (k, v) in body_obj {
+                    merged.insert(k.clone(), v.clone());
                 }
             }
         }
     }
 
-    Ok(merged_params)
+    insert_request_meta(&mut merged_params, &headers);
+    Ok((merged_params, RawBody::default()))
+}
+
+/// 最后写入原始请求头/Cookie，覆盖 query 或请求体中同名的保留键，避免客户端伪造请求头
+fn insert_request_meta(params: &mut Value, headers: &HeaderMap) {
+    if let Some(merged) = params.as_object_mut() {
+        let (raw_headers, cookies) = headers_to_params(headers);
+        merged.insert(HEADERS_KEY.to_string(), raw_headers);
+        merged.insert(COOKIES_KEY.to_string(), cookies);
+    }
+}
+
+/// 操作声明的 query 参数中，类型为整数、数字或布尔值的参数名及其 JSON Schema 类型
+fn query_param_types(operation: &utoipa::openapi::path::Operation) -> HashMap<String, &'static str> {
+    operation
+        .parameters
+        .iter()
+        .flatten()
+        .filter(|param| matches!(param.parameter_in, ParameterIn::Query))
+        .filter_map(|param| {
+            let schema = serde_json::to_value(param.schema.as_ref()?).ok()?;
+            Some((param.name.clone(), scalar_type(&schema)?))
+        })
+        .collect()
+}
+
+/// schema 的标量类型；`Option<T>` 生成的 `["integer", "null"]` 与 `oneOf` 同样识别
+fn scalar_type(schema: &Value) -> Option<&'static str> {
+    let types: Vec<&str> = match schema.get("type") {
+        Some(Value::String(ty)) => vec![ty.as_str()],
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+        _ => {
+            let branches = schema.get("oneOf").or_else(|| schema.get("anyOf"))?.as_array()?;
+            return branches.iter().find_map(scalar_type);
+        }
+    };
+    ["integer", "number", "boolean"].into_iter().find(|ty| types.contains(ty))
+}
+
+/// 按声明的类型还原 query 中的字符串值；未声明为数字或布尔值的参数保持为字符串
+/// （例如 `zip=01234` 对 `String` 字段仍是 `"01234"`）。无法按声明类型解析的值也保持原样，
+/// 由反序列化报告 400。
+fn coerce_query_value(v: String, declared: Option<&str>) -> Value {
+    let coerced = match declared {
+        // 整数保持为整数，否则 `page=0` 会变成 `0.0`，无法反序列化为 `u32`
+        Some("integer") => v.parse::<i64>().map(Value::from).or_else(|_| v.parse::<u64>().map(Value::from)).ok(),
+        Some("number") => v.parse::<f64>().ok().and_then(serde_json::Number::from_f64).map(Value::Number),
+        Some("boolean") => match v.as_str() {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        _ => None,
+    };
+    coerced.unwrap_or(Value::String(v))
+}
+
+/// 请求体不是 JSON 时交由处理函数读取原始请求体；
+/// urlencoded 表单也由处理函数按 `Form<T>` 的字段类型解析，值不会被猜测为数字或布尔值
+fn takes_raw_body(operation: &utoipa::openapi::path::Operation) -> bool {
+    operation.request_body.as_ref().is_some_and(|body| {
+        !body.content.is_empty() && !body.content.keys().any(|ct| ct == "application/json")
+    })
+}
+
+/// 将请求头转为 JSON（多值请求头为数组），并解析 `Cookie` 请求头
+fn headers_to_params(headers: &HeaderMap) -> (Value, Value) {
+    let mut raw_headers = serde_json::Map::new();
+    for name in headers.keys() {
+        let values: Vec<Value> = headers
+            .get_all(name)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .map(|v| Value::String(v.to_string()))
+            .collect();
+        let value = match values.len() {
+            0 => continue,
+            1 => values.into_iter().next().unwrap_or(Value::Null),
+            _ => Value::Array(values),
+        };
+        raw_headers.insert(name.as_str().to_string(), value);
+    }
+
+    let mut cookies = serde_json::Map::new();
+    for cookie_header in headers.get_all(axum::http::header::COOKIE) {
+        for pair in cookie_header.to_str().unwrap_or("").split(';') {
+            if let Some((name, value)) = pair.trim().split_once('=') {
+                cookies.insert(name.trim().to_string(), Value::String(value.trim().to_string()));
+            }
+        }
+    }
+    (Value::Object(raw_headers), Value::Object(cookies))
 }
 
 #[derive(Default, Clone)]
 pub struct RestRouterBuilder {
     openapi: Option<OpenApi>,
+    state: ApiState,
+    authenticator: Option<SharedAuthenticator>,
+    body_limit: Option<usize>,
 }
 
 impl RestRouterBuilder {
@@ -81,14 +181,30 @@ impl RestRouterBuilder {
         self
     }
 
+    /// 注入应用状态，`#[api]` 处理函数可通过 `State<T>` 取得
+    pub fn with_state<S: Clone + Send + Sync + 'static>(mut self, state: S) -> Self {
+        self.state = ApiState::new(state);
+        self
+    }
+
+    /// 注册认证器，校验声明了 `security` 的操作；缺少或无效的凭证返回 401
+    pub fn with_authenticator<A: Authenticator>(mut self, authenticator: A) -> Self {
+        self.authenticator = Some(Arc::new(authenticator));
+        self
+    }
+
+    /// 请求体的大小上限（字节），默认 [`DEFAULT_BODY_LIMIT`]；超出时返回 413
+    pub fn body_limit(mut self, limit: usize) -> Self {
+        self.body_limit = Some(limit);
+        self
+    }
+
     pub fn build(self) -> Result<Router> {
         let openapi = self.openapi.ok_or_else(|| {
             Error::SpecError("OpenAPI document not provided".to_string())
         })?;
-        let mut handler_map: std::collections::HashMap<&'static str, fn(&serde_json::Value) -> crate::handler::DynHandlerFuture> = std::collections::HashMap::new();
-        for inv in inventory::iter::<ApiHandlerInventory> {
-            handler_map.insert(inv.operation_id, inv.handler);
-        }
+        let handler_map = collect_api_handlers()?;
+        let body_limit = self.body_limit.unwrap_or(DEFAULT_BODY_LIMIT);
 
         let mut router = Router::new();
 
@@ -97,9 +213,19 @@ impl RestRouterBuilder {
                 if let Some(op_id) = operation.operation_id.as_deref() {
                     if let Some(handler_fn) = handler_map.get(op_id) {
                         let handler_fn = *handler_fn;
+                        let state = self.state.clone();
+                        let raw_body = takes_raw_body(operation);
+                        let query_types = query_param_types(operation);
+                        let schemes = auth::required_schemes(&openapi, operation);
+                        auth::ensure_authenticator(self.authenticator.as_ref(), op_id, &schemes)?;
+                        let authenticator = self.authenticator.clone();
+                        let op_id = op_id.to_string();
                         let route_handler = move |req: Request<Body>| async move {
-                            match extract_and_merge_params(req).await {
-                                Ok(params) => match handler_fn(&params).await {
+                            if let Err(e) = auth::authorize(authenticator.as_deref(), &op_id, &schemes, req.headers()).await {
+                                return e.into_response();
+                            }
+                            match extract_and_merge_params(req, raw_body, body_limit, &query_types).await {
+                                Ok((params, body)) => match handler_fn(&params, &state, body).await {
                                     Ok(resp) => resp,
                                     Err(e) => e.into_response(),
                                 },
@@ -113,7 +239,8 @@ impl RestRouterBuilder {
                 }
             }
         }
-        Ok(router)
+        // `Multipart` 等 axum 提取器同样遵循该上限
+        Ok(router.layer(DefaultBodyLimit::max(body_limit)))
     }
 }
 
@@ -0,0 +1,312 @@
+//! 导出 OpenAPI 文档，供提交到仓库与评审 diff 使用。
+//!
+//! 服务的 `main` 在构建好文档后调用 [`export_spec_if_requested`]：设置了 [`EXPORT_SPEC_ENV`]
+//! 时写出文档并返回 `true`，服务随即退出而不监听端口。`cargo forge export-spec` 正是以这种
+//! 方式运行服务二进制，再用 [`render_spec`] 转换为所需的格式与 OpenAPI 版本。
+//!
+//! ```ignore
+//! let openapi = build_openapi_spec();
+//! if service_kit::spec_export::export_spec_if_requested(&openapi) {
+//!     return;
+//! }
+//! ```
+//!
+//! 输出的所有对象键按字母序排列，同一份文档总是得到相同的文本。
+
+use crate::error::{Error, Result};
+use serde_json::{Map, Value};
+use utoipa::openapi::OpenApi;
+
+/// 值为输出路径（`-` 表示标准输出）时，服务以导出模式运行
+pub const EXPORT_SPEC_ENV: &str = "SERVICE_KIT_EXPORT_SPEC";
+
+/// 导出模式下同时写出已注册处理函数的 operation_id（JSON 数组），供 `cargo forge spec lint` 使用
+pub const EXPORT_HANDLERS_ENV: &str = "SERVICE_KIT_EXPORT_HANDLERS";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecFormat {
+    Json,
+    Yaml,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenApiVersion {
+    /// OpenAPI 3.0.3：可空类型写作 `nullable: true`，兼容只支持 3.0 的工具
+    V3_0,
+    /// OpenAPI 3.1.0：utoipa 原生输出的版本
+    V3_1,
+}
+
+/// 设置了 [`EXPORT_SPEC_ENV`] 时，以 OpenAPI 3.1 JSON 写出 `openapi` 并返回 `true`；写入失败时以状态码 1 退出
+pub fn export_spec_if_requested(openapi: &OpenApi) -> bool {
+    let Ok(target) = std::env::var(EXPORT_SPEC_ENV) else {
+        return false;
+    };
+    let result = serde_json::to_value(openapi)
+        .map_err(Error::from)
+        .and_then(|spec| render_spec(&spec, SpecFormat::Json, OpenApiVersion::V3_1))
+        .and_then(|text| {
+            if target == "-" {
+                print!("{}", text);
+                Ok(())
+            } else {
+                std::fs::write(&target, text).map_err(Error::from)
+            }
+        })
+        .and_then(|()| {
+            let Ok(handlers_path) = std::env::var(EXPORT_HANDLERS_ENV) else {
+                return Ok(());
+            };
+            let mut operation_ids: Vec<String> = crate::spec_lint::registered_operation_ids().into_iter().collect();
+            operation_ids.sort();
+            std::fs::write(handlers_path, serde_json::to_string(&operation_ids)?).map_err(Error::from)
+        });
+    if let Err(e) = result {
+        eprintln!("Failed to export the OpenAPI spec to {}: {}", target, e);
+        std::process::exit(1);
+    }
+    true
+}
+
+/// 按目标版本转换 `spec`，对象键排序后输出为 JSON 或 YAML
+pub fn render_spec(spec: &Value, format: SpecFormat, version: OpenApiVersion) -> Result<String> {
+    let mut spec = spec.clone();
+    match version {
+        OpenApiVersion::V3_0 => downgrade_to_3_0(&mut spec),
+        OpenApiVersion::V3_1 => {
+            if let Some(root) = spec.as_object_mut() {
+                root.insert("openapi".to_string(), Value::from("3.1.0"));
+            }
+        }
+    }
+    let spec = sorted(&spec);
+    match format {
+        SpecFormat::Json => Ok(format!("{}\n", serde_json::to_string_pretty(&spec)?)),
+        SpecFormat::Yaml => to_yaml(&spec),
+    }
+}
+
+#[cfg(feature = "yaml")]
+fn to_yaml(spec: &Value) -> Result<String> {
+    serde_norway::to_string(spec).map_err(|e| Error::SpecError(format!("Failed to serialize YAML: {}", e)))
+}
+
+#[cfg(not(feature = "yaml"))]
+fn to_yaml(_spec: &Value) -> Result<String> {
+    Err(Error::SpecError("YAML output requires the `yaml` feature".to_string()))
+}
+
+/// 递归地按键排序；数组保持原有顺序（参数、`required`、`enum` 的顺序有意义）
+pub fn sorted(value: &Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<(&String, &Value)> = object.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            Value::Object(entries.into_iter().map(|(key, value)| (key.clone(), sorted(value))).collect())
+        }
+        Value::Array(values) => Value::Array(values.iter().map(sorted).collect()),
+        value => value.clone(),
+    }
+}
+
+/// 将 utoipa 生成的 3.1 文档改写为 3.0.3：类型数组与 `null` 分支改为 `nullable`，
+/// `const` 改为单值 `enum`，数组形式的 `examples` 改为 `example`，数值形式的
+/// `exclusiveMinimum`/`exclusiveMaximum` 改为布尔形式
+pub fn downgrade_to_3_0(spec: &mut Value) {
+    if let Some(root) = spec.as_object_mut() {
+        root.insert("openapi".to_string(), Value::from("3.0.3"));
+    }
+    downgrade(spec);
+}
+
+/// 以名称为键的映射：其值是 schema 等对象，映射本身不是 schema
+const NAME_MAPS: &[&str] = &[
+    "properties",
+    "patternProperties",
+    "schemas",
+    "paths",
+    "responses",
+    "content",
+    "headers",
+    "securitySchemes",
+    "requestBodies",
+    "parameters",
+    "callbacks",
+    "links",
+    "encoding",
+    "$defs",
+];
+
+/// 其值是示例数据或字面量，不再向下改写
+const LITERALS: &[&str] = &["example", "examples", "default", "enum", "const"];
+
+fn downgrade(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            downgrade_schema(object);
+            for (key, child) in object.iter_mut() {
+                if LITERALS.contains(&key.as_str()) || key.starts_with("x-") {
+                    continue;
+                }
+                match child {
+                    Value::Object(map) if NAME_MAPS.contains(&key.as_str()) => map.values_mut().for_each(downgrade),
+                    child => downgrade(child),
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(downgrade),
+        _ => {}
+    }
+}
+
+fn downgrade_schema(schema: &mut Map<String, Value>) {
+    let is_null = |value: &Value| value.get("type").and_then(Value::as_str) == Some("null");
+
+    if let Some(Value::Array(types)) = schema.get("type").cloned() {
+        let nullable = types.iter().any(|ty| ty == "null");
+        let types: Vec<Value> = types.into_iter().filter(|ty| ty != "null").collect();
+        match types.as_slice() {
+            [single] => {
+                schema.insert("type".to_string(), single.clone());
+            }
+            _ => {
+                schema.remove("type");
+                if !types.is_empty() {
+                    let branches = types.into_iter().map(|ty| Value::Object(Map::from_iter([("type".to_string(), ty)])));
+                    schema.insert("anyOf".to_string(), Value::Array(branches.collect()));
+                }
+            }
+        }
+        if nullable {
+            schema.insert("nullable".to_string(), Value::Bool(true));
+        }
+    }
+
+    for combinator in ["oneOf", "anyOf"] {
+        let Some(Value::Array(branches)) = schema.get(combinator) else { continue };
+        if !branches.iter().any(is_null) {
+            continue;
+        }
+        let mut branches: Vec<Value> = branches.iter().filter(|branch| !is_null(branch)).cloned().collect();
+        schema.remove(combinator);
+        if branches.len() == 1 {
+            match branches.remove(0) {
+                // 3.0 中与 `$ref` 同级的关键字会被忽略，改用 `allOf` 包裹
+                branch if branch.get("$ref").is_some() => {
+                    schema.insert("allOf".to_string(), Value::Array(vec![branch]));
+                }
+                Value::Object(branch) => {
+                    for (key, value) in branch {
+                        schema.entry(key).or_insert(value);
+                    }
+                }
+                branch => {
+                    schema.insert(combinator.to_string(), Value::Array(vec![branch]));
+                }
+            }
+        } else {
+            schema.insert(combinator.to_string(), Value::Array(branches));
+        }
+        schema.insert("nullable".to_string(), Value::Bool(true));
+    }
+
+    if let Some(value) = schema.remove("const") {
+        schema.insert("enum".to_string(), Value::Array(vec![value]));
+    }
+    if let Some(Value::Array(mut examples)) = schema.get("examples").cloned() {
+        schema.remove("examples");
+        if !examples.is_empty() && !schema.contains_key("example") {
+            schema.insert("example".to_string(), examples.remove(0));
+        }
+    }
+    for (exclusive, bound) in [("exclusiveMinimum", "minimum"), ("exclusiveMaximum", "maximum")] {
+        if let Some(limit) = schema.get(exclusive).filter(|limit| limit.is_number()).cloned() {
+            schema.insert(bound.to_string(), limit);
+            schema.insert(exclusive.to_string(), Value::Bool(true));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn nullable_types_and_branches_become_nullable() {
+        let mut spec = json!({
+            "openapi": "3.1.0",
+            "components": { "schemas": { "Category": { "type": "object", "properties": {
+                "name": { "type": ["string", "null"] },
+                "code": { "type": ["string", "integer"] },
+                "parent": { "oneOf": [{ "type": "null" }, { "$ref": "#/components/schemas/Category" }] },
+                "size": { "oneOf": [{ "type": "null" }, { "type": "integer", "minimum": 0 }] }
+            } } } }
+        });
+        downgrade_to_3_0(&mut spec);
+
+        assert_eq!(spec["openapi"], "3.0.3");
+        let properties = &spec["components"]["schemas"]["Category"]["properties"];
+        assert_eq!(properties["name"], json!({ "type": "string", "nullable": true }));
+        assert_eq!(properties["code"], json!({ "anyOf": [{ "type": "string" }, { "type": "integer" }] }));
+        assert_eq!(
+            properties["parent"],
+            json!({ "allOf": [{ "$ref": "#/components/schemas/Category" }], "nullable": true })
+        );
+        assert_eq!(properties["size"], json!({ "type": "integer", "minimum": 0, "nullable": true }));
+    }
+
+    #[test]
+    fn keywords_without_a_3_0_form_are_rewritten() {
+        let mut spec = json!({ "components": { "schemas": { "Tick": { "type": "object", "properties": {
+            "kind": { "const": "tick" },
+            "price": { "type": "number", "exclusiveMinimum": 0, "examples": [9.5, 10] },
+            // 字面量与扩展字段保持原样
+            "meta": { "type": "object", "default": { "type": ["string", "null"] }, "x-raw": { "const": 1 } }
+        } } } } });
+        downgrade_to_3_0(&mut spec);
+
+        let properties = &spec["components"]["schemas"]["Tick"]["properties"];
+        assert_eq!(properties["kind"], json!({ "enum": ["tick"] }));
+        assert_eq!(
+            properties["price"],
+            json!({ "type": "number", "minimum": 0, "exclusiveMinimum": true, "example": 9.5 })
+        );
+        assert_eq!(properties["meta"]["default"], json!({ "type": ["string", "null"] }));
+        assert_eq!(properties["meta"]["x-raw"], json!({ "const": 1 }));
+    }
+
+    #[test]
+    fn properties_named_like_keywords_are_not_rewritten() {
+        let mut spec = json!({ "type": "object", "properties": { "const": { "type": "string" } } });
+        downgrade_to_3_0(&mut spec);
+
+        assert_eq!(spec["properties"]["const"], json!({ "type": "string" }));
+    }
+
+    #[test]
+    fn rendered_output_is_sorted_and_stable() {
+        let spec = json!({ "paths": {}, "openapi": "3.1.0", "info": { "version": "1.0.0", "title": "Demo" }, "tags": [{ "name": "b" }, { "name": "a" }] });
+
+        let json = render_spec(&spec, SpecFormat::Json, OpenApiVersion::V3_1).unwrap();
+        assert_eq!(json, render_spec(&spec, SpecFormat::Json, OpenApiVersion::V3_1).unwrap());
+        let keys: Vec<&str> = json.lines().filter(|line| line.starts_with("  \"")).map(|line| line.trim().split('"').nth(1).unwrap()).collect();
+        assert_eq!(keys, ["info", "openapi", "paths", "tags"]);
+        let rendered: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(rendered["info"].as_object().unwrap().keys().collect::<Vec<_>>(), ["title", "version"]);
+        assert_eq!(rendered["tags"], json!([{ "name": "b" }, { "name": "a" }]));
+
+        let downgraded: Value = serde_json::from_str(&render_spec(&spec, SpecFormat::Json, OpenApiVersion::V3_0).unwrap()).unwrap();
+        assert_eq!(downgraded["openapi"], "3.0.3");
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn yaml_output_is_sorted() {
+        let spec = json!({ "paths": {}, "openapi": "3.0.0", "info": { "title": "Demo", "version": "1.0.0" } });
+        let yaml = render_spec(&spec, SpecFormat::Yaml, OpenApiVersion::V3_1).unwrap();
+
+        assert!(yaml.starts_with("info:\n"));
+        assert!(yaml.contains("openapi: 3.1.0\n"));
+    }
+}
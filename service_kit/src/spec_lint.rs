@@ -0,0 +1,451 @@
+//! 检查 `build_openapi_basic` 等生成的 OpenAPI 文档（`cargo forge spec lint`）。
+//!
+//! 每条规则都有默认级别，可通过 [`LintConfig`] 调整或关闭；单个操作可用 `x-lint-allow`
+//! 扩展豁免指定规则：
+//!
+//! ```ignore
+//! #[api(GET, "/v1/legacy_report", extensions(x_lint_allow = ["non-kebab-case-path"]))]
+//! ```
+//!
+//! 服务内部可直接调用 [`lint_openapi`]，它同时检查每个操作是否有 `#[api]` 注册的处理函数。
+
+use crate::error::{Error, Result};
+use crate::handler::ApiHandlerInventory;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use utoipa::openapi::OpenApi;
+
+/// 操作上列出豁免规则名称的扩展
+pub const ALLOW_EXTENSION: &str = "x-lint-allow";
+
+/// 问题的严重程度；存在 `Error` 级别的问题时 `cargo forge spec lint` 以非零状态退出
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintRule {
+    /// 操作缺少 `summary`（处理函数 doc 注释的第一行）
+    MissingSummary,
+    /// 操作缺少 `description`，或 DTO schema 缺少描述
+    MissingDescription,
+    /// 参数没有描述
+    EmptyParameterDescription,
+    /// 操作没有声明任何 4xx/5xx 或 `default` 响应
+    MissingErrorResponse,
+    /// 路径的固定段不是 kebab-case
+    NonKebabCasePath,
+    /// 路径中的 `{占位符}` 与声明的 path 参数不一致
+    PathParameterMismatch,
+    /// 操作没有 `#[api]` 注册的处理函数（`ApiHandlerInventory`）
+    UnregisteredHandler,
+}
+
+impl LintRule {
+    pub const ALL: [LintRule; 7] = [
+        LintRule::MissingSummary,
+        LintRule::MissingDescription,
+        LintRule::EmptyParameterDescription,
+        LintRule::MissingErrorResponse,
+        LintRule::NonKebabCasePath,
+        LintRule::PathParameterMismatch,
+        LintRule::UnregisteredHandler,
+    ];
+
+    /// 规则名称，用于配置、`x-lint-allow` 与报告
+    pub fn name(self) -> &'static str {
+        match self {
+            LintRule::MissingSummary => "missing-summary",
+            LintRule::MissingDescription => "missing-description",
+            LintRule::EmptyParameterDescription => "empty-parameter-description",
+            LintRule::MissingErrorResponse => "missing-error-response",
+            LintRule::NonKebabCasePath => "non-kebab-case-path",
+            LintRule::PathParameterMismatch => "path-parameter-mismatch",
+            LintRule::UnregisteredHandler => "unregistered-handler",
+        }
+    }
+
+    pub fn default_severity(self) -> Severity {
+        match self {
+            LintRule::MissingDescription => Severity::Info,
+            LintRule::PathParameterMismatch | LintRule::UnregisteredHandler => Severity::Error,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+impl std::str::FromStr for LintRule {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        LintRule::ALL.into_iter().find(|rule| rule.name() == name).ok_or_else(|| {
+            let names: Vec<&str> = LintRule::ALL.iter().map(|rule| rule.name()).collect();
+            Error::SpecError(format!("Unknown lint rule `{}`, expected one of: {}", name, names.join(", ")))
+        })
+    }
+}
+
+impl Serialize for LintRule {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+/// 每条规则的级别；`None` 表示关闭该规则
+#[derive(Debug, Clone)]
+pub struct LintConfig {
+    levels: HashMap<LintRule, Option<Severity>>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self { levels: LintRule::ALL.into_iter().map(|rule| (rule, Some(rule.default_severity()))).collect() }
+    }
+}
+
+impl LintConfig {
+    pub fn level(&self, rule: LintRule) -> Option<Severity> {
+        self.levels.get(&rule).copied().flatten()
+    }
+
+    pub fn set_level(mut self, rule: LintRule, level: Option<Severity>) -> Self {
+        self.levels.insert(rule, level);
+        self
+    }
+
+    pub fn allow(self, rule: LintRule) -> Self {
+        self.set_level(rule, None)
+    }
+
+    /// 应用 `<rule>=<off|info|warning|error>` 形式的设置
+    pub fn apply(self, setting: &str) -> Result<Self> {
+        let (rule, level) = setting
+            .split_once('=')
+            .ok_or_else(|| Error::SpecError(format!("Expected `<rule>=<level>`, got `{}`", setting)))?;
+        let level = match level {
+            "off" | "allow" => None,
+            "info" => Some(Severity::Info),
+            "warning" | "warn" => Some(Severity::Warning),
+            "error" | "deny" => Some(Severity::Error),
+            _ => {
+                return Err(Error::SpecError(format!(
+                    "Unknown lint level `{}`, expected one of: off, info, warning, error",
+                    level
+                )))
+            }
+        };
+        Ok(self.set_level(rule.parse()?, level))
+    }
+}
+
+/// 一处问题；`location` 为操作（`GET /v1/products`）、路径或 `schema Product`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LintFinding {
+    pub rule: LintRule,
+    pub severity: Severity,
+    pub location: String,
+    pub message: String,
+}
+
+/// [`lint_spec`] 的结果；`Display` 输出按严重程度排序的可读报告
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LintReport {
+    pub findings: Vec<LintFinding>,
+}
+
+impl LintReport {
+    pub fn has_errors(&self) -> bool {
+        self.findings.iter().any(|finding| finding.severity == Severity::Error)
+    }
+
+    pub fn count(&self, severity: Severity) -> usize {
+        self.findings.iter().filter(|finding| finding.severity == severity).count()
+    }
+}
+
+impl std::fmt::Display for LintReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut findings: Vec<&LintFinding> = self.findings.iter().collect();
+        findings.sort_by_key(|finding| std::cmp::Reverse(finding.severity));
+        for finding in findings {
+            let severity = match finding.severity {
+                Severity::Info => "info",
+                Severity::Warning => "warning",
+                Severity::Error => "error",
+            };
+            writeln!(f, "{}[{}] {}: {}", severity, finding.rule.name(), finding.location, finding.message)?;
+        }
+        writeln!(
+            f,
+            "{} error(s), {} warning(s), {} info",
+            self.count(Severity::Error),
+            self.count(Severity::Warning),
+            self.count(Severity::Info)
+        )
+    }
+}
+
+/// 在服务进程内检查文档；`unregistered-handler` 对照本进程中 `#[api]` 注册的处理函数
+pub fn lint_openapi(openapi: &OpenApi, config: &LintConfig) -> Result<LintReport> {
+    let spec = serde_json::to_value(openapi)?;
+    Ok(lint_spec(&spec, Some(&registered_operation_ids()), config))
+}
+
+/// 本进程中 `#[api]` 注册的 operation_id
+pub fn registered_operation_ids() -> HashSet<String> {
+    inventory::iter::<ApiHandlerInventory>.into_iter().map(|inv| inv.operation_id.to_string()).collect()
+}
+
+const HTTP_METHODS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+/// 检查 OpenAPI JSON 文档；`handlers` 为 `None` 时（如只有规范文件）跳过 `unregistered-handler`
+pub fn lint_spec(spec: &Value, handlers: Option<&HashSet<String>>, config: &LintConfig) -> LintReport {
+    let mut linter = Linter { config, findings: Vec::new() };
+    let empty = serde_json::Map::new();
+    let paths = spec.get("paths").and_then(Value::as_object).unwrap_or(&empty);
+
+    for (path, item) in paths {
+        let operations: Vec<(&str, &Value)> =
+            HTTP_METHODS.iter().filter_map(|method| Some((*method, item.get(*method)?))).collect();
+        // 路径级规则只有在所有操作都豁免时才跳过
+        let path_allows = |rule: LintRule| !operations.is_empty() && operations.iter().all(|(_, op)| allows(op, rule));
+
+        if let Some(segment) = path.split('/').find(|segment| !segment.starts_with('{') && !is_kebab_case(segment)) {
+            if !path_allows(LintRule::NonKebabCasePath) {
+                linter.report(LintRule::NonKebabCasePath, path, format!("segment `{}` is not kebab-case", segment));
+            }
+        }
+
+        for (method, operation) in operations {
+            let location = format!("{} {}", method.to_uppercase(), path);
+            let allowed: HashSet<LintRule> = LintRule::ALL.into_iter().filter(|rule| allows(operation, *rule)).collect();
+            let mut report = |rule: LintRule, message: String| {
+                if !allowed.contains(&rule) {
+                    linter.report(rule, &location, message);
+                }
+            };
+
+            if is_blank(operation.get("summary")) {
+                report(LintRule::MissingSummary, "operation has no summary".to_string());
+            }
+            if is_blank(operation.get("description")) {
+                report(LintRule::MissingDescription, "operation has no description".to_string());
+            }
+
+            let parameters: Vec<&Value> = [item.get("parameters"), operation.get("parameters")]
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_array)
+                .flatten()
+                .collect();
+            for parameter in &parameters {
+                if is_blank(parameter.get("description")) {
+                    let name = parameter.get("name").and_then(Value::as_str).unwrap_or_default();
+                    let location_in = parameter.get("in").and_then(Value::as_str).unwrap_or_default();
+                    report(
+                        LintRule::EmptyParameterDescription,
+                        format!("{} parameter `{}` has no description", location_in, name),
+                    );
+                }
+            }
+
+            let placeholders: BTreeSet<&str> = placeholders(path).collect();
+            let declared: BTreeSet<&str> = parameters
+                .iter()
+                .filter(|parameter| parameter.get("in").and_then(Value::as_str) == Some("path"))
+                .filter_map(|parameter| parameter.get("name").and_then(Value::as_str))
+                .collect();
+            for name in placeholders.difference(&declared) {
+                report(LintRule::PathParameterMismatch, format!("placeholder `{{{}}}` has no declared path parameter", name));
+            }
+            for name in declared.difference(&placeholders) {
+                report(LintRule::PathParameterMismatch, format!("path parameter `{}` does not appear in the path", name));
+            }
+
+            let has_error_response = operation
+                .get("responses")
+                .and_then(Value::as_object)
+                .is_some_and(|responses| responses.keys().any(|status| status == "default" || status.starts_with(['4', '5'])));
+            if !has_error_response {
+                report(LintRule::MissingErrorResponse, "operation declares no 4xx/5xx or default response".to_string());
+            }
+
+            if let Some(handlers) = handlers {
+                match operation.get("operationId").and_then(Value::as_str) {
+                    Some(operation_id) if handlers.contains(operation_id) => {}
+                    Some(operation_id) => report(
+                        LintRule::UnregisteredHandler,
+                        format!("operation `{}` has no handler registered with #[api]", operation_id),
+                    ),
+                    None => report(LintRule::UnregisteredHandler, "operation has no operationId".to_string()),
+                }
+            }
+        }
+    }
+
+    let schemas = spec.pointer("/components/schemas").and_then(Value::as_object).unwrap_or(&empty);
+    for (name, schema) in schemas {
+        if is_blank(schema.get("description")) && !allows(schema, LintRule::MissingDescription) {
+            linter.report(LintRule::MissingDescription, &format!("schema {}", name), "schema has no description".to_string());
+        }
+    }
+    LintReport { findings: linter.findings }
+}
+
+struct Linter<'a> {
+    config: &'a LintConfig,
+    findings: Vec<LintFinding>,
+}
+
+impl Linter<'_> {
+    fn report(&mut self, rule: LintRule, location: &str, message: String) {
+        if let Some(severity) = self.config.level(rule) {
+            self.findings.push(LintFinding { rule, severity, location: location.to_string(), message });
+        }
+    }
+}
+
+/// 对象是否通过 `x-lint-allow` 豁免了 `rule`
+fn allows(object: &Value, rule: LintRule) -> bool {
+    object
+        .get(ALLOW_EXTENSION)
+        .and_then(Value::as_array)
+        .is_some_and(|names| names.iter().any(|name| name == rule.name()))
+}
+
+fn is_blank(text: Option<&Value>) -> bool {
+    text.and_then(Value::as_str).is_none_or(|text| text.trim().is_empty())
+}
+
+/// 空段（开头或结尾的 `/`）视为合法
+fn is_kebab_case(segment: &str) -> bool {
+    segment.is_empty()
+        || segment.split('-').all(|word| !word.is_empty() && word.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()))
+}
+
+fn placeholders(path: &str) -> impl Iterator<Item = &str> {
+    path.split('{').skip(1).filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec() -> Value {
+        json!({
+            "paths": {
+                "/v1/products/{id}": { "get": {
+                    "operationId": "get_product",
+                    "summary": "Get a product",
+                    "description": "Looks the product up by id.",
+                    "parameters": [{ "name": "id", "in": "path", "required": true, "description": "Product id" }],
+                    "responses": { "200": { "description": "" }, "404": { "description": "" } }
+                } },
+                "/v1/legacy_items/{itemId}": { "get": {
+                    "operationId": "legacy_items",
+                    "parameters": [{ "name": "id", "in": "path", "required": true }],
+                    "responses": { "200": { "description": "" } }
+                } },
+                "/v1/legacy_report": { "get": {
+                    "operationId": "legacy_report",
+                    "summary": "Legacy report",
+                    "description": "Kept for old clients.",
+                    "x-lint-allow": ["non-kebab-case-path", "missing-error-response"],
+                    "responses": { "200": { "description": "" } }
+                } }
+            },
+            "components": { "schemas": {
+                "Product": { "type": "object", "description": "A catalog entry" },
+                "Legacy": { "type": "object" }
+            } }
+        })
+    }
+
+    fn found(report: &LintReport, rule: LintRule, location: &str) -> Vec<(Severity, String)> {
+        report
+            .findings
+            .iter()
+            .filter(|finding| finding.rule == rule && finding.location == location)
+            .map(|finding| (finding.severity, finding.message.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn rules_report_at_their_default_severity() {
+        let report = lint_spec(&spec(), None, &LintConfig::default());
+        let legacy = "GET /v1/legacy_items/{itemId}";
+
+        assert!(report.findings.iter().all(|finding| finding.location != "GET /v1/products/{id}"));
+        assert_eq!(
+            found(&report, LintRule::NonKebabCasePath, "/v1/legacy_items/{itemId}"),
+            [(Severity::Warning, "segment `legacy_items` is not kebab-case".to_string())]
+        );
+        assert_eq!(found(&report, LintRule::MissingSummary, legacy).len(), 1);
+        assert_eq!(found(&report, LintRule::MissingDescription, legacy)[0].0, Severity::Info);
+        assert_eq!(
+            found(&report, LintRule::EmptyParameterDescription, legacy),
+            [(Severity::Warning, "path parameter `id` has no description".to_string())]
+        );
+        assert_eq!(found(&report, LintRule::MissingErrorResponse, legacy).len(), 1);
+        assert_eq!(
+            found(&report, LintRule::PathParameterMismatch, legacy),
+            [
+                (Severity::Error, "placeholder `{itemId}` has no declared path parameter".to_string()),
+                (Severity::Error, "path parameter `id` does not appear in the path".to_string()),
+            ]
+        );
+        assert_eq!(
+            found(&report, LintRule::MissingDescription, "schema Legacy"),
+            [(Severity::Info, "schema has no description".to_string())]
+        );
+        assert!(found(&report, LintRule::MissingDescription, "schema Product").is_empty());
+        assert!(report.findings.iter().all(|finding| finding.rule != LintRule::UnregisteredHandler));
+        assert!(report.has_errors());
+    }
+
+    #[test]
+    fn operations_opt_out_with_the_allow_extension() {
+        let report = lint_spec(&spec(), None, &LintConfig::default());
+
+        assert!(found(&report, LintRule::NonKebabCasePath, "/v1/legacy_report").is_empty());
+        assert!(found(&report, LintRule::MissingErrorResponse, "GET /v1/legacy_report").is_empty());
+    }
+
+    #[test]
+    fn operations_without_a_handler_are_reported() {
+        let handlers = HashSet::from(["get_product".to_string(), "legacy_report".to_string()]);
+        let report = lint_spec(&spec(), Some(&handlers), &LintConfig::default());
+
+        assert_eq!(
+            found(&report, LintRule::UnregisteredHandler, "GET /v1/legacy_items/{itemId}"),
+            [(Severity::Error, "operation `legacy_items` has no handler registered with #[api]".to_string())]
+        );
+        assert_eq!(report.findings.iter().filter(|finding| finding.rule == LintRule::UnregisteredHandler).count(), 1);
+    }
+
+    #[test]
+    fn levels_are_configurable() {
+        let config = LintConfig::default()
+            .apply("path-parameter-mismatch=off")
+            .unwrap()
+            .apply("missing-summary=error")
+            .unwrap()
+            .allow(LintRule::MissingDescription);
+        let report = lint_spec(&spec(), None, &config);
+
+        assert!(report.findings.iter().all(|finding| finding.rule != LintRule::PathParameterMismatch));
+        assert!(report.findings.iter().all(|finding| finding.rule != LintRule::MissingDescription));
+        assert_eq!(found(&report, LintRule::MissingSummary, "GET /v1/legacy_items/{itemId}")[0].0, Severity::Error);
+        assert!(report.to_string().starts_with("error[missing-summary] GET /v1/legacy_items/{itemId}: "));
+
+        assert!(LintConfig::default().apply("no-such-rule=off").is_err());
+        assert!(LintConfig::default().apply("missing-summary=loud").is_err());
+        assert!(LintConfig::default().apply("missing-summary").is_err());
+    }
+}
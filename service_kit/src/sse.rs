@@ -0,0 +1,109 @@
+//! 增量解析 `text/event-stream` 响应，供 CLI 与 MCP 在事件到达时逐个处理
+
+/// 事件流的 Content-Type
+pub const EVENT_STREAM: &str = "text/event-stream";
+
+/// 一条 Server-Sent Event；多行 `data:` 以换行拼接
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    pub event: Option<String>,
+    pub id: Option<String>,
+    pub data: String,
+}
+
+/// 按块喂入响应体，返回其中已完整的事件（以空行结束）。
+/// 缓冲原始字节，只解码完整的事件，跨块切开的多字节字符不会被替换为 `U+FFFD`。
+#[derive(Debug, Default)]
+pub struct SseParser {
+    buffer: Vec<u8>,
+}
+
+impl SseParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some((end, next)) = event_boundary(&self.buffer) {
+            let raw: Vec<u8> = self.buffer.drain(..next).collect();
+            events.extend(parse_event(&String::from_utf8_lossy(&raw[..end])));
+        }
+        events
+    }
+
+    /// 流结束时取出最后一个未以空行结束的事件
+    pub fn finish(&mut self) -> Option<SseEvent> {
+        let remaining = std::mem::take(&mut self.buffer);
+        parse_event(&String::from_utf8_lossy(&remaining))
+    }
+}
+
+/// 找到第一个空行：返回事件正文的结束位置与下一个事件的起始位置
+fn event_boundary(buffer: &[u8]) -> Option<(usize, usize)> {
+    let mut line_start = 0;
+    for (i, byte) in buffer.iter().enumerate() {
+        if *byte == b'\n' {
+            let line = &buffer[line_start..i];
+            if line.is_empty() || line == b"\r" {
+                return Some((line_start, i + 1));
+            }
+            line_start = i + 1;
+        }
+    }
+    None
+}
+
+/// 解析一个事件的全部字段行；没有 `data:` 的事件（例如只有注释）被忽略
+fn parse_event(text: &str) -> Option<SseEvent> {
+    let mut event = SseEvent::default();
+    let mut has_data = false;
+    for line in text.lines() {
+        // 以冒号开头的是注释（例如 keep-alive）
+        if line.is_empty() || line.starts_with(':') {
+            continue;
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => event.event = Some(value.to_string()),
+            "id" => event.id = Some(value.to_string()),
+            "data" => {
+                if has_data {
+                    event.data.push('\n');
+                }
+                event.data.push_str(value);
+                has_data = true;
+            }
+            _ => {}
+        }
+    }
+    has_data.then_some(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multi_byte_characters_split_across_chunks_are_decoded() {
+        let stream = "event: tick\ndata: 价格 ✓\n\ndata: b\r\n\r\n".as_bytes();
+        let split = stream.iter().position(|b| *b == 0xE4).unwrap() + 1;
+        let mut parser = SseParser::new();
+
+        assert!(parser.feed(&stream[..split]).is_empty());
+        let events = parser.feed(&stream[split..]);
+
+        assert_eq!(
+            events,
+            vec![
+                SseEvent { event: Some("tick".to_string()), id: None, data: "价格 ✓".to_string() },
+                SseEvent { event: None, id: None, data: "b".to_string() },
+            ]
+        );
+        assert_eq!(parser.finish(), None);
+    }
+}
@@ -0,0 +1,468 @@
+//! 由 `#[api]` 元数据直接构建的类型化客户端，供同一 workspace 内的服务之间调用，无需经过规范文件。
+//!
+//! 启用 `client` 特性后，`#[api]` 会为每个处理函数额外生成一个扩展 trait（`get_product` -> `GetProductClient`），
+//! 为所有实现了 [`ApiClient`] 的类型提供同名异步方法。方法的参数与返回值沿用处理函数签名中的 DTO 类型，
+//! 请求的方法、路径与认证要求取自该处理函数注册的 [`ApiMetadata`]，因此 DTO 字段改名会在调用处直接编译失败。
+//!
+//! ```ignore
+//! service_kit::client!(pub ProductService);
+//!
+//! use product_service::handlers::*;
+//! let service = ProductService::builder("http://127.0.0.1:3000").bearer_token("dev-token").build()?;
+//! let page = service.list_products_paged(ProductPageQuery { page: 1, status: None }).await?;
+//! ```
+
+use crate::sse::SseParser;
+use crate::ApiMetadata;
+use reqwest::header::{HeaderMap, HeaderName};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+pub use reqwest::header::HeaderValue;
+pub use reqwest::multipart;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(String),
+    #[error("Invalid Argument: {0}")]
+    InvalidArgument(String),
+    #[error("Request Error: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Decode Error: {0}")]
+    Decode(#[from] serde_json::Error),
+    #[error("Status {status}: {body}")]
+    Status { status: u16, body: String },
+}
+
+impl Error {
+    /// 非 2xx 响应的状态码
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Error::Status { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// 将非 2xx 响应体解析为 `T`（如 `ErrorBody`）；不是该类型时为 `None`
+    pub fn body<T: DeserializeOwned>(&self) -> Option<T> {
+        match self {
+            Error::Status { body, .. } => serde_json::from_str(body).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// 连接某个服务的客户端；`client!` 声明的类型包装的就是它
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: reqwest::Url,
+    bearer_token: Option<String>,
+}
+
+impl Client {
+    /// 以默认配置连接 `base_url` 处的服务
+    pub fn new(base_url: &str) -> Result<Self, Error> {
+        Self::builder(base_url).build()
+    }
+
+    pub fn builder(base_url: &str) -> ClientBuilder {
+        ClientBuilder::new(base_url)
+    }
+
+    pub fn base_url(&self) -> &reqwest::Url {
+        &self.base_url
+    }
+}
+
+/// `#[api]` 生成的扩展 trait 为所有实现了该 trait 的类型提供类型化方法
+pub trait ApiClient: Sync {
+    fn api_client(&self) -> &Client;
+}
+
+impl ApiClient for Client {
+    fn api_client(&self) -> &Client {
+        self
+    }
+}
+
+/// [`Client`] 或 `client!` 声明的类型的构建器；非法的请求头推迟到 `build` 时报告
+pub struct ClientBuilder<C = Client> {
+    base_url: String,
+    headers: HeaderMap,
+    bearer_token: Option<String>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    error: Option<Error>,
+    _client: PhantomData<fn() -> C>,
+}
+
+impl<C: From<Client>> ClientBuilder<C> {
+    pub fn new(base_url: &str) -> Self {
+        ClientBuilder {
+            base_url: base_url.to_string(),
+            headers: HeaderMap::new(),
+            bearer_token: None,
+            timeout: None,
+            connect_timeout: None,
+            error: None,
+            _client: PhantomData,
+        }
+    }
+
+    /// 每个请求都携带的默认请求头
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        match (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+            (Ok(name), Ok(value)) => {
+                self.headers.insert(name, value);
+            }
+            _ => self.error = Some(Error::InvalidArgument(format!("invalid header `{}`", name))),
+        }
+        self
+    }
+
+    /// 调用 `#[api(..., security = "bearer")]` 声明的接口时发送的令牌
+    pub fn bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    pub fn build(self) -> Result<C, Error> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let base_url = reqwest::Url::parse(&self.base_url)
+            .ok()
+            .filter(|url| !url.cannot_be_a_base())
+            .ok_or_else(|| Error::InvalidUrl(self.base_url.clone()))?;
+        let mut http = reqwest::Client::builder().default_headers(self.headers);
+        if let Some(timeout) = self.timeout {
+            http = http.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            http = http.connect_timeout(timeout);
+        }
+        Ok(C::from(Client { http: http.build()?, base_url, bearer_token: self.bearer_token }))
+    }
+}
+
+enum Body {
+    Empty,
+    Json(Vec<u8>),
+    Form(String),
+    Multipart(multipart::Form),
+    Raw(&'static str, reqwest::Body),
+}
+
+/// `#[api]` 生成的方法发出的单次调用；参数在加入时即序列化，错误推迟到发送时返回
+#[doc(hidden)]
+pub struct Call<'a> {
+    client: &'a Client,
+    metadata: &'static ApiMetadata,
+    path: Option<Value>,
+    query: Vec<(String, String)>,
+    headers: HeaderMap,
+    cookies: Vec<String>,
+    body: Body,
+    error: Option<Error>,
+}
+
+impl<'a> Call<'a> {
+    pub fn new(client: &'a Client, metadata: &'static ApiMetadata) -> Self {
+        Call {
+            client,
+            metadata,
+            path: None,
+            query: Vec::new(),
+            headers: HeaderMap::new(),
+            cookies: Vec::new(),
+            body: Body::Empty,
+            error: None,
+        }
+    }
+
+    /// `Path<T>` 的取值：标量对应唯一的占位符，元组按顺序、结构体按字段名对应各占位符
+    pub fn path<T: Serialize>(mut self, value: &T) -> Self {
+        if let Some(value) = self.serialize(value) {
+            self.path = Some(value);
+        }
+        self
+    }
+
+    /// `Query<T>` 的取值：顶层字段各为一个查询参数，`null` 省略，数组展开为同名的多个参数
+    pub fn query<T: Serialize>(mut self, value: &T) -> Self {
+        if let Some(value) = self.serialize(value) {
+            let pairs = flatten(&value);
+            self.query.extend(pairs);
+        }
+        self
+    }
+
+    /// `headers(...)` 声明的请求头；`None` 时不发送
+    pub fn header<T: Serialize>(mut self, name: &'static str, value: &T) -> Self {
+        let value = match self.serialize(value) {
+            Some(Value::Null) | None => return self,
+            Some(value) => scalar(&value),
+        };
+        match HeaderValue::try_from(value) {
+            Ok(value) => {
+                self.headers.append(name, value);
+            }
+            Err(_) => self.fail(format!("invalid value for header `{}`", name)),
+        }
+        self
+    }
+
+    /// `TypedHeader<H>` 编码出的请求头
+    pub fn header_values(mut self, name: &'static str, values: Vec<HeaderValue>) -> Self {
+        for value in values {
+            self.headers.append(name, value);
+        }
+        self
+    }
+
+    /// `cookies(...)` 声明的 cookie；`None` 时不发送
+    pub fn cookie<T: Serialize>(mut self, name: &str, value: &T) -> Self {
+        match self.serialize(value) {
+            Some(Value::Null) | None => {}
+            Some(value) => self.cookies.push(format!("{}={}", name, scalar(&value))),
+        }
+        self
+    }
+
+    pub fn json<T: Serialize>(mut self, body: &T) -> Self {
+        match serde_json::to_vec(body) {
+            Ok(body) => self.body = Body::Json(body),
+            Err(e) => self.error = Some(Error::Decode(e)),
+        }
+        self
+    }
+
+    pub fn form<T: Serialize>(mut self, body: &T) -> Self {
+        match serde_urlencoded::to_string(body) {
+            Ok(body) => self.body = Body::Form(body),
+            Err(e) => self.fail(e.to_string()),
+        }
+        self
+    }
+
+    pub fn multipart(mut self, form: multipart::Form) -> Self {
+        self.body = Body::Multipart(form);
+        self
+    }
+
+    /// `Bytes`（`application/octet-stream`）或 `String`（`text/plain`）请求体
+    pub fn body(mut self, content_type: &'static str, body: impl Into<reqwest::Body>) -> Self {
+        self.body = Body::Raw(content_type, body.into());
+        self
+    }
+
+    /// 成功响应的 JSON 响应体；空响应体按 `null` 解析
+    pub async fn decode<T: DeserializeOwned>(self) -> Result<T, Error> {
+        let body = self.send().await?.bytes().await?;
+        let body: &[u8] = if body.is_empty() { b"null" } else { &body };
+        Ok(serde_json::from_slice(body)?)
+    }
+
+    /// 返回 `Option<Json<T>>` 的处理函数：404 为 `None`
+    pub async fn decode_optional<T: DeserializeOwned>(self) -> Result<Option<T>, Error> {
+        match self.decode().await {
+            Err(Error::Status { status: 404, .. }) => Ok(None),
+            result => result.map(Some),
+        }
+    }
+
+    /// 返回 `Sse<...>` 的处理函数：逐条解析事件的 `data`
+    pub async fn events<T: DeserializeOwned>(self) -> Result<EventStream<T>, Error> {
+        let response = self.send().await?;
+        Ok(EventStream { response, parser: SseParser::new(), pending: Vec::new(), _event: PhantomData })
+    }
+
+    async fn send(self) -> Result<reqwest::Response, Error> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let metadata = self.metadata;
+        let method = reqwest::Method::from_bytes(metadata.method.to_uppercase().as_bytes())
+            .map_err(|_| Error::InvalidArgument(format!("unsupported method `{}`", metadata.method)))?;
+        let url = self.url()?;
+        let mut request = self.client.http.request(method, url).headers(self.headers);
+        if !self.query.is_empty() {
+            request = request.query(&self.query);
+        }
+        if !self.cookies.is_empty() {
+            request = request.header(reqwest::header::COOKIE, self.cookies.join("; "));
+        }
+        if let (false, Some(token)) = (metadata.security.is_empty(), &self.client.bearer_token) {
+            request = request.bearer_auth(token);
+        }
+        request = match self.body {
+            Body::Empty => request,
+            Body::Json(body) => request.header(reqwest::header::CONTENT_TYPE, "application/json").body(body),
+            Body::Form(body) => {
+                request.header(reqwest::header::CONTENT_TYPE, "application/x-www-form-urlencoded").body(body)
+            }
+            Body::Multipart(form) => request.multipart(form),
+            Body::Raw(content_type, body) => request.header(reqwest::header::CONTENT_TYPE, content_type).body(body),
+        };
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(Error::Status { status: status.as_u16(), body });
+        }
+        Ok(response)
+    }
+
+    /// 以元数据中的路由模板拼出 URL；`{*rest}` 的取值可以包含 `/`
+    fn url(&self) -> Result<reqwest::Url, Error> {
+        let mut url = self.client.base_url.clone();
+        let template: Vec<&str> = self.metadata.path.split('/').filter(|segment| !segment.is_empty()).collect();
+        let placeholders = template.iter().filter(|segment| segment.starts_with('{')).count();
+        let mut index = 0;
+        let mut segments = Vec::new();
+        for segment in template {
+            let Some(name) = segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) else {
+                segments.push(segment.to_string());
+                continue;
+            };
+            let (name, wildcard) = match name.strip_prefix('*') {
+                Some(name) => (name, true),
+                None => (name, false),
+            };
+            let value = match &self.path {
+                Some(Value::Object(fields)) => fields.get(name),
+                Some(Value::Array(items)) => items.get(index),
+                Some(value) if placeholders == 1 => Some(value),
+                _ => None,
+            }
+            .filter(|value| !value.is_null())
+            .ok_or_else(|| Error::InvalidArgument(format!("missing path parameter `{}`", name)))?;
+            index += 1;
+            let value = scalar(value);
+            if wildcard {
+                segments.extend(value.split('/').map(str::to_string));
+            } else {
+                segments.push(value);
+            }
+        }
+        url.path_segments_mut()
+            .map_err(|_| Error::InvalidUrl(self.client.base_url.to_string()))?
+            .pop_if_empty()
+            .extend(&segments);
+        Ok(url)
+    }
+
+    fn serialize<T: Serialize>(&mut self, value: &T) -> Option<Value> {
+        match serde_json::to_value(value) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                self.error.get_or_insert(Error::Decode(e));
+                None
+            }
+        }
+    }
+
+    fn fail(&mut self, message: String) {
+        self.error.get_or_insert(Error::InvalidArgument(message));
+    }
+}
+
+/// 字符串原样输出，其余标量输出其 JSON 文本
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn flatten(value: &Value) -> Vec<(String, String)> {
+    let Value::Object(fields) = value else {
+        return Vec::new();
+    };
+    let mut pairs = Vec::new();
+    for (name, value) in fields {
+        match value {
+            Value::Null => {}
+            Value::Array(items) => pairs.extend(items.iter().map(|item| (name.clone(), scalar(item)))),
+            value => pairs.push((name.clone(), scalar(value))),
+        }
+    }
+    pairs
+}
+
+/// `Sse<...>` 接口的事件流
+pub struct EventStream<T> {
+    response: reqwest::Response,
+    parser: SseParser,
+    pending: Vec<crate::sse::SseEvent>,
+    _event: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> EventStream<T> {
+    /// 等待下一个事件；服务端关闭流后为 `None`
+    pub async fn next(&mut self) -> Option<Result<T, Error>> {
+        loop {
+            if !self.pending.is_empty() {
+                let event = self.pending.remove(0);
+                return Some(serde_json::from_str(&event.data).map_err(Error::Decode));
+            }
+            match self.response.chunk().await {
+                Ok(Some(chunk)) => self.pending = self.parser.feed(&chunk),
+                Ok(None) => return None,
+                Err(e) => return Some(Err(Error::Request(e))),
+            }
+        }
+    }
+}
+
+/// 声明一个类型化客户端：`service_kit::client!(pub ProductService)`。
+///
+/// 生成的类型提供 `new(base_url)` 与 `builder(base_url)`，并实现 [`ApiClient`]；
+/// 导入处理函数所在模块（如 `use product_service::handlers::*`）后即可调用各 `#[api]` 对应的方法。
+#[macro_export]
+macro_rules! client {
+    ($(#[$attr:meta])* $vis:vis $name:ident) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone)]
+        $vis struct $name($crate::typed_client::Client);
+
+        impl $name {
+            /// Connects to the service at `base_url` with default settings.
+            $vis fn new(base_url: &str) -> ::std::result::Result<Self, $crate::typed_client::Error> {
+                Self::builder(base_url).build()
+            }
+
+            /// Starts configuring default headers, timeouts and the bearer token.
+            $vis fn builder(base_url: &str) -> $crate::typed_client::ClientBuilder<Self> {
+                $crate::typed_client::ClientBuilder::new(base_url)
+            }
+        }
+
+        impl ::std::convert::From<$crate::typed_client::Client> for $name {
+            fn from(client: $crate::typed_client::Client) -> Self {
+                $name(client)
+            }
+        }
+
+        impl $crate::typed_client::ApiClient for $name {
+            fn api_client(&self) -> &$crate::typed_client::Client {
+                &self.0
+            }
+        }
+    };
+}
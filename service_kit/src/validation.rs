@@ -0,0 +1,355 @@
+//! `#[api_dto]` 字段上 `#[validate(...)]` 约束的运行时校验。
+//!
+//! 约束以 JSON Schema 关键字（`minLength`、`maximum`、`pattern` 等）写入 DTO 的 schema，
+//! `#[api]` 生成的执行函数在调用处理函数前按同一份 schema 校验入参，文档与校验不会不一致。
+
+use crate::error::{Error, Result};
+use crate::openapi_utils::{component_name, registered_schemas, SchemaResolver};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+
+/// 嵌套 `$ref` 的最大展开深度，防止递归类型无限展开
+const MAX_DEPTH: usize = 32;
+
+/// 以 `components.schemas` 中的组件名（如 `Product`、`Page_Product`）为键，与 `$ref` 一致
+static DTO_SCHEMAS: Lazy<HashMap<String, Value>> = Lazy::new(|| {
+    registered_schemas()
+        .into_iter()
+        .filter_map(|(name, schema)| serde_json::to_value(schema).ok().map(|schema| (component_name(&name), schema)))
+        .collect()
+});
+
+/// 与 `build_openapi_basic` 相同的类型名解析：容器（`Vec<T>`、`Option<T>` 等）展开，DTO 以 `$ref` 引用
+static RESOLVER: Lazy<SchemaResolver> = Lazy::new(|| SchemaResolver::new(registered_schemas().keys().map(String::as_str)));
+
+static PATTERNS: Lazy<Mutex<HashMap<String, Regex>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 单个字段未满足的约束
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldViolation {
+    /// 字段路径（序列化后的字段名），例如 `category.name`、`tags[0]`
+    pub field: String,
+    /// 违反的 JSON Schema 关键字，例如 `minLength`、`pattern`
+    pub rule: &'static str,
+    pub message: String,
+}
+
+/// 一次校验中收集到的全部违规字段
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ValidationErrors {
+    pub errors: Vec<FieldViolation>,
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let messages: Vec<String> = self
+            .errors
+            .iter()
+            .map(|e| format!("{}: {}", e.field, e.message))
+            .collect();
+        f.write_str(&messages.join("; "))
+    }
+}
+
+/// 按 `type_name`（如 `crate::dtos::Product`、`Vec<Product>`、`Page<Product>`）解析出的 schema 约束校验 `value`；
+/// 无法解析的类型直接通过。类型不匹配的值留给反序列化报错，这里只检查约束本身。
+pub fn validate(type_name: &str, value: &Value) -> Result<()> {
+    let Some(schema) = RESOLVER.resolve(type_name).and_then(|schema| serde_json::to_value(schema).ok()) else {
+        return Ok(());
+    };
+    let mut errors = Vec::new();
+    Validator { schemas: &DTO_SCHEMAS }.check(&schema, value, "", 0, &mut errors)?;
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Validation(ValidationErrors { errors }))
+    }
+}
+
+/// `type_name` 解析出的对象 schema 的属性名（序列化后的字段名）；无法解析或不是对象时返回 `None`
+pub fn property_names(type_name: &str) -> Option<Vec<String>> {
+    let schema = serde_json::to_value(RESOLVER.resolve(type_name)?).ok()?;
+    let validator = Validator { schemas: &DTO_SCHEMAS };
+    let properties = validator.resolve(&schema).get("properties")?.as_object()?;
+    Some(properties.keys().cloned().collect())
+}
+
+/// 按组件名解析 `$ref` 的校验器
+struct Validator<'a> {
+    schemas: &'a HashMap<String, Value>,
+}
+
+impl Validator<'_> {
+    fn resolve<'s>(&'s self, schema: &'s Value) -> &'s Value {
+        match schema.get("$ref").and_then(Value::as_str) {
+            Some(reference) => {
+                let name = reference.rsplit('/').next().unwrap_or(reference);
+                self.schemas.get(name).unwrap_or(&Value::Null)
+            }
+            None => schema,
+        }
+    }
+
+    fn check(&self, schema: &Value, value: &Value, path: &str, depth: usize, errors: &mut Vec<FieldViolation>) -> Result<()> {
+        if depth > MAX_DEPTH || value.is_null() {
+            return Ok(());
+        }
+        if schema.get("$ref").is_some() {
+            return self.check(self.resolve(schema), value, path, depth + 1, errors);
+        }
+        for branch in schema.get("allOf").and_then(Value::as_array).into_iter().flatten() {
+            self.check(branch, value, path, depth + 1, errors)?;
+        }
+        for combinator in ["oneOf", "anyOf"] {
+            if let Some(branches) = schema.get(combinator).and_then(Value::as_array) {
+                self.check_alternatives(schema, branches, value, path, depth, errors)?;
+            }
+        }
+
+        let mut violation = |rule: &'static str, message: String| {
+            errors.push(FieldViolation { field: path.to_string(), rule, message });
+        };
+        match value {
+            Value::String(s) => {
+                let length = s.chars().count() as u64;
+                if let Some(min) = schema.get("minLength").and_then(Value::as_u64).filter(|min| length < *min) {
+                    violation("minLength", format!("must be at least {} characters long", min));
+                }
+                if let Some(max) = schema.get("maxLength").and_then(Value::as_u64).filter(|max| length > *max) {
+                    violation("maxLength", format!("must be at most {} characters long", max));
+                }
+                if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+                    if !matches_pattern(pattern, s)? {
+                        violation("pattern", format!("must match the pattern `{}`", pattern));
+                    }
+                }
+                // urlencoded 表单的值都是字符串，数值字段按解析后的数值检查范围
+                if is_numeric_schema(schema) {
+                    if let Ok(n) = s.trim().parse::<f64>() {
+                        check_range(schema, n, &mut violation);
+                    }
+                }
+            }
+            Value::Number(n) => check_range(schema, n.as_f64().unwrap_or_default(), &mut violation),
+            Value::Array(items) => {
+                let count = items.len() as u64;
+                if let Some(min) = schema.get("minItems").and_then(Value::as_u64).filter(|min| count < *min) {
+                    violation("minItems", format!("must contain at least {} items", min));
+                }
+                if let Some(max) = schema.get("maxItems").and_then(Value::as_u64).filter(|max| count > *max) {
+                    violation("maxItems", format!("must contain at most {} items", max));
+                }
+                if let Some(item_schema) = schema.get("items") {
+                    for (i, item) in items.iter().enumerate() {
+                        self.check(item_schema, item, &format!("{}[{}]", path, i), depth + 1, errors)?;
+                    }
+                }
+            }
+            Value::Object(fields) => {
+                let properties = schema.get("properties").and_then(Value::as_object);
+                let field_path = |name: &str| if path.is_empty() { name.to_string() } else { format!("{}.{}", path, name) };
+                for (name, property) in properties.into_iter().flatten() {
+                    if let Some(field_value) = fields.get(name) {
+                        self.check(property, field_value, &field_path(name), depth + 1, errors)?;
+                    }
+                }
+                // `HashMap<String, T>`：其余字段按 `additionalProperties` 校验
+                if let Some(additional) = schema.get("additionalProperties").filter(|s| s.is_object()) {
+                    for (name, field_value) in fields {
+                        if !properties.is_some_and(|p| p.contains_key(name)) {
+                            self.check(additional, field_value, &field_path(name), depth + 1, errors)?;
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// `oneOf` / `anyOf`：有 discriminator 时只按标签选中的分支校验；
+    /// 否则在类型相符的分支中，只要有一个分支通过即视为通过，全部不通过时报告违规最少的分支
+    fn check_alternatives(
+        &self,
+        schema: &Value,
+        branches: &[Value],
+        value: &Value,
+        path: &str,
+        depth: usize,
+        errors: &mut Vec<FieldViolation>,
+    ) -> Result<()> {
+        if let Some(branch) = self.discriminated_branch(schema, branches, value) {
+            return self.check(branch, value, path, depth + 1, errors);
+        }
+        let mut best: Option<Vec<FieldViolation>> = None;
+        for branch in branches.iter().filter(|branch| self.type_matches(branch, value, depth)) {
+            let mut branch_errors = Vec::new();
+            self.check(branch, value, path, depth + 1, &mut branch_errors)?;
+            if branch_errors.is_empty() {
+                return Ok(());
+            }
+            if best.as_ref().is_none_or(|best| branch_errors.len() < best.len()) {
+                best = Some(branch_errors);
+            }
+        }
+        errors.extend(best.unwrap_or_default());
+        Ok(())
+    }
+
+    /// 按 `discriminator.propertyName` 的值选出分支：优先使用 `mapping`，
+    /// 否则取该属性 `enum` 中包含此标签的分支
+    fn discriminated_branch<'s>(&'s self, schema: &Value, branches: &'s [Value], value: &Value) -> Option<&'s Value> {
+        let discriminator = schema.get("discriminator")?;
+        let property = discriminator.get("propertyName")?.as_str()?;
+        let tag = value.get(property)?;
+        if let Some(reference) = tag.as_str().and_then(|tag| discriminator.get("mapping")?.get(tag)) {
+            return branches.iter().find(|branch| branch.get("$ref") == Some(reference));
+        }
+        branches.iter().find(|branch| {
+            self.resolve(branch)
+                .get("properties")
+                .and_then(|properties| properties.get(property))
+                .and_then(|tag_schema| tag_schema.get("enum"))
+                .and_then(Value::as_array)
+                .is_some_and(|tags| tags.contains(tag))
+        })
+    }
+
+    /// 值的 JSON 类型是否可能属于该分支；未声明 `type` 的分支（组合、任意值）视为可能
+    fn type_matches(&self, branch: &Value, value: &Value, depth: usize) -> bool {
+        let branch = self.resolve(branch);
+        if depth > MAX_DEPTH {
+            return true;
+        }
+        if let Some(nested) = ["oneOf", "anyOf"].iter().find_map(|key| branch.get(*key).and_then(Value::as_array)) {
+            return nested.iter().any(|b| self.type_matches(b, value, depth + 1));
+        }
+        let accepts = |ty: &Value| match (ty.as_str(), value) {
+            (Some("null"), Value::Null)
+            | (Some("boolean"), Value::Bool(_))
+            | (Some("number"), Value::Number(_))
+            | (Some("string"), Value::String(_))
+            | (Some("array"), Value::Array(_))
+            | (Some("object"), Value::Object(_)) => true,
+            (Some("integer"), Value::Number(n)) => n.is_i64() || n.is_u64(),
+            _ => false,
+        };
+        match branch.get("type") {
+            Some(Value::Array(types)) => types.iter().any(accepts),
+            Some(ty) => accepts(ty),
+            None => true,
+        }
+    }
+}
+
+fn is_numeric_schema(schema: &Value) -> bool {
+    let is_numeric = |t: &Value| matches!(t.as_str(), Some("integer" | "number"));
+    match schema.get("type") {
+        Some(Value::Array(types)) => types.iter().any(is_numeric),
+        Some(t) => is_numeric(t),
+        None => false,
+    }
+}
+
+fn check_range(schema: &Value, n: f64, violation: &mut impl FnMut(&'static str, String)) {
+    if let Some(min) = schema.get("minimum").and_then(Value::as_f64).filter(|min| n < *min) {
+        violation("minimum", format!("must be greater than or equal to {}", min));
+    }
+    if let Some(max) = schema.get("maximum").and_then(Value::as_f64).filter(|max| n > *max) {
+        violation("maximum", format!("must be less than or equal to {}", max));
+    }
+}
+
+fn matches_pattern(pattern: &str, value: &str) -> Result<bool> {
+    let mut patterns = PATTERNS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if !patterns.contains_key(pattern) {
+        let regex = Regex::new(pattern)
+            .map_err(|e| Error::SpecError(format!("Invalid pattern `{}`: {}", pattern, e)))?;
+        patterns.insert(pattern.to_string(), regex);
+    }
+    Ok(patterns[pattern].is_match(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn violations(schemas: &HashMap<String, Value>, schema: Value, value: Value) -> Vec<&'static str> {
+        let mut errors = Vec::new();
+        Validator { schemas }.check(&schema, &value, "", 0, &mut errors).unwrap();
+        errors.into_iter().map(|e| e.rule).collect()
+    }
+
+    fn price_rules() -> HashMap<String, Value> {
+        HashMap::from([
+            (
+                "FixedDiscount".to_string(),
+                json!({ "type": "object", "properties": {
+                    "type": { "type": "string", "enum": ["fixedDiscount"] },
+                    "amount": { "type": "number", "minimum": 0 }
+                } }),
+            ),
+            (
+                "PercentOff".to_string(),
+                json!({ "type": "object", "properties": {
+                    "type": { "type": "string", "enum": ["percentOff"] },
+                    "amount": { "type": "number", "maximum": 100 }
+                } }),
+            ),
+        ])
+    }
+
+    #[test]
+    fn discriminator_selects_the_branch_to_check() {
+        let schemas = price_rules();
+        let schema = json!({
+            "oneOf": [{ "$ref": "#/components/schemas/FixedDiscount" }, { "$ref": "#/components/schemas/PercentOff" }],
+            "discriminator": { "propertyName": "type" }
+        });
+
+        // 250 违反 PercentOff 的 maximum，但按标签只校验 FixedDiscount
+        assert!(violations(&schemas, schema.clone(), json!({ "type": "fixedDiscount", "amount": 250 })).is_empty());
+        assert_eq!(violations(&schemas, schema.clone(), json!({ "type": "percentOff", "amount": 250 })), vec!["maximum"]);
+        assert_eq!(violations(&schemas, schema, json!({ "type": "fixedDiscount", "amount": -1 })), vec!["minimum"]);
+    }
+
+    #[test]
+    fn any_passing_branch_accepts_the_value() {
+        let schemas = HashMap::new();
+        let schema = json!({ "anyOf": [
+            { "type": "string", "minLength": 5 },
+            { "type": "string", "pattern": "^[0-9]+$" },
+            { "type": "integer", "minimum": 10 }
+        ] });
+
+        assert!(violations(&schemas, schema.clone(), json!("123")).is_empty());
+        assert!(violations(&schemas, schema.clone(), json!("abcdef")).is_empty());
+        assert_eq!(violations(&schemas, schema.clone(), json!("ab1")), vec!["minLength"]);
+        assert_eq!(violations(&schemas, schema, json!(3)), vec!["minimum"]);
+    }
+
+    #[test]
+    fn nullable_references_and_containers_are_resolved() {
+        let mut schemas = price_rules();
+        schemas.insert(
+            "Page_FixedDiscount".to_string(),
+            json!({ "type": "object", "properties": {
+                "items": { "type": "array", "items": { "$ref": "#/components/schemas/FixedDiscount" } },
+                "next": { "oneOf": [{ "type": "null" }, { "$ref": "#/components/schemas/FixedDiscount" }] }
+            } }),
+        );
+        let page = json!({ "$ref": "#/components/schemas/Page_FixedDiscount" });
+        let value = json!({ "items": [{ "amount": 1 }, { "amount": -1 }], "next": { "amount": -2 } });
+
+        let mut errors = Vec::new();
+        Validator { schemas: &schemas }.check(&page, &value, "", 0, &mut errors).unwrap();
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["items[1].amount", "next.amount"]);
+    }
+}
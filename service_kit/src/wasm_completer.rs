@@ -47,8 +47,8 @@ impl WasmCompleter {
 
             if part.starts_with('-') {
                 if let Some(arg_match) = current_cmd.get_arguments().find(|a| {
-                    (a.get_long().map_or(false, |l| format!("--{}", l) == *part)) ||
-                    (a.get_short().map_or(false, |s| format!("-{}", s) == *part))
+                    (a.get_long().is_some_and(|l| format!("--{}", l) == *part)) ||
+                    (a.get_short().is_some_and(|s| format!("-{}", s) == *part))
                 }) {
                     last_arg_opt = Some(arg_match);
                     if !arg_match.get_action().takes_values() {
@@ -69,7 +69,7 @@ impl WasmCompleter {
                     current_cmd = sub_cmd;
                     last_arg_opt = None;
                 } else {
-                    if !last_arg_opt.map_or(false, |arg| arg.get_action().takes_values()) {
+                    if !last_arg_opt.is_some_and(|arg| arg.get_action().takes_values()) {
                         break;
                     }
                     last_arg_opt = None;
@@ -84,8 +84,8 @@ impl WasmCompleter {
                     let command_for_arg = get_command_at_path(&self.command, path_to_arg_command);
 
                     if let Some(clap_arg) = command_for_arg.get_arguments().find(|a| {
-                        a.get_long().map_or(false, |l| format!("--{}", l) == *arg_name_part) ||
-                        a.get_short().map_or(false, |s| format!("-{}", s) == *arg_name_part)
+                        a.get_long().is_some_and(|l| format!("--{}", l) == *arg_name_part) ||
+                        a.get_short().is_some_and(|s| format!("-{}", s) == *arg_name_part)
                     }) {
                         if clap_arg.get_action().takes_values() {
                             suggestions.extend(find_value_suggestions(clap_arg, current_word, span_start, pos));
@@ -95,7 +95,7 @@ impl WasmCompleter {
             }
         }
 
-        if line_to_cursor.ends_with(' ') && last_arg_opt.map_or(false, |arg| arg.get_action().takes_values()) {
+        if line_to_cursor.ends_with(' ') && last_arg_opt.is_some_and(|arg| arg.get_action().takes_values()) {
             if let Some(arg_that_needs_value) = last_arg_opt {
                 suggestions.extend(find_value_suggestions(arg_that_needs_value, "", span_start, pos));
             }
@@ -122,7 +122,7 @@ impl WasmCompleter {
 
                 for sugg in long_args {
                     if let Some(arg_def) = current_cmd.get_arguments().find(|a| {
-                        a.get_long().map_or(false, |l| format!("--{}", l) == sugg.value)
+                        a.get_long().is_some_and(|l| format!("--{}", l) == sugg.value)
                     }) {
                         if matches!(*arg_def.get_action(), clap::ArgAction::SetTrue) && existing_flags.contains(sugg.value.as_str()) {
                             continue;
@@ -137,7 +137,7 @@ impl WasmCompleter {
 
                 for sugg in short_args {
                     if let Some(arg_def) = current_cmd.get_arguments().find(|a| {
-                        a.get_short().map_or(false, |s_char| format!("-{}", s_char) == sugg.value)
+                        a.get_short().is_some_and(|s_char| format!("-{}", s_char) == sugg.value)
                     }) {
                         if matches!(*arg_def.get_action(), clap::ArgAction::SetTrue) && existing_flags.contains(sugg.value.as_str()) {
                             continue;
@@ -171,7 +171,7 @@ fn find_subcommand_suggestions(
     let mut suggestions = Vec::new();
 
     let relevant_parts_count = if current_word.is_empty() && !parts.is_empty()
-        && parts.last().map_or(false, |p| !p.is_empty()) {
+        && parts.last().is_some_and(|p| !p.is_empty()) {
         parts.len()
     } else {
         parts.len().saturating_sub(1)
@@ -223,15 +223,15 @@ fn find_argument_suggestions(
         }
         if let Some(short) = arg.get_short() {
             let short_flag = format!("-{}", short);
-            if short_flag.starts_with(current_word) {
-                if !suggestions.iter().any(|s| s.value == short_flag) {
-                    suggestions.push(CompletionSuggestion {
-                        value: short_flag,
-                        description: arg.get_help().map(|s| s.to_string()),
-                        start_pos: span_start,
-                        end_pos: span_end,
-                    });
-                }
+            if short_flag.starts_with(current_word)
+                && !suggestions.iter().any(|s| s.value == short_flag)
+            {
+                suggestions.push(CompletionSuggestion {
+                    value: short_flag,
+                    description: arg.get_help().map(|s| s.to_string()),
+                    start_pos: span_start,
+                    end_pos: span_end,
+                });
             }
         }
     }
@@ -0,0 +1,61 @@
+use axum::extract::Query;
+use axum::Json;
+use service_kit::{api, api_dto, ApiDocBuilder};
+
+/// Query parameters for searching products.
+#[api_dto]
+pub struct ProductSearch {
+    pub q: String,
+}
+
+/// Search products
+#[api(GET, "/v1/products/search", tags = ["catalog"], operation_id = "catalog.search")]
+pub async fn search_products(Query(search): Query<ProductSearch>) -> Json<String> {
+    Json(search.q)
+}
+
+/// Service information
+#[api(GET, "/v1/info", tags = ["ops"])]
+pub async fn info() -> Json<String> {
+    Json("products".to_string())
+}
+
+/// Count products
+#[api(GET, "/v1/products/count")]
+pub async fn count_products() -> Json<u64> {
+    Json(0)
+}
+
+#[test]
+fn test_api_doc_builder_sets_top_level_fields() {
+    let openapi = ApiDocBuilder::new("Products", "1.2.3")
+        .description("Product endpoints")
+        .terms_of_service("https://example.com/terms")
+        .contact("Catalog team", "", "catalog@example.com")
+        .license("MIT", "https://opensource.org/licenses/MIT")
+        .server("https://api.example.com", "Production")
+        .server("http://127.0.0.1:3000", "")
+        .tag("ops", "Operational endpoints")
+        .external_docs("https://docs.example.com", "Guide")
+        .extension("x-audience", serde_json::json!("internal"))
+        .customize(|openapi| openapi.info.title.push_str(" (beta)"))
+        .build();
+    let spec = serde_json::to_value(&openapi).unwrap();
+
+    assert_eq!(spec["info"]["title"], "Products (beta)");
+    assert_eq!(spec["info"]["description"], "Product endpoints");
+    assert_eq!(spec["info"]["termsOfService"], "https://example.com/terms");
+    assert_eq!(spec["info"]["contact"], serde_json::json!({ "name": "Catalog team", "email": "catalog@example.com" }));
+    assert_eq!(spec["info"]["license"]["url"], "https://opensource.org/licenses/MIT");
+    assert_eq!(spec["servers"][0], serde_json::json!({ "url": "https://api.example.com", "description": "Production" }));
+    assert_eq!(spec["servers"][1], serde_json::json!({ "url": "http://127.0.0.1:3000" }));
+    // Declared tags first, then every other tag used by an operation
+    assert_eq!(spec["tags"], serde_json::json!([
+        { "name": "ops", "description": "Operational endpoints" },
+        { "name": "App" },
+        { "name": "catalog" },
+    ]));
+    assert_eq!(spec["externalDocs"], serde_json::json!({ "url": "https://docs.example.com", "description": "Guide" }));
+    assert_eq!(spec["x-audience"], "internal");
+    assert_eq!(spec["paths"]["/v1/products/search"]["get"]["operationId"], "catalog.search");
+}
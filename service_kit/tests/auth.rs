@@ -0,0 +1,70 @@
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::Json;
+use service_kit::auth::Credentials;
+use service_kit::error::Error;
+use service_kit::rest_router_builder::RestRouterBuilder;
+use service_kit::{api, api_dto};
+use tower::ServiceExt;
+
+#[api_dto]
+pub struct Greeting {
+    pub message: String,
+}
+
+/// Get a product by its ID
+#[api(GET, "/v1/products/{id}")]
+pub async fn get_product(Path(id): Path<String>) -> Json<Greeting> {
+    Json(Greeting { message: id })
+}
+
+/// Delete a product
+#[api(DELETE, "/v1/products/{id}", security = "bearer")]
+pub async fn delete_product(Path(id): Path<String>) -> Json<Greeting> {
+    Json(Greeting {
+        message: format!("Deleted {}", id),
+    })
+}
+
+#[test]
+fn test_security_requirement_is_documented() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let spec = serde_json::to_value(&openapi).unwrap();
+
+    assert_eq!(spec["components"]["securitySchemes"]["bearer"]["scheme"], "bearer");
+    assert_eq!(spec["paths"]["/v1/products/{id}"]["delete"]["security"], serde_json::json!([{ "bearer": [] }]));
+    assert!(spec["paths"]["/v1/products/{id}"]["get"].get("security").is_none());
+}
+
+#[tokio::test]
+async fn test_rest_router_rejects_missing_or_invalid_tokens() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let router = RestRouterBuilder::new()
+        .openapi(openapi)
+        .with_authenticator(|_: &str, credentials: &Credentials| credentials.token == "secret")
+        .build()
+        .unwrap();
+    let delete = |token: Option<&str>| {
+        let mut request = axum::http::Request::delete("/v1/products/prod-042");
+        if let Some(token) = token {
+            request = request.header("authorization", format!("Bearer {}", token));
+        }
+        request.body(axum::body::Body::empty()).unwrap()
+    };
+
+    let missing = router.clone().oneshot(delete(None)).await.unwrap();
+    assert_eq!(missing.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(missing.headers()["www-authenticate"], "Bearer");
+    let invalid = router.clone().oneshot(delete(Some("wrong"))).await.unwrap();
+    assert_eq!(invalid.status(), StatusCode::UNAUTHORIZED);
+    let accepted = router.oneshot(delete(Some("secret"))).await.unwrap();
+    assert_eq!(accepted.status(), StatusCode::OK);
+}
+
+#[test]
+fn test_secured_operations_require_an_authenticator() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let result = RestRouterBuilder::new().openapi(openapi).build();
+
+    assert!(matches!(result, Err(Error::SpecError(msg)) if msg.contains("delete_product")));
+}
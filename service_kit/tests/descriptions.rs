@@ -0,0 +1,94 @@
+use axum::extract::{Path, Query};
+use axum::http::HeaderMap;
+use axum::Json;
+use service_kit::{api, api_dto};
+
+#[api_dto]
+pub struct Greeting {
+    pub message: String,
+}
+
+/// Query parameters for searching products.
+#[api_dto]
+pub struct ProductSearch {
+    /// Upper bound on the product price.
+    pub max_price: Option<f64>,
+}
+
+/// DTO for updating a product.
+#[api_dto]
+pub struct ProductUpdate {
+    pub name: Option<String>,
+}
+
+/// Get a product by its ID
+#[api(GET, "/v1/products/{id}", params(id = "Product identifier"))]
+pub async fn get_product(Path(id): Path<String>) -> Json<Greeting> {
+    Json(Greeting { message: id })
+}
+
+/// Update a product
+#[api(PATCH, "/v1/products/{id}")]
+pub async fn update_product(
+    /// Product identifier
+    Path(id): Path<String>,
+    /// Fields to change; omitted fields are left untouched
+    Json(payload): Json<ProductUpdate>,
+) -> Json<Greeting> {
+    Json(Greeting {
+        message: format!("{} {}", id, payload.name.unwrap_or_default()),
+    })
+}
+
+/// Get the tenant's catalog name
+#[api(
+    GET,
+    "/v1/tenant/catalog",
+    headers(x_tenant_id: String),
+    params(x_tenant_id = "Tenant whose catalog is returned")
+)]
+pub async fn get_tenant_catalog(headers: HeaderMap) -> Json<Greeting> {
+    Json(Greeting {
+        message: format!("{} headers", headers.len()),
+    })
+}
+
+/// Search products
+#[api(GET, "/v1/products/search")]
+pub async fn search_products(Query(search): Query<ProductSearch>) -> Json<Option<f64>> {
+    Json(search.max_price)
+}
+
+#[test]
+fn test_parameter_and_body_descriptions_are_collected() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let description_of = |path: &str, method: &str, name: &str| {
+        let item = &openapi.paths.paths[path];
+        let operation = match method {
+            "get" => item.get.as_ref(),
+            _ => item.patch.as_ref(),
+        }
+        .unwrap();
+        operation
+            .parameters
+            .as_ref()
+            .unwrap()
+            .iter()
+            .find(|p| p.name == name)
+            .and_then(|p| p.description.clone())
+    };
+
+    assert_eq!(description_of("/v1/products/{id}", "get", "id").as_deref(), Some("Product identifier"));
+    assert_eq!(description_of("/v1/products/{id}", "patch", "id").as_deref(), Some("Product identifier"));
+    assert_eq!(
+        description_of("/v1/tenant/catalog", "get", "x-tenant-id").as_deref(),
+        Some("Tenant whose catalog is returned")
+    );
+    assert_eq!(
+        description_of("/v1/products/search", "get", "maxPrice").as_deref(),
+        Some("Upper bound on the product price.")
+    );
+
+    let body = openapi.paths.paths["/v1/products/{id}"].patch.as_ref().unwrap().request_body.as_ref().unwrap();
+    assert_eq!(body.description.as_deref(), Some("Fields to change; omitted fields are left untouched"));
+}
@@ -0,0 +1,103 @@
+use axum::extract::{Path, Query};
+use axum::Json;
+use service_kit::handler::{ApiState, RawBody};
+use service_kit::{api, api_dto};
+
+/// Lifecycle status of a product.
+#[api_dto]
+pub enum ProductStatus {
+    Draft,
+    Active,
+    Discontinued,
+}
+
+/// Query parameters for the paginated product listing.
+#[api_dto]
+pub struct ProductPageQuery {
+    #[serde(default)]
+    pub page: u32,
+    pub status: Option<ProductStatus>,
+}
+
+/// One page of a paginated listing.
+#[api_dto]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub page: u32,
+    pub total: u64,
+}
+
+#[api_dto]
+pub struct Product {
+    pub id: String,
+}
+
+/// A pricing rule; the `type` field selects the kind of rule.
+#[api_dto]
+#[serde(tag = "type")]
+pub enum PriceRule {
+    FixedDiscount { amount: f64 },
+    PercentOff { percent: f64, max_discount: Option<f64> },
+}
+
+/// List products page by page
+#[api(GET, "/v1/products/paged")]
+pub async fn list_products_paged(Query(query): Query<ProductPageQuery>) -> Json<Page<Product>> {
+    let items = match query.status {
+        None | Some(ProductStatus::Active) => vec![Product { id: "prod-001".to_string() }],
+        Some(_) => Vec::new(),
+    };
+    let total = items.len() as u64;
+    Json(Page { items, page: query.page, total })
+}
+
+/// Quote a product price under a pricing rule
+#[api(POST, "/v1/products/{id}/price-quote")]
+pub async fn quote_price(Path(id): Path<String>, Json(rule): Json<PriceRule>) -> Json<(String, f64)> {
+    let list_price = 99.99;
+    let price = match rule {
+        PriceRule::FixedDiscount { amount } => list_price - amount,
+        PriceRule::PercentOff { percent, max_discount } => {
+            let discount = list_price * percent / 100.0;
+            list_price - max_discount.map_or(discount, |max| discount.min(max))
+        }
+    };
+    Json((id, price))
+}
+
+async fn body_json(response: axum::response::Response) -> serde_json::Value {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[test]
+fn test_enum_and_generic_dto_schemas_are_registered() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let spec = serde_json::to_value(&openapi).unwrap();
+    let schemas = &spec["components"]["schemas"];
+
+    assert_eq!(schemas["ProductStatus"]["enum"], serde_json::json!(["draft", "active", "discontinued"]));
+    assert_eq!(schemas["PriceRule"]["discriminator"]["propertyName"], "type");
+    assert!(schemas["PriceRule"]["oneOf"][1]["properties"]["maxDiscount"].is_object());
+    assert_eq!(schemas["Page_Product"]["properties"]["items"]["type"], "array");
+    let response = &spec["paths"]["/v1/products/paged"]["get"]["responses"]["200"]["content"]["application/json"];
+    assert_eq!(response["schema"]["$ref"], "#/components/schemas/Page_Product");
+}
+
+#[tokio::test]
+async fn test_tagged_union_body_is_deserialized() {
+    let params = serde_json::json!({ "id": "prod-1", "type": "percentOff", "percent": 50.0, "maxDiscount": 10.0 });
+    let response = __API_EXEC_QUOTE_PRICE(&params, &ApiState::default(), RawBody::default()).await.unwrap();
+
+    assert_eq!(body_json(response).await, serde_json::json!(["prod-1", 89.99]));
+}
+
+#[tokio::test]
+async fn test_unit_enum_query_field_is_deserialized() {
+    let params = serde_json::json!({ "status": "discontinued" });
+    let response = __API_EXEC_LIST_PRODUCTS_PAGED(&params, &ApiState::default(), RawBody::default()).await.unwrap();
+    let json_value = body_json(response).await;
+
+    assert_eq!(json_value["total"], 0);
+    assert_eq!(json_value["items"], serde_json::json!([]));
+}
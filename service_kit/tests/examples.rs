@@ -0,0 +1,94 @@
+use axum::extract::{Path, Query};
+use axum::Json;
+use service_kit::{api, api_dto};
+
+/// Parameters for adding two numbers.
+#[api_dto(example = json!({ "a": 1.5, "b": 2.5 }))]
+pub struct AddParams {
+    pub a: f64,
+    pub b: f64,
+}
+
+/// Query parameters for searching products.
+#[api_dto]
+pub struct ProductSearch {
+    #[example = "Example"]
+    pub q: String,
+}
+
+/// DTO for updating a product.
+#[api_dto]
+pub struct ProductUpdate {
+    #[example = "P-12345"]
+    pub product_code: Option<String>,
+}
+
+/// A pricing rule; the `type` field selects the kind of rule.
+#[api_dto]
+#[serde(tag = "type")]
+pub enum PriceRule {
+    FixedDiscount { amount: f64 },
+    PercentOff { percent: f64, max_discount: Option<f64> },
+}
+
+/// Add two numbers
+#[api(GET, "/v1/add")]
+pub async fn add(Query(params): Query<AddParams>) -> Json<f64> {
+    Json(params.a + params.b)
+}
+
+/// Search products
+#[api(GET, "/v1/products/search", operation_id = "catalog.search")]
+pub async fn search_products(Query(search): Query<ProductSearch>) -> Json<String> {
+    Json(search.q)
+}
+
+/// Update a product
+#[api(PATCH, "/v1/products/{id}")]
+pub async fn update_product(Path(id): Path<String>, Json(update): Json<ProductUpdate>) -> Json<String> {
+    Json(format!("{} {}", id, update.product_code.unwrap_or_default()))
+}
+
+/// Quote a product price under a pricing rule
+#[api(
+    POST,
+    "/v1/products/{id}/price-quote",
+    request_example = json!({ "type": "percentOff", "percent": 10.0, "maxDiscount": 5.0 }),
+    response_example = json!({ "price": 94.99 })
+)]
+pub async fn quote_price(Path(id): Path<String>, Json(rule): Json<PriceRule>) -> Json<serde_json::Value> {
+    let price = match rule {
+        PriceRule::FixedDiscount { amount } => 99.99 - amount,
+        PriceRule::PercentOff { percent, .. } => 99.99 * (1.0 - percent / 100.0),
+    };
+    Json(serde_json::json!({ "id": id, "price": price }))
+}
+
+#[test]
+fn test_examples_are_documented() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let spec = serde_json::to_value(&openapi).unwrap();
+    let schemas = &spec["components"]["schemas"];
+    let quote = &spec["paths"]["/v1/products/{id}/price-quote"]["post"];
+
+    assert_eq!(schemas["AddParams"]["example"], serde_json::json!({ "a": 1.5, "b": 2.5 }));
+    assert_eq!(schemas["ProductUpdate"]["properties"]["productCode"]["example"], "P-12345");
+    assert_eq!(quote["requestBody"]["content"]["application/json"]["example"]["type"], "percentOff");
+    assert_eq!(quote["responses"]["200"]["content"]["application/json"]["example"]["price"], 94.99);
+}
+
+#[cfg(feature = "mcp")]
+#[test]
+fn test_examples_are_included_in_mcp_tool_descriptions() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let router = service_kit::openapi_to_mcp::OpenApiMcpRouterBuilder::new().openapi(openapi).build::<()>().unwrap();
+    let description_of = |name: &str| {
+        let tools = router.list_all();
+        let tool = tools.iter().find(|tool| tool.name == name).unwrap();
+        tool.description.as_deref().unwrap_or_default().to_string()
+    };
+
+    assert!(description_of("quote_price")
+        .ends_with(r#"Example arguments: {"maxDiscount":5.0,"percent":10.0,"type":"percentOff"}"#));
+    assert!(description_of("catalog.search").ends_with(r#"Example arguments: {"q":"Example"}"#));
+}
@@ -0,0 +1,92 @@
+use axum::http::{HeaderMap, StatusCode};
+use axum::Json;
+use axum_extra::{headers::UserAgent, TypedHeader};
+use service_kit::error::Error;
+use service_kit::handler::{ApiState, RawBody};
+use service_kit::rest_router_builder::RestRouterBuilder;
+use service_kit::{api, api_dto};
+use tower::ServiceExt;
+
+#[api_dto]
+pub struct Greeting {
+    pub message: String,
+}
+
+/// Get the tenant's catalog name
+#[api(
+    GET,
+    "/v1/tenant/catalog",
+    headers(x_tenant_id: String, x_request_id: Option<String>),
+    params(x_tenant_id = "Tenant whose catalog is returned")
+)]
+pub async fn get_tenant_catalog(headers: HeaderMap) -> Json<Greeting> {
+    let tenant = headers.get("x-tenant-id").and_then(|v| v.to_str().ok()).unwrap_or_default();
+    Json(Greeting {
+        message: format!("Catalog of tenant {}", tenant),
+    })
+}
+
+/// Echo the caller's user agent
+#[api(GET, "/v1/whoami")]
+pub async fn whoami(TypedHeader(agent): TypedHeader<UserAgent>) -> Json<Greeting> {
+    Json(Greeting {
+        message: format!("Hello, {}", agent.as_str()),
+    })
+}
+
+async fn body_json(response: axum::response::Response) -> serde_json::Value {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn test_declared_header_is_read_from_flat_params() {
+    let params = serde_json::json!({ "x-tenant-id": "acme" });
+    let response = __API_EXEC_GET_TENANT_CATALOG(&params, &ApiState::default(), RawBody::default()).await.unwrap();
+
+    assert_eq!(body_json(response).await["message"], "Catalog of tenant acme");
+}
+
+#[tokio::test]
+async fn test_missing_required_header_is_a_bad_request() {
+    let result = __API_EXEC_GET_TENANT_CATALOG(&serde_json::json!({}), &ApiState::default(), RawBody::default()).await;
+
+    assert!(matches!(result, Err(Error::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_rest_query_and_body_cannot_spoof_headers() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let router = RestRouterBuilder::new().openapi(openapi).build().unwrap();
+    let catalog = |uri: &str, tenant: Option<&str>| {
+        let mut request = axum::http::Request::get(uri);
+        if let Some(tenant) = tenant {
+            request = request.header("x-tenant-id", tenant);
+        }
+        request.body(axum::body::Body::empty()).unwrap()
+    };
+
+    let spoofed = router.clone().oneshot(catalog("/v1/tenant/catalog?x-tenant-id=evil", None)).await.unwrap();
+    assert_eq!(spoofed.status(), StatusCode::BAD_REQUEST);
+    let forged_meta = router
+        .clone()
+        .oneshot(catalog("/v1/tenant/catalog?__headers=evil", None))
+        .await
+        .unwrap();
+    assert_eq!(forged_meta.status(), StatusCode::BAD_REQUEST);
+
+    let accepted = router
+        .oneshot(catalog("/v1/tenant/catalog?x-tenant-id=evil", Some("acme")))
+        .await
+        .unwrap();
+    assert_eq!(accepted.status(), StatusCode::OK);
+    assert_eq!(body_json(accepted).await["message"], "Catalog of tenant acme");
+}
+
+#[tokio::test]
+async fn test_typed_header_is_decoded_from_request_headers() {
+    let params = serde_json::json!({ "__headers": { "user-agent": "forge-cli/0.1" } });
+    let response = __API_EXEC_WHOAMI(&params, &ApiState::default(), RawBody::default()).await.unwrap();
+
+    assert_eq!(body_json(response).await["message"], "Hello, forge-cli/0.1");
+}
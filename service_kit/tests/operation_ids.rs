@@ -0,0 +1,27 @@
+use axum::Json;
+use service_kit::api;
+
+/// Search products
+#[api(GET, "/v1/products/search", operation_id = "catalog.search")]
+pub async fn search_products() -> Json<Vec<String>> {
+    Json(Vec::new())
+}
+
+/// List all products
+#[api(GET, "/v1/products")]
+pub async fn list_products() -> Json<Vec<String>> {
+    Json(Vec::new())
+}
+
+#[test]
+fn test_explicit_operation_id_is_registered() {
+    let handlers = service_kit::handler::collect_api_handlers().unwrap();
+
+    assert!(handlers.contains_key("catalog.search"));
+    assert!(!handlers.contains_key("search_products"));
+    assert!(handlers.contains_key("list_products"));
+
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let spec = serde_json::to_value(&openapi).unwrap();
+    assert_eq!(spec["paths"]["/v1/products/search"]["get"]["operationId"], "catalog.search");
+}
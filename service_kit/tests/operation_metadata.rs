@@ -0,0 +1,40 @@
+use axum::Json;
+use service_kit::api;
+
+/// List all products
+#[api(GET, "/v1/products", tags = ["catalog"])]
+pub async fn list_products() -> Json<Vec<String>> {
+    Json(Vec::new())
+}
+
+/// Get a product count
+#[api(GET, "/v1/products/count")]
+pub async fn count_products() -> Json<u64> {
+    Json(0)
+}
+
+/// Add two numbers
+#[api(GET, "/v1/add", deprecated)]
+pub async fn add() -> Json<f64> {
+    Json(0.0)
+}
+
+/// Get service info
+#[api(GET, "/v1/info", extensions(x_internal = true, x_lint_allow = ["missing-error-response"]))]
+pub async fn service_info() -> Json<String> {
+    Json("ok".to_string())
+}
+
+#[test]
+fn test_tags_deprecation_and_extensions_are_emitted() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let spec = serde_json::to_value(&openapi).unwrap();
+    let paths = &spec["paths"];
+
+    assert_eq!(paths["/v1/products"]["get"]["tags"], serde_json::json!(["catalog"]));
+    assert_eq!(paths["/v1/products/count"]["get"]["tags"], serde_json::json!(["App"]));
+    assert_eq!(paths["/v1/add"]["get"]["deprecated"], true);
+    assert!(paths["/v1/products"]["get"].get("deprecated").is_none());
+    assert_eq!(paths["/v1/info"]["get"]["x-internal"], true);
+    assert_eq!(paths["/v1/info"]["get"]["x-lint-allow"], serde_json::json!(["missing-error-response"]));
+}
@@ -0,0 +1,125 @@
+use axum::extract::Query;
+use axum::Json;
+use service_kit::error::Error;
+use service_kit::handler::{ApiState, RawBody};
+use service_kit::rest_router_builder::RestRouterBuilder;
+use service_kit::{api, api_dto};
+use tower::ServiceExt;
+
+/// Query parameters for searching products.
+#[api_dto]
+pub struct ProductSearch {
+    pub q: String,
+    pub category_id: Option<String>,
+    #[serde(default)]
+    pub page: u32,
+    pub max_price: Option<f64>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub sort: String,
+}
+
+/// Search products
+#[api(GET, "/v1/products/search")]
+pub async fn search_products(Query(search): Query<ProductSearch>) -> Json<Vec<String>> {
+    Json(vec![search.q, search.page.to_string(), search.sort])
+}
+
+/// Paging controls; the whole query may be omitted.
+#[api_dto]
+pub struct Paging {
+    #[validate(range(min = 1))]
+    pub per_page: u32,
+    pub cursor: Option<String>,
+}
+
+/// List products, optionally paged
+#[api(GET, "/v1/products")]
+pub async fn list_products(paging: Option<Query<Paging>>) -> Json<Option<u32>> {
+    Json(paging.map(|Query(paging)| paging.per_page))
+}
+
+async fn body_json(response: axum::response::Response) -> serde_json::Value {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[test]
+fn test_optional_query_fields_are_not_required() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let operation = openapi.paths.paths["/v1/products/search"].get.as_ref().unwrap();
+    let mut required: Vec<(&str, bool)> = operation
+        .parameters
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|p| (p.name.as_str(), matches!(p.required, utoipa::openapi::Required::True)))
+        .collect();
+    required.sort();
+
+    assert_eq!(
+        required,
+        vec![("categoryId", false), ("maxPrice", false), ("page", false), ("q", true), ("sort", false)]
+    );
+}
+
+#[test]
+fn test_skipped_fields_are_required_unless_they_have_a_default() {
+    #[service_kit::api_dto]
+    struct SkippedField {
+        id: String,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        tags: Vec<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        labels: Vec<String>,
+    }
+
+    let schema = serde_json::to_value(<SkippedField as utoipa::PartialSchema>::schema()).unwrap();
+    assert_eq!(schema["required"], serde_json::json!(["id", "tags"]));
+    assert!(serde_json::from_value::<SkippedField>(serde_json::json!({ "id": "a" })).is_err());
+    assert!(serde_json::from_value::<SkippedField>(serde_json::json!({ "id": "a", "tags": [] })).is_ok());
+}
+
+#[tokio::test]
+async fn test_query_with_only_required_fields() {
+    let params = serde_json::json!({ "q": "Product" });
+    let response = __API_EXEC_SEARCH_PRODUCTS(&params, &ApiState::default(), RawBody::default()).await.unwrap();
+
+    assert_eq!(body_json(response).await, serde_json::json!(["Product", "0", ""]));
+}
+
+#[tokio::test]
+async fn test_optional_query_is_none_only_without_its_fields() {
+    let list = |params: serde_json::Value| async move {
+        __API_EXEC_LIST_PRODUCTS(&params, &ApiState::default(), RawBody::default()).await
+    };
+
+    let absent = list(serde_json::json!({ "__headers": { "accept": "*/*" } })).await.unwrap();
+    assert_eq!(body_json(absent).await, serde_json::Value::Null);
+    let present = list(serde_json::json!({ "perPage": 5 })).await.unwrap();
+    assert_eq!(body_json(present).await, 5);
+
+    // Malformed or invalid queries are reported instead of being treated as absent
+    assert!(matches!(list(serde_json::json!({ "cursor": "abc" })).await, Err(Error::BadRequest(_))));
+    assert!(matches!(list(serde_json::json!({ "perPage": "five" })).await, Err(Error::BadRequest(_))));
+    assert!(matches!(list(serde_json::json!({ "perPage": 0 })).await, Err(Error::Validation(_))));
+}
+
+#[tokio::test]
+async fn test_rest_query_values_follow_the_declared_types() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let router = RestRouterBuilder::new().openapi(openapi).build().unwrap();
+    let get = |uri: &str| {
+        let request = axum::http::Request::get(uri).body(axum::body::Body::empty()).unwrap();
+        router.clone().oneshot(request)
+    };
+
+    // String fields keep their text even when it looks like a number or a boolean
+    let response = get("/v1/products/search?q=01234&page=2&sort=true&maxPrice=9.5").await.unwrap();
+    assert_eq!(response.status(), axum::http::StatusCode::OK);
+    assert_eq!(body_json(response).await, serde_json::json!(["01234", "2", "true"]));
+
+    let response = get("/v1/products/search?q=a&page=two").await.unwrap();
+    assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
+    let response = get("/v1/products?perPage=0").await.unwrap();
+    assert_eq!(response.status(), axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+}
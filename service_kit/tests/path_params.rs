@@ -0,0 +1,66 @@
+use axum::extract::Path;
+use axum::response::IntoResponse;
+use axum::Json;
+use service_kit::error::Error;
+use service_kit::handler::{ApiState, RawBody};
+use service_kit::{api, api_dto};
+
+#[api_dto]
+pub struct Greeting {
+    pub message: String,
+}
+
+/// Get a product within a category
+#[api(GET, "/v1/categories/{category_id}/products/{index}")]
+pub async fn get_category_product(Path((category_id, index)): Path<(String, u32)>) -> Json<Greeting> {
+    Json(Greeting {
+        message: format!("{}-{}", category_id, index),
+    })
+}
+
+/// Get a product review
+#[api(GET, "/v1/products/{id}/reviews/{review_id}")]
+pub async fn get_product_review(path: Path<(String, u32)>) -> Json<Greeting> {
+    let (id, review_id) = path.0;
+    Json(Greeting {
+        message: format!("Review {} of {}", review_id, id),
+    })
+}
+
+async fn body_json(response: axum::response::Response) -> serde_json::Value {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn test_typed_tuple_path_params_are_deserialized() {
+    let params = serde_json::json!({ "category_id": "cat-01", "index": "7" });
+    let response = __API_EXEC_GET_CATEGORY_PRODUCT(&params, &ApiState::default(), RawBody::default()).await.unwrap();
+
+    assert_eq!(body_json(response).await["message"], "cat-01-7");
+}
+
+#[tokio::test]
+async fn test_undestructured_tuple_path_binds_placeholders_by_position() {
+    let params = serde_json::json!({ "id": "prod-001", "review_id": "3" });
+    let response = __API_EXEC_GET_PRODUCT_REVIEW(&params, &ApiState::default(), RawBody::default()).await.unwrap();
+    assert_eq!(body_json(response).await["message"], "Review 3 of prod-001");
+
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let spec = serde_json::to_value(&openapi).unwrap();
+    let parameters = &spec["paths"]["/v1/products/{id}/reviews/{review_id}"]["get"]["parameters"];
+    assert_eq!(parameters[0]["name"], "id");
+    assert_eq!(parameters[0]["schema"]["type"], "string");
+    assert_eq!(parameters[1]["name"], "review_id");
+    assert_eq!(parameters[1]["schema"]["type"], "integer");
+}
+
+#[tokio::test]
+async fn test_invalid_path_param_is_a_bad_request() {
+    let params = serde_json::json!({ "category_id": "cat-01", "index": "seven" });
+    let result = __API_EXEC_GET_CATEGORY_PRODUCT(&params, &ApiState::default(), RawBody::default()).await;
+
+    let err = result.expect_err("expected a bad request error");
+    assert!(matches!(err, Error::BadRequest(_)));
+    assert_eq!(err.into_response().status(), axum::http::StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,70 @@
+use axum::Json;
+use service_kit::{api, api_dto};
+
+#[api_dto]
+pub struct Category {
+    pub id: String,
+    /// A category can have a parent, creating a recursive structure.
+    pub parent: Option<Box<Category>>,
+}
+
+/// A node of the category tree.
+#[api_dto]
+pub struct CategoryTree {
+    pub id: String,
+    pub children: Vec<CategoryTree>,
+}
+
+/// A set of products sold together.
+#[api_dto]
+pub struct Bundle {
+    pub name: String,
+    pub items: Vec<BundleItem>,
+}
+
+/// An entry of a bundle; either a product or a nested bundle.
+#[api_dto]
+pub struct BundleItem {
+    pub product_id: Option<String>,
+    pub bundle: Option<Bundle>,
+}
+
+/// Get the category tree
+#[api(GET, "/v1/categories/tree")]
+pub async fn get_category_tree() -> Json<CategoryTree> {
+    Json(CategoryTree { id: "cat-root".to_string(), children: Vec::new() })
+}
+
+/// Get a category
+#[api(GET, "/v1/categories/root")]
+pub async fn get_root_category() -> Json<Category> {
+    Json(Category { id: "cat-root".to_string(), parent: None })
+}
+
+/// Get a bundle
+#[api(GET, "/v1/bundles/root")]
+pub async fn get_bundle() -> Json<Bundle> {
+    Json(Bundle { name: "root".to_string(), items: Vec::new() })
+}
+
+#[test]
+fn test_recursive_dtos_are_referenced() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let spec = serde_json::to_value(&openapi).unwrap();
+    let schemas = &spec["components"]["schemas"];
+
+    assert_eq!(schemas["CategoryTree"]["properties"]["children"]["items"]["$ref"], "#/components/schemas/CategoryTree");
+    assert_eq!(schemas["Category"]["properties"]["parent"]["oneOf"][1]["$ref"], "#/components/schemas/Category");
+    assert!(!schemas["Category"]["required"].as_array().unwrap().contains(&serde_json::json!("parent")));
+    assert_eq!(schemas["Bundle"]["properties"]["items"]["items"]["$ref"], "#/components/schemas/BundleItem");
+    assert_eq!(schemas["BundleItem"]["properties"]["bundle"]["oneOf"][1]["$ref"], "#/components/schemas/Bundle");
+}
+
+#[test]
+fn test_recursive_dto_schema_collection_terminates() {
+    let mut collected = Vec::new();
+    <Bundle as utoipa::ToSchema>::schemas(&mut collected);
+    <CategoryTree as utoipa::ToSchema>::schemas(&mut collected);
+
+    assert!(collected.is_empty());
+}
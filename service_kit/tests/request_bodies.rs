@@ -0,0 +1,190 @@
+use axum::body::Bytes;
+use axum::extract::{Multipart, Path};
+use axum::http::StatusCode;
+use axum::{Form, Json};
+use service_kit::handler::{ApiState, RawBody};
+use service_kit::rest_router_builder::RestRouterBuilder;
+use service_kit::{api, api_dto};
+use tower::ServiceExt;
+
+#[api_dto]
+pub struct Greeting {
+    pub message: String,
+}
+
+/// A newsletter subscription submitted as an urlencoded form.
+#[api_dto]
+pub struct Subscription {
+    pub email: String,
+    #[serde(default)]
+    pub weekly: bool,
+    pub zip: Option<String>,
+}
+
+/// A file received through a multipart upload.
+#[api_dto]
+pub struct UploadedFile {
+    pub field: String,
+    pub file_name: Option<String>,
+    pub size: u64,
+}
+
+/// Subscribe to the newsletter
+#[api(POST, "/v1/newsletter")]
+pub async fn subscribe(Form(subscription): Form<Subscription>) -> Json<Greeting> {
+    let cadence = if subscription.weekly { "weekly" } else { "monthly" };
+    let edition = subscription.zip.map(|zip| format!(" for zip {}", zip)).unwrap_or_default();
+    Json(Greeting {
+        message: format!("Subscribed {} to the {} newsletter{}", subscription.email, cadence, edition),
+    })
+}
+
+/// Upload product images
+#[api(POST, "/v1/products/{id}/images")]
+pub async fn upload_product_images(Path(id): Path<String>, mut multipart: Multipart) -> Json<Vec<UploadedFile>> {
+    let mut files = Vec::new();
+    while let Some(mut field) = multipart.next_field().await.unwrap() {
+        let name = field.name().unwrap_or_default().to_string();
+        let file_name = field.file_name().map(str::to_string);
+        let mut size = 0;
+        while let Some(chunk) = field.chunk().await.unwrap() {
+            size += chunk.len() as u64;
+        }
+        files.push(UploadedFile { field: name, file_name, size });
+    }
+    assert_eq!(id, "prod-001");
+    Json(files)
+}
+
+/// Replace a product thumbnail
+#[api(PUT, "/v1/products/{id}/thumbnail")]
+pub async fn put_product_thumbnail(Path(id): Path<String>, image: Bytes) -> Json<Greeting> {
+    Json(Greeting {
+        message: format!("Stored {} bytes for {}", image.len(), id),
+    })
+}
+
+/// Import products from CSV
+#[api(POST, "/v1/products/import")]
+pub async fn import_products(csv: String) -> Json<Greeting> {
+    let count = csv.lines().skip(1).filter(|line| !line.trim().is_empty()).count();
+    Json(Greeting {
+        message: format!("Imported {} products", count),
+    })
+}
+
+/// Echo a greeting
+#[api(POST, "/v1/greetings")]
+pub async fn echo_greeting(Json(greeting): Json<Greeting>) -> Json<Greeting> {
+    Json(greeting)
+}
+
+async fn body_json(response: axum::response::Response) -> serde_json::Value {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn test_form_body_keeps_string_values() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let router = RestRouterBuilder::new().openapi(openapi).build().unwrap();
+    let request = axum::http::Request::post("/v1/newsletter")
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body(axum::body::Body::from("email=a%40example.com&weekly=true&zip=01234"))
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        body_json(response).await["message"],
+        "Subscribed a@example.com to the weekly newsletter for zip 01234"
+    );
+}
+
+#[tokio::test]
+async fn test_multipart_is_streamed_from_the_raw_body() {
+    let body = "--XYZ\r\nContent-Disposition: form-data; name=\"image\"; filename=\"a.png\"\r\n\r\n12345\r\n--XYZ--\r\n";
+    let params = serde_json::json!({
+        "id": "prod-001",
+        "__headers": { "content-type": "multipart/form-data; boundary=XYZ" },
+    });
+    let response = __API_EXEC_UPLOAD_PRODUCT_IMAGES(&params, &ApiState::default(), RawBody::new(body.into()))
+        .await
+        .unwrap();
+    let json_value = body_json(response).await;
+
+    assert_eq!(json_value[0]["fileName"], "a.png");
+    assert_eq!(json_value[0]["size"], 5);
+}
+
+#[tokio::test]
+async fn test_multipart_is_rebuilt_from_flat_arguments() {
+    let params = serde_json::json!({ "id": "prod-001", "image": "abc" });
+    let response = __API_EXEC_UPLOAD_PRODUCT_IMAGES(&params, &ApiState::default(), RawBody::default())
+        .await
+        .unwrap();
+    let json_value = body_json(response).await;
+
+    assert_eq!(json_value[0]["field"], "image");
+    assert_eq!(json_value[0]["size"], 3);
+}
+
+#[tokio::test]
+async fn test_raw_bodies_are_read() {
+    let params = serde_json::json!({ "id": "prod-001" });
+    let response = __API_EXEC_PUT_PRODUCT_THUMBNAIL(&params, &ApiState::default(), RawBody::new(vec![0u8; 4].into()))
+        .await
+        .unwrap();
+    assert_eq!(body_json(response).await["message"], "Stored 4 bytes for prod-001");
+
+    let params = serde_json::json!({ "body": "id,name\np1,One\np2,Two\n" });
+    let response = __API_EXEC_IMPORT_PRODUCTS(&params, &ApiState::default(), RawBody::default()).await.unwrap();
+    assert_eq!(body_json(response).await["message"], "Imported 2 products");
+}
+
+#[test]
+fn test_request_body_content_types_are_documented() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let spec = serde_json::to_value(&openapi).unwrap();
+    let content_types = |path: &str, method: &str| -> Vec<String> {
+        spec["paths"][path][method]["requestBody"]["content"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect()
+    };
+
+    assert_eq!(content_types("/v1/newsletter", "post"), vec!["application/x-www-form-urlencoded"]);
+    assert_eq!(content_types("/v1/products/{id}/images", "post"), vec!["multipart/form-data"]);
+    assert_eq!(content_types("/v1/products/{id}/thumbnail", "put"), vec!["application/octet-stream"]);
+    assert_eq!(content_types("/v1/products/import", "post"), vec!["text/plain"]);
+    assert_eq!(
+        spec["paths"]["/v1/products/{id}/thumbnail"]["put"]["requestBody"]["content"]["application/octet-stream"]["schema"]["format"],
+        "binary"
+    );
+}
+
+#[tokio::test]
+async fn test_oversized_bodies_are_rejected() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let router = RestRouterBuilder::new().openapi(openapi).body_limit(32).build().unwrap();
+    let send = |method: &str, uri: &str, content_type: &str, body: String| {
+        let request = axum::http::Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", content_type)
+            .body(axum::body::Body::from(body))
+            .unwrap();
+        router.clone().oneshot(request)
+    };
+
+    let small = send("PUT", "/v1/products/prod-001/thumbnail", "application/octet-stream", "x".repeat(32)).await.unwrap();
+    assert_eq!(small.status(), StatusCode::OK);
+    let large = send("PUT", "/v1/products/prod-001/thumbnail", "application/octet-stream", "x".repeat(33)).await.unwrap();
+    assert_eq!(large.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let json = serde_json::json!({ "message": "x".repeat(64) }).to_string();
+    let large = send("POST", "/v1/greetings", "application/json", json).await.unwrap();
+    assert_eq!(large.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
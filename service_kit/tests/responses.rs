@@ -0,0 +1,71 @@
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::Json;
+use service_kit::handler::{ApiState, RawBody};
+use service_kit::{api, api_dto};
+
+#[api_dto]
+pub struct Greeting {
+    pub message: String,
+}
+
+#[api_dto]
+pub struct ErrorBody {
+    pub code: String,
+    pub message: String,
+}
+
+/// Find a product by its product code
+#[api(GET, "/v1/products/by-code/{code}")]
+pub async fn find_product_by_code(Path(code): Path<String>) -> Option<Json<Greeting>> {
+    (code == "P-12345").then_some(Json(Greeting { message: code }))
+}
+
+/// Delete a product
+#[api(
+    DELETE,
+    "/v1/products/{id}",
+    responses((404, "Product not found", ErrorBody), (409, "Product is still referenced", ErrorBody))
+)]
+pub async fn delete_product(Path(id): Path<String>) -> Result<Json<Greeting>, (StatusCode, Json<ErrorBody>)> {
+    match id.as_str() {
+        "prod-001" => Err((
+            StatusCode::CONFLICT,
+            Json(ErrorBody {
+                code: "product_in_use".to_string(),
+                message: format!("Product {} is still referenced by an order", id),
+            }),
+        )),
+        _ => Ok(Json(Greeting {
+            message: format!("Deleted {}", id),
+        })),
+    }
+}
+
+#[tokio::test]
+async fn test_missing_optional_response_is_not_found() {
+    let params = serde_json::json!({ "code": "P-00000" });
+    let response = __API_EXEC_FIND_PRODUCT_BY_CODE(&params, &ApiState::default(), RawBody::default()).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_error_response_keeps_its_status() {
+    let params = serde_json::json!({ "id": "prod-001" });
+    let response = __API_EXEC_DELETE_PRODUCT(&params, &ApiState::default(), RawBody::default()).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
+
+#[test]
+fn test_declared_and_inferred_responses_are_registered() {
+    let metadata = service_kit::inventory::iter::<service_kit::ApiMetadata>
+        .into_iter()
+        .find(|m| m.operation_id == "delete_product")
+        .unwrap();
+    let statuses: Vec<u16> = metadata.responses.iter().map(|r| r.status_code).collect();
+
+    assert_eq!(statuses, vec![404, 409, 200, service_kit::DEFAULT_RESPONSE_STATUS]);
+    assert_eq!(metadata.responses[3].type_name, Some("ErrorBody"));
+}
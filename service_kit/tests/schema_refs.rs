@@ -0,0 +1,144 @@
+use axum::extract::{Path, Query};
+use axum::Json;
+use service_kit::{api, api_dto};
+
+#[api_dto]
+pub struct Category {
+    pub id: String,
+    pub name: String,
+}
+
+#[api_dto]
+pub struct Product {
+    pub id: String,
+    pub name: String,
+    pub category: Category,
+}
+
+/// DTO for updating a product.
+#[api_dto]
+pub struct ProductUpdate {
+    pub name: Option<String>,
+}
+
+/// Parameters for adding two numbers.
+#[api_dto]
+pub struct AddParams {
+    pub a: f64,
+    pub b: f64,
+}
+
+/// Lifecycle status of a product.
+#[api_dto]
+pub enum ProductStatus {
+    Draft,
+    Active,
+    Discontinued,
+}
+
+/// Query parameters for the paginated product listing.
+#[api_dto]
+pub struct ProductPageQuery {
+    #[serde(default)]
+    pub page: u32,
+    pub status: Option<ProductStatus>,
+}
+
+/// One page of a paginated listing.
+#[api_dto]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub page: u32,
+    pub total: u64,
+}
+
+/// A titled section of the catalog with the first page of its categories.
+#[api_dto]
+pub struct CatalogSection {
+    pub title: String,
+    pub categories: Page<Category>,
+}
+
+fn sample_category() -> Category {
+    Category { id: "cat-1".to_string(), name: "Tools".to_string() }
+}
+
+/// Update a product
+#[api(PATCH, "/v1/products/{id}")]
+pub async fn update_product(Path(id): Path<String>, Json(update): Json<ProductUpdate>) -> Json<Product> {
+    Json(Product { id, name: update.name.unwrap_or_default(), category: sample_category() })
+}
+
+/// Add two numbers
+#[api(GET, "/v1/add")]
+pub async fn add(Query(params): Query<AddParams>) -> Json<f64> {
+    Json(params.a + params.b)
+}
+
+/// List products page by page
+#[api(GET, "/v1/products/paged")]
+pub async fn list_products_paged(Query(query): Query<ProductPageQuery>) -> Json<Page<Product>> {
+    Json(Page { items: Vec::new(), page: query.page, total: 0 })
+}
+
+/// Get the featured catalog section
+#[api(GET, "/v1/catalog/featured")]
+pub async fn get_featured_section() -> Json<CatalogSection> {
+    let categories = Page { items: vec![sample_category()], page: 0, total: 1 };
+    Json(CatalogSection { title: "Featured".to_string(), categories })
+}
+
+#[test]
+fn test_dto_schemas_are_referenced_from_operations() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let spec = serde_json::to_value(&openapi).unwrap();
+    let schemas = &spec["components"]["schemas"];
+    let update = &spec["paths"]["/v1/products/{id}"]["patch"];
+
+    assert_eq!(update["requestBody"]["content"]["application/json"]["schema"], serde_json::json!({ "$ref": "#/components/schemas/ProductUpdate" }));
+    assert_eq!(update["responses"]["200"]["content"]["application/json"]["schema"]["$ref"], "#/components/schemas/Product");
+    assert_eq!(update["parameters"][0]["schema"]["type"], "string");
+    // Query DTOs are still flattened into one inline parameter per field
+    let add_params = &spec["paths"]["/v1/add"]["get"]["parameters"];
+    assert_eq!(add_params[0]["name"], "a");
+    assert_eq!(add_params[0]["schema"]["type"], "number");
+    // Generic instances nested in a DTO are registered so that their `$ref` resolves
+    assert_eq!(schemas["CatalogSection"]["properties"]["categories"]["$ref"], "#/components/schemas/Page_Category");
+    assert_eq!(schemas["Page_Category"]["properties"]["items"]["items"]["$ref"], "#/components/schemas/Category");
+    // Primitives are inlined, never registered as components
+    assert!(schemas.get("String").is_none());
+    assert!(schemas.get("f64").is_none());
+}
+
+#[test]
+fn test_nested_to_schema_types_are_registered() {
+    #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+    struct ParcelSize {
+        width: u32,
+    }
+
+    #[service_kit::api_dto]
+    struct Parcel {
+        size: ParcelSize,
+    }
+
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let spec = serde_json::to_value(&openapi).unwrap();
+
+    assert_eq!(spec["components"]["schemas"]["Parcel"]["properties"]["size"]["$ref"], "#/components/schemas/ParcelSize");
+    assert_eq!(spec["components"]["schemas"]["ParcelSize"]["properties"]["width"]["type"], "integer");
+}
+
+#[cfg(feature = "mcp")]
+#[test]
+fn test_mcp_input_schemas_have_no_dangling_references() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let router = service_kit::openapi_to_mcp::OpenApiMcpRouterBuilder::new().openapi(openapi).build::<()>().unwrap();
+
+    for tool in router.list_all() {
+        let schema = serde_json::Value::Object((*tool.input_schema).clone());
+        assert!(!schema.to_string().contains("#/components/"), "{} has a dangling $ref: {}", tool.name, schema);
+    }
+    let paged = router.list_all().into_iter().find(|tool| tool.name == "list_products_paged").unwrap();
+    assert_eq!(paged.input_schema["properties"]["status"]["oneOf"][1]["enum"], serde_json::json!(["draft", "active", "discontinued"]));
+}
@@ -0,0 +1,50 @@
+use axum::extract::Path;
+use axum::response::sse::{Event, Sse};
+use futures::stream::{self, Stream};
+use service_kit::handler::{ApiState, RawBody};
+use service_kit::{api, api_dto};
+use std::convert::Infallible;
+
+/// A single price update pushed over the price stream.
+#[api_dto]
+pub struct PriceTick {
+    pub product_id: String,
+    pub sequence: u32,
+    pub price: f64,
+}
+
+/// Stream price updates
+#[api(GET, "/v1/products/{id}/price-stream", responses((200, "One PriceTick per event", PriceTick)))]
+pub async fn stream_prices(Path(id): Path<String>) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let ticks = (1..=3u32).map(move |sequence| {
+        let tick = PriceTick { product_id: id.clone(), sequence, price: 99.99 + sequence as f64 };
+        Ok(Event::default().event("price").json_data(tick).expect("PriceTick serializes"))
+    });
+    Sse::new(stream::iter(ticks))
+}
+
+#[test]
+fn test_sse_response_is_documented_as_event_stream() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let spec = serde_json::to_value(&openapi).unwrap();
+    let content = &spec["paths"]["/v1/products/{id}/price-stream"]["get"]["responses"]["200"]["content"];
+
+    assert_eq!(content["text/event-stream"]["schema"]["$ref"], "#/components/schemas/PriceTick");
+    assert!(content.get("application/json").is_none());
+}
+
+#[tokio::test]
+async fn test_sse_handler_streams_events() {
+    let params = serde_json::json!({ "id": "prod-7" });
+    let response = __API_EXEC_STREAM_PRICES(&params, &ApiState::default(), RawBody::default()).await.unwrap();
+    assert_eq!(response.headers()["content-type"], service_kit::sse::EVENT_STREAM);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let mut parser = service_kit::sse::SseParser::new();
+    let events = parser.feed(&body);
+
+    assert_eq!(events.len(), 3);
+    assert_eq!(events[0].event.as_deref(), Some("price"));
+    let tick: serde_json::Value = serde_json::from_str(&events[2].data).unwrap();
+    assert_eq!(tick["productId"], "prod-7");
+    assert_eq!(tick["sequence"], 3);
+}
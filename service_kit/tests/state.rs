@@ -0,0 +1,42 @@
+use axum::extract::State;
+use axum::Json;
+use service_kit::error::Error;
+use service_kit::handler::{ApiState, RawBody};
+use service_kit::{api, api_dto};
+
+#[derive(Clone, Debug)]
+pub struct AppState {
+    pub service_name: String,
+}
+
+#[api_dto]
+pub struct Greeting {
+    pub message: String,
+}
+
+/// Get service info
+#[api(GET, "/v1/info")]
+pub async fn service_info(State(state): State<AppState>) -> Json<Greeting> {
+    Json(Greeting {
+        message: format!("Welcome to {}", state.service_name),
+    })
+}
+
+#[tokio::test]
+async fn test_state_is_passed_to_handler() {
+    let state = ApiState::new(AppState {
+        service_name: "Test Service".to_string(),
+    });
+    let response = __API_EXEC_SERVICE_INFO(&serde_json::json!({}), &state, RawBody::default()).await.unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json_value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json_value["message"], "Welcome to Test Service");
+}
+
+#[tokio::test]
+async fn test_missing_state_is_an_error() {
+    let result = __API_EXEC_SERVICE_INFO(&serde_json::json!({}), &ApiState::default(), RawBody::default()).await;
+
+    assert!(matches!(result, Err(Error::StateError(_))));
+}
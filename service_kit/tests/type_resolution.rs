@@ -0,0 +1,48 @@
+use axum::extract::Query;
+use axum::Json;
+use service_kit::{api, api_dto};
+use std::collections::HashMap;
+
+#[api_dto]
+pub struct Product {
+    pub id: String,
+}
+
+/// Parameters for adding two numbers.
+#[api_dto]
+pub struct AddParams {
+    pub a: f64,
+    pub b: f64,
+}
+
+/// List all products
+#[api(GET, "/v1/products")]
+pub async fn list_products() -> Json<Vec<Product>> {
+    Json(vec![Product { id: "prod-001".to_string() }])
+}
+
+/// Get the price of every product
+#[api(GET, "/v1/products/prices")]
+pub async fn get_prices() -> Json<HashMap<String, f64>> {
+    Json(HashMap::from([("prod-001".to_string(), 99.99)]))
+}
+
+/// Add two numbers
+#[api(GET, "/v1/add")]
+pub async fn add(Query(params): Query<AddParams>) -> Json<f64> {
+    Json(params.a + params.b)
+}
+
+#[test]
+fn test_container_return_types_are_resolved() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let spec = serde_json::to_value(&openapi).unwrap();
+    let schema_of = |path: &str| spec["paths"][path]["get"]["responses"]["200"]["content"]["application/json"]["schema"].clone();
+
+    assert_eq!(schema_of("/v1/products"), serde_json::json!({ "type": "array", "items": { "$ref": "#/components/schemas/Product" } }));
+    assert_eq!(
+        schema_of("/v1/products/prices"),
+        serde_json::json!({ "type": "object", "additionalProperties": { "type": "number", "format": "double" } })
+    );
+    assert_eq!(schema_of("/v1/add"), serde_json::json!({ "type": "number", "format": "double" }));
+}
@@ -0,0 +1,90 @@
+use axum::extract::{Path, Query};
+use axum::http::StatusCode;
+use axum::Json;
+use service_kit::error::{Error, Result};
+use service_kit::handler::{ApiState, RawBody};
+use service_kit::{api, api_dto};
+
+/// Query parameters for searching products.
+#[api_dto]
+pub struct ProductSearch {
+    #[validate(length(min = 1))]
+    pub q: String,
+}
+
+/// DTO for updating a product.
+#[api_dto]
+pub struct ProductUpdate {
+    #[validate(length(min = 1, max = 64))]
+    pub name: Option<String>,
+    #[validate(pattern = "^P-")]
+    pub product_code: Option<String>,
+    #[validate(range(min = 0.0))]
+    pub price: Option<f64>,
+}
+
+/// Search products
+#[api(GET, "/v1/products/search")]
+pub async fn search_products(Query(search): Query<ProductSearch>) -> Json<String> {
+    Json(search.q)
+}
+
+/// Update a product
+#[api(PATCH, "/v1/products/{id}")]
+pub async fn update_product(Path(id): Path<String>, Json(payload): Json<ProductUpdate>) -> Json<String> {
+    Json(format!("{} {}", id, payload.name.unwrap_or_default()))
+}
+
+#[test]
+fn test_validation_constraints_are_documented() {
+    let openapi = service_kit::openapi_utils::build_openapi_basic("Test", "0.0.0", "", "App");
+    let spec = serde_json::to_value(&openapi).unwrap();
+    let properties = &spec["components"]["schemas"]["ProductUpdate"]["properties"];
+
+    assert_eq!(properties["name"]["minLength"], 1);
+    assert_eq!(properties["name"]["maxLength"], 64);
+    assert_eq!(properties["productCode"]["pattern"], "^P-");
+    assert_eq!(properties["price"]["minimum"], 0.0);
+}
+
+#[tokio::test]
+async fn test_invalid_body_is_rejected_before_the_handler() {
+    let params = serde_json::json!({ "id": "prod-1", "name": "", "productCode": "X-1", "price": -1.0 });
+    let result = __API_EXEC_UPDATE_PRODUCT(&params, &ApiState::default(), RawBody::default()).await;
+    let Err(Error::Validation(errors)) = result else {
+        panic!("expected a validation error");
+    };
+    let rules: Vec<(&str, &str)> = errors.errors.iter().map(|e| (e.field.as_str(), e.rule)).collect();
+
+    assert_eq!(rules, vec![("name", "minLength"), ("price", "minimum"), ("productCode", "pattern")]);
+}
+
+#[tokio::test]
+async fn test_validation_failure_is_a_structured_422() {
+    let params = serde_json::json!({ "q": "" });
+    let error = __API_EXEC_SEARCH_PRODUCTS(&params, &ApiState::default(), RawBody::default()).await.unwrap_err();
+    let response = axum::response::IntoResponse::into_response(error);
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(body["code"], "validation_failed");
+    assert_eq!(body["errors"][0]["field"], "q");
+    assert_eq!(body["errors"][0]["rule"], "minLength");
+}
+
+#[test]
+fn test_validation_resolves_qualified_and_container_type_names() {
+    let invalid = serde_json::json!({ "price": -1.0 });
+    let field = |result: Result<()>| match result {
+        Err(Error::Validation(errors)) => errors.errors[0].field.clone(),
+        other => panic!("expected a validation error, got {:?}", other),
+    };
+
+    assert_eq!(field(service_kit::validation::validate("crate::dtos::ProductUpdate", &invalid)), "price");
+    assert_eq!(
+        field(service_kit::validation::validate("Vec<crate::dtos::ProductUpdate>", &serde_json::json!([{}, invalid]))),
+        "[1].price"
+    );
+    assert!(service_kit::validation::validate("Vec<crate::dtos::ProductUpdate>", &serde_json::json!([{}])).is_ok());
+}